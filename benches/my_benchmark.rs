@@ -1,5 +1,5 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use irange::{range::AnyRange, RangeSet};
+use irange::{eytzinger::EytzingerRangeSet, range::AnyRange, RangeSet};
 use rand::{seq::SliceRandom, thread_rng};
 
 fn criterion_benchmark(c: &mut Criterion) {
@@ -159,6 +159,16 @@ fn criterion_benchmark(c: &mut Criterion) {
         b.iter(|| range_regex_small_w.intersection(&range_regex_small_d))
     });
 
+    let dense = RangeSet::<u32>::new_from_iter((0..100_000).map(|i| i * 4..=i * 4 + 1));
+    let sparse = RangeSet::<u32>::new_from_iter((0..100).map(|i| i * 4000 + 2..=i * 4000 + 2));
+    c.bench_function("intersection_sparse", |b| {
+        b.iter(|| dense.intersection(&sparse))
+    });
+    c.bench_function("union_sparse", |b| b.iter(|| dense.union(&sparse)));
+
+    let head = RangeSet::<u32>::new_from_iter((0..10).map(|i| i * 4 + 1..=i * 4 + 2));
+    c.bench_function("union_tail", |b| b.iter(|| dense.union(&head)));
+
     c.bench_function("has_intersection", |b| {
         b.iter(|| range_regex_small_w.has_intersection(&range_regex_small_d))
     });
@@ -175,9 +185,21 @@ fn criterion_benchmark(c: &mut Criterion) {
         b.iter(|| range_regex_small_w.contains_all(&range_regex_small_d))
     });
 
+    c.bench_function("iter", |b| b.iter(|| range_regex_small_w.iter().count()));
+
     c.bench_function("contains", |b| {
         b.iter(|| range_regex_small_w.contains(43790))
     });
+
+    let range_small = RangeSet::<u32>(vec![10, 20, 30, 40, 50, 60, 70, 80]);
+    c.bench_function("contains_small", |b| {
+        b.iter(|| range_small.contains(std::hint::black_box(55)))
+    });
+
+    let eytzinger_regex_small_w = EytzingerRangeSet::from(&range_regex_small_w);
+    c.bench_function("contains_eytzinger", |b| {
+        b.iter(|| eytzinger_regex_small_w.contains(43790))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
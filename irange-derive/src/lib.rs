@@ -0,0 +1,73 @@
+//! Derive macro for the `irange::discriminant::Discriminants` trait, use it through the `derive` feature flag of `irange`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, Ident};
+
+const INTEGERS: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Implement `irange::discriminant::Discriminants` for a fieldless enum.
+///
+/// The type of the discriminants is the one given by `#[repr(...)]`, `isize` otherwise.
+#[proc_macro_derive(Discriminants)]
+pub fn derive_discriminants(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(
+            input.ident.span(),
+            "`Discriminants` can only be derived for enums",
+        ));
+    };
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(Error::new(
+            variant.fields.span(),
+            "`Discriminants` can only be derived for enums without fields",
+        ));
+    }
+
+    let repr = repr(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = data.variants.iter().map(|variant| &variant.ident);
+
+    Ok(quote! {
+        impl #impl_generics ::irange::discriminant::Discriminants for #name #ty_generics #where_clause {
+            type Repr = #repr;
+
+            fn discriminants() -> ::irange::RangeSet<#repr> {
+                ::irange::RangeSet::new_from_iter([
+                    #(#name::#variants as #repr..=#name::#variants as #repr),*
+                ])
+            }
+        }
+    })
+}
+
+/// Return the integer type given by `#[repr(...)]`, `isize` otherwise.
+fn repr(input: &DeriveInput) -> Result<Ident, Error> {
+    let mut repr = None;
+    for attribute in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attribute.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if INTEGERS.contains(&ident.to_string().as_str()) {
+                    repr = Some(ident.clone());
+                }
+            }
+            Ok(())
+        })?;
+    }
+    Ok(repr.unwrap_or_else(|| Ident::new("isize", input.ident.span())))
+}
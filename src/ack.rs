@@ -0,0 +1,159 @@
+use std::ops::RangeBounds;
+
+use crate::{integer::NumericInteger, range_to_bounds, RangeSet};
+
+/// A structure tracking the acknowledged values of a stream, such as the processed offsets of a partition.
+///
+/// The values are expected from a starting point, those below it are considered as already acknowledged.
+/// Since the values are mostly acknowledged in order, the marks collapse into a few ranges.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct AckTracker<T: NumericInteger> {
+    /// The first value expected.
+    start: T,
+    /// The values acknowledged, all greater than or equal to `start`.
+    marked: RangeSet<T>,
+}
+
+impl<T: NumericInteger> AckTracker<T> {
+    /// Create a new instance expecting the values from `start`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::ack::AckTracker;
+    ///
+    /// let mut offsets = AckTracker::<u64>::new(100);
+    /// offsets.mark(100);
+    /// offsets.mark_range(102..=104);
+    /// offsets.mark(107);
+    ///
+    /// assert_eq!(Some(100), offsets.highest_contiguous());
+    /// assert_eq!(RangeSet(vec![101, 101, 105, 106]), offsets.missing_below(107));
+    ///
+    /// offsets.mark(101);
+    /// assert_eq!(Some(104), offsets.highest_contiguous());
+    /// ```
+    pub fn new(start: T) -> AckTracker<T> {
+        AckTracker {
+            start,
+            marked: RangeSet::empty(),
+        }
+    }
+
+    /// Return the first value expected.
+    #[inline]
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    /// Return the values acknowledged from the start.
+    #[inline]
+    pub fn as_range_set(&self) -> &RangeSet<T> {
+        &self.marked
+    }
+
+    /// Acknowledge the given value, ignored if it is below the start.
+    pub fn mark(&mut self, value: T) {
+        if value >= self.start {
+            self.marked.entry(value).or_insert();
+        }
+    }
+
+    /// Acknowledge the values of the given range, ignoring the ones below the start.
+    pub fn mark_range<R: RangeBounds<T>>(&mut self, range: R) {
+        let (min, max) = range_to_bounds(&range);
+        if max >= self.start && min <= max {
+            self.marked = self.marked.union_range(min.max(self.start)..=max);
+        }
+    }
+
+    /// Return `true` if the given value has been acknowledged or is below the start.
+    #[inline]
+    pub fn is_marked(&self, value: T) -> bool {
+        value < self.start || self.marked.contains(value)
+    }
+
+    /// Return the highest value such that all the values from the start up to it are acknowledged, `None` if the start is not.
+    #[inline]
+    pub fn highest_contiguous(&self) -> Option<T> {
+        match self.marked.0[..] {
+            [min, max, ..] if min == self.start => Some(max),
+            _ => None,
+        }
+    }
+
+    /// Return the values from the start up to `watermark` excluded that are not acknowledged.
+    pub fn missing_below(&self, watermark: T) -> RangeSet<T> {
+        if watermark <= self.start {
+            return RangeSet::empty();
+        }
+        RangeSet::new_from_iter(self.marked.gaps_within(self.start..watermark))
+    }
+
+    /// Move the start to `watermark`, forgetting the acknowledged values below it, and return the values below it that were never acknowledged.
+    ///
+    /// It does nothing if `watermark` is not above the start.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::ack::AckTracker;
+    ///
+    /// let mut offsets = AckTracker::<u64>::new(0);
+    /// offsets.mark_range(0..10);
+    /// offsets.mark_range(12..20);
+    ///
+    /// assert_eq!(RangeSet(vec![10, 11]), offsets.compact_below(15));
+    /// assert_eq!(15, offsets.start());
+    /// assert_eq!(Some(19), offsets.highest_contiguous());
+    /// assert!(offsets.is_marked(10));
+    /// ```
+    pub fn compact_below(&mut self, watermark: T) -> RangeSet<T> {
+        let missing = self.missing_below(watermark);
+        if watermark > self.start {
+            self.marked = self.marked.difference_range(..watermark);
+            self.start = watermark;
+        }
+        missing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker() -> Result<(), String> {
+        let mut tracker = AckTracker::<u8>::new(10);
+        assert_eq!(None, tracker.highest_contiguous());
+        assert_eq!(RangeSet(vec![10, 19]), tracker.missing_below(20));
+        assert!(tracker.missing_below(10).is_empty());
+        assert!(tracker.is_marked(9));
+        assert!(!tracker.is_marked(10));
+
+        tracker.mark(5);
+        tracker.mark_range(0..=11);
+        tracker.mark_range(14..14);
+        tracker.mark(255);
+        assert_eq!(&RangeSet(vec![10, 11, 255, 255]), tracker.as_range_set());
+        assert_eq!(Some(11), tracker.highest_contiguous());
+        assert_eq!(RangeSet(vec![12, 254]), tracker.missing_below(255));
+        assert_eq!(RangeSet(vec![12, 254]), tracker.missing_below(u8::MAX));
+
+        tracker.mark_range(13..=20);
+        assert_eq!(RangeSet(vec![12, 12]), tracker.compact_below(15));
+        assert_eq!(Some(20), tracker.highest_contiguous());
+        assert!(tracker.compact_below(12).is_empty());
+        assert_eq!(15, tracker.start());
+
+        assert_eq!(RangeSet(vec![21, 29]), tracker.compact_below(30));
+        assert_eq!(None, tracker.highest_contiguous());
+        assert_eq!(&RangeSet(vec![255, 255]), tracker.as_range_set());
+        tracker.mark_range(30..=254);
+        assert_eq!(Some(255), tracker.highest_contiguous());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use crate::{integer::NumericInteger, RangeSet};
+
+/// The partition of the domain into the maximal classes of values that belong to exactly the same given sets.
+///
+/// This is the alphabet compression used by DFA-based regex and lexer engines: every transition can be computed on one representative per class instead of every value.
+/// Code points can be partitioned with `RangeSet<u32>`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct EquivalenceClasses<T: NumericInteger> {
+    classes: Vec<RangeSet<T>>,
+    members: Vec<Vec<usize>>,
+}
+
+impl<T: NumericInteger> EquivalenceClasses<T> {
+    /// Compute the equivalence classes of the given sets.
+    ///
+    /// The classes are numbered by their smallest value, and together they cover the whole domain.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::alphabet::EquivalenceClasses;
+    ///
+    /// let digits = RangeSet::<u8>::new_from_range(b'0'..=b'9');
+    /// let hex = RangeSet::<u8>::new_from_iter([b'0'..=b'9', b'a'..=b'f']);
+    ///
+    /// let classes = EquivalenceClasses::new(&[digits, hex]);
+    /// assert_eq!(3, classes.len());
+    /// assert_eq!(&RangeSet::new_from_range(b'a'..=b'f'), classes.class(2));
+    /// assert_eq!(&[1], classes.members(0));
+    /// assert_eq!(&[1, 2], classes.members(1));
+    /// ```
+    pub fn new(sets: &[RangeSet<T>]) -> EquivalenceClasses<T> {
+        let mut boundaries = vec![T::min_value()];
+        for set in sets {
+            for range in set.0.chunks_exact(2) {
+                boundaries.push(range[0]);
+                if range[1] < T::max_value() {
+                    boundaries.push(range[1] + T::one());
+                }
+            }
+        }
+        boundaries.sort();
+        boundaries.dedup();
+
+        let mut ids: HashMap<Vec<usize>, usize> = HashMap::new();
+        let mut segments: Vec<Vec<(T, T)>> = vec![];
+        for (k, &min) in boundaries.iter().enumerate() {
+            let max = match boundaries.get(k + 1) {
+                Some(&next) => next - T::one(),
+                None => T::max_value(),
+            };
+            let signature: Vec<usize> =
+                (0..sets.len()).filter(|&i| sets[i].contains(min)).collect();
+            let id = *ids.entry(signature).or_insert_with(|| {
+                segments.push(vec![]);
+                segments.len() - 1
+            });
+            segments[id].push((min, max));
+        }
+
+        let mut members = vec![vec![]; sets.len()];
+        for (signature, id) in ids {
+            for i in signature {
+                members[i].push(id);
+            }
+        }
+        for ids in &mut members {
+            ids.sort();
+        }
+
+        EquivalenceClasses {
+            classes: segments
+                .into_iter()
+                .map(|segments| {
+                    RangeSet::new_from_iter(segments.into_iter().map(|(min, max)| min..=max))
+                })
+                .collect(),
+            members,
+        }
+    }
+
+    /// Return the number of classes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    /// Return `true` if there is no class, which never happens since the classes cover the whole domain.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    /// Return the values of the class with the given ID.
+    #[inline]
+    pub fn class(&self, id: usize) -> &RangeSet<T> {
+        &self.classes[id]
+    }
+
+    /// Return the classes, indexed by their ID.
+    #[inline]
+    pub fn classes(&self) -> &[RangeSet<T>] {
+        &self.classes
+    }
+
+    /// Return the IDs of the classes whose union is the given set, in increasing order.
+    #[inline]
+    pub fn members(&self, set: usize) -> &[usize] {
+        &self.members[set]
+    }
+
+    /// Return the ID of the class containing the given value.
+    pub fn class_of(&self, value: T) -> usize {
+        self.classes
+            .iter()
+            .position(|class| class.contains(value))
+            .expect("the classes cover the whole domain")
+    }
+}
+
+impl EquivalenceClasses<u8> {
+    /// Return the class ID of every byte.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::alphabet::EquivalenceClasses;
+    ///
+    /// let classes = EquivalenceClasses::new(&[RangeSet::<u8>::new_from_range(b'a'..=b'z')]);
+    /// let table = classes.byte_table();
+    /// assert_eq!(table[b'a' as usize], table[b'z' as usize]);
+    /// assert_ne!(table[b'a' as usize], table[b'A' as usize]);
+    /// ```
+    pub fn byte_table(&self) -> [usize; 256] {
+        let mut table = [0; 256];
+        for (id, class) in self.classes.iter().enumerate() {
+            for byte in class.iter() {
+                table[usize::from(byte)] = id;
+            }
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classes() -> Result<(), String> {
+        let classes = EquivalenceClasses::<u8>::new(&[]);
+        assert_eq!(vec![RangeSet::total()], classes.classes());
+
+        let sets = [
+            RangeSet::<u8>(vec![0, 10, 20, 30]),
+            RangeSet::<u8>(vec![5, 25]),
+            RangeSet::<u8>(vec![250, 255]),
+            RangeSet::<u8>::empty(),
+        ];
+        let classes = EquivalenceClasses::new(&sets);
+        assert_eq!(
+            vec![
+                RangeSet(vec![0, 4, 26, 30]),
+                RangeSet(vec![5, 10, 20, 25]),
+                RangeSet(vec![11, 19]),
+                RangeSet(vec![31, 249]),
+                RangeSet(vec![250, 255]),
+            ],
+            classes.classes()
+        );
+        assert_eq!(&[0, 1], classes.members(0));
+        assert_eq!(&[1, 2], classes.members(1));
+        assert_eq!(&[4], classes.members(2));
+        assert!(classes.members(3).is_empty());
+
+        let table = classes.byte_table();
+        for value in 0..=255u8 {
+            let id = classes.class_of(value);
+            assert_eq!(id, table[usize::from(value)]);
+            for (i, set) in sets.iter().enumerate() {
+                assert_eq!(set.contains(value), classes.members(i).contains(&id));
+            }
+        }
+
+        Ok(())
+    }
+}
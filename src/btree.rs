@@ -0,0 +1,361 @@
+use std::{
+    collections::BTreeMap,
+    ops::{RangeBounds, RangeInclusive},
+};
+
+use crate::{
+    integer::{Measure, NumericInteger},
+    range_to_bounds, stream, RangeSet,
+};
+
+/// A set of ranges stored in a `BTreeMap` from the start to the end of each range.
+///
+/// Inserting or removing a range takes `O(log n + k)`, `k` being the number of ranges merged or removed, instead of moving all the following bounds of a `RangeSet`.
+/// It is suited to sets of millions of ranges modified in the middle, and can be converted to and from a `RangeSet` for the set operations.
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct BTreeRangeSet<T: NumericInteger> {
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: NumericInteger> BTreeRangeSet<T> {
+    /// Create a new instance that does not contain any value.
+    #[inline]
+    pub fn empty() -> BTreeRangeSet<T> {
+        BTreeRangeSet {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// Create a new instance that contains all possible values.
+    #[inline]
+    pub fn total() -> BTreeRangeSet<T> {
+        BTreeRangeSet {
+            ranges: BTreeMap::from([(T::min_value(), T::max_value())]),
+        }
+    }
+
+    /// Return the number of ranges.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Return `true` if it contains all the possible values.
+    #[inline]
+    pub fn is_total(&self) -> bool {
+        self.ranges.len() == 1 && self.ranges.get(&T::min_value()) == Some(&T::max_value())
+    }
+
+    /// Return `true` if it contains the given value.
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.containing_range(value).is_some()
+    }
+
+    /// Return the range containing the given value.
+    pub fn containing_range(&self, value: T) -> Option<RangeInclusive<T>> {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .filter(|(_, &end)| end >= value)
+            .map(|(&start, &end)| start..=end)
+    }
+
+    /// Return an iterator over the ranges, in order.
+    pub fn ranges(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..=end)
+    }
+
+    /// Return an iterator over the ranges having a common value with the given window, in order.
+    pub fn ranges_overlapping<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        let (min, max) = range_to_bounds(&range);
+        let start = self
+            .containing_range(min)
+            .map_or(min, |range| *range.start());
+        let ranges = (min <= max).then(|| self.ranges.range(start..=max));
+
+        ranges
+            .into_iter()
+            .flatten()
+            .map(|(&start, &end)| start..=end)
+    }
+
+    /// Return an iterator over all the values contained, in order.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        stream::values(self.ranges())
+    }
+
+    /// Return an iterator over the minimum of each range, in order.
+    pub fn starts(&self) -> impl DoubleEndedIterator<Item = T> + '_ {
+        self.ranges.keys().copied()
+    }
+
+    /// Return an iterator over the maximum of each range, in order.
+    pub fn ends(&self) -> impl DoubleEndedIterator<Item = T> + '_ {
+        self.ranges.values().copied()
+    }
+
+    /// Return the distance between the given value and the closest value contained, `0` if it is contained and `None` if the set is empty.
+    pub fn distance_to(&self, value: T) -> Option<T::DistanceType> {
+        let below = self.ranges.range(..=value).next_back().map(|(_, &end)| {
+            if end >= value {
+                T::distance_from_u128(0)
+            } else {
+                T::distance(end, value)
+            }
+        });
+        let above = self
+            .ranges
+            .range(value..)
+            .find(|(&start, _)| start > value)
+            .map(|(&start, _)| T::distance(value, start));
+        match (below, above) {
+            (Some(below), Some(above)) => Some(std::cmp::min(below, above)),
+            (below, above) => below.or(above),
+        }
+    }
+
+    /// Return `true` if it contains all the values of the given set.
+    pub fn contains_all(&self, that: &BTreeRangeSet<T>) -> bool {
+        that.ranges().all(|range| {
+            self.containing_range(*range.start())
+                .is_some_and(|containing| containing.end() >= range.end())
+        })
+    }
+
+    /// Return `true` if it has at least one value in common with the given set.
+    pub fn has_intersection(&self, that: &BTreeRangeSet<T>) -> bool {
+        stream::merge_intersection(self.ranges(), that.ranges())
+            .next()
+            .is_some()
+    }
+
+    /// Return the number of values contained measured with the given type, `None` if it does not fit.
+    pub fn covered_len<M: Measure<T>>(&self) -> Option<M> {
+        self.ranges
+            .iter()
+            .try_fold(M::zero(), |len, (&start, &end)| {
+                len.checked_sum(M::measure(start, end)?)
+            })
+    }
+
+    /// Return the number of values contained within the given window, saturating at `u128::MAX`.
+    pub fn count_in<R: RangeBounds<T>>(&self, range: R) -> u128 {
+        let (min, max) = range_to_bounds(&range);
+        self.ranges_overlapping(min..=max)
+            .map(|range| {
+                let (range_min, range_max) = range.into_inner();
+                let extent =
+                    T::distance(std::cmp::max(range_min, min), std::cmp::min(range_max, max));
+                T::distance_to_u128(extent)
+            })
+            .fold(0u128, |count, extent| {
+                count.saturating_add(extent).saturating_add(1)
+            })
+    }
+
+    /// Return an iterator over the ranges of values not contained within the given window, in order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::btree::BTreeRangeSet;
+    ///
+    /// let range = BTreeRangeSet::<u32>::from(&RangeSet(vec![0, 1, 5, 6, 10, 12, 20, 25]));
+    ///
+    /// let gaps: Vec<_> = range.gaps_within(4..=15).collect();
+    /// assert_eq!(vec![4..=4, 7..=9, 13..=15], gaps);
+    /// assert_eq!(3, range.count_in(6..=11));
+    /// assert_eq!(Some(2), range.distance_to(8));
+    /// ```
+    pub fn gaps_within<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        let (min, max) = range_to_bounds(&range);
+        stream::merge_difference(
+            (min <= max).then_some(min..=max),
+            self.ranges_overlapping(min..=max),
+        )
+    }
+
+    /// Insert the values of the given range, merging the ranges it overlaps or touches.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::btree::BTreeRangeSet;
+    ///
+    /// let mut range = BTreeRangeSet::<u32>::from(&RangeSet(vec![0, 4, 10, 14, 20, 24]));
+    ///
+    /// range.insert_range(5..=12);
+    /// assert_eq!(RangeSet(vec![0, 14, 20, 24]), RangeSet::from(&range));
+    ///
+    /// range.remove_range(2..22);
+    /// assert_eq!(RangeSet(vec![0, 1, 22, 24]), RangeSet::from(&range));
+    /// ```
+    pub fn insert_range<R: RangeBounds<T>>(&mut self, range: R) {
+        let (mut min, mut max) = range_to_bounds(&range);
+        if min > max {
+            return;
+        }
+
+        if let Some((&start, &end)) = self.ranges.range(..=min).next_back() {
+            if end >= min || end + T::one() == min {
+                if end >= max {
+                    return;
+                }
+                min = start;
+            }
+        }
+
+        let limit = if max == T::max_value() {
+            max
+        } else {
+            max + T::one()
+        };
+        let merged: Vec<T> = self
+            .ranges
+            .range(min..=limit)
+            .map(|(&start, _)| start)
+            .collect();
+        for start in merged {
+            if let Some(end) = self.ranges.remove(&start) {
+                max = max.max(end);
+            }
+        }
+
+        self.ranges.insert(min, max);
+    }
+
+    /// Remove the values of the given range, splitting the range containing it if needed.
+    pub fn remove_range<R: RangeBounds<T>>(&mut self, range: R) {
+        let (min, max) = range_to_bounds(&range);
+        if min > max {
+            return;
+        }
+
+        if let Some((&start, &end)) = self.ranges.range(..min).next_back() {
+            if end >= min {
+                self.ranges.insert(start, min - T::one());
+                if end > max {
+                    self.ranges.insert(max + T::one(), end);
+                    return;
+                }
+            }
+        }
+
+        let removed: Vec<T> = self
+            .ranges
+            .range(min..=max)
+            .map(|(&start, _)| start)
+            .collect();
+        for start in removed {
+            if let Some(end) = self.ranges.remove(&start) {
+                if end > max {
+                    self.ranges.insert(max + T::one(), end);
+                }
+            }
+        }
+    }
+}
+
+impl<T: NumericInteger> From<&RangeSet<T>> for BTreeRangeSet<T> {
+    fn from(value: &RangeSet<T>) -> Self {
+        BTreeRangeSet {
+            ranges: value.starts().zip(value.ends()).collect(),
+        }
+    }
+}
+
+impl<T: NumericInteger> From<&BTreeRangeSet<T>> for RangeSet<T> {
+    fn from(value: &BTreeRangeSet<T>) -> Self {
+        let mut bounds = Vec::with_capacity(value.len() * 2);
+        for (&start, &end) in &value.ranges {
+            bounds.push(start);
+            bounds.push(end);
+        }
+        RangeSet(bounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_mutations() -> Result<(), String> {
+        let mut rng = StdRng::seed_from_u64(191);
+        let mut expected = RangeSet::<u8>::empty();
+        let mut range = BTreeRangeSet::<u8>::empty();
+        for _ in 0..2000 {
+            let (a, b) = (rng.gen_range(0..=255), rng.gen_range(0..=255));
+            let (min, max) = (a.min(b), a.max(b));
+            if rng.gen_bool(0.5) {
+                expected = expected.union_range(min..=max);
+                range.insert_range(min..=max);
+            } else {
+                expected = expected.difference_range(min..=max);
+                range.remove_range(min..=max);
+            }
+            assert_eq!(expected, RangeSet::from(&range));
+            assert_eq!(range, BTreeRangeSet::from(&expected));
+
+            let (a, b) = (rng.gen_range(0..=255), rng.gen_range(0..=255));
+            assert_eq!(
+                expected.ranges_overlapping(a..=b).collect::<Vec<_>>(),
+                range.ranges_overlapping(a..=b).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                expected.containing_range(a).map(|(_, range)| range),
+                range.containing_range(a)
+            );
+            assert_eq!(
+                expected.gaps_within(a..=b).collect::<Vec<_>>(),
+                range.gaps_within(a..=b).collect::<Vec<_>>()
+            );
+            assert_eq!(expected.count_in(a..=b), range.count_in(a..=b));
+            assert_eq!(expected.distance_to(a), range.distance_to(a));
+            assert_eq!(expected.covered_len::<u16>(), range.covered_len());
+            assert!(expected.iter().eq(range.iter()));
+
+            let other = RangeSet::new_from_range(a.min(b)..=a.max(b));
+            assert_eq!(
+                expected.contains_all(&other),
+                range.contains_all(&BTreeRangeSet::from(&other))
+            );
+            assert_eq!(
+                expected.has_intersection(&other),
+                range.has_intersection(&BTreeRangeSet::from(&other))
+            );
+        }
+
+        let mut range = BTreeRangeSet::<i8>::empty();
+        range.insert_range(..);
+        assert!(range.is_total());
+        range.remove_range(i8::MIN..=i8::MIN);
+        range.remove_range(i8::MAX..);
+        assert_eq!(RangeSet(vec![-127, 126]), RangeSet::from(&range));
+        range.insert_range(10..0);
+        range.remove_range(10..0);
+        assert!(range.contains(0));
+        assert!(!range.contains(i8::MIN));
+        assert_eq!(1, range.len());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+//! Parsing of command line arguments such as `--ids 1-5,8,100-200` with `clap`.
+
+use std::marker::PhantomData;
+
+use clap::{
+    builder::{TypedValueParser, ValueParserFactory},
+    error::ErrorKind,
+    Arg, Command, Error,
+};
+
+use crate::{integer::NumericInteger, RangeSet};
+
+/// A `clap` value parser reading comma separated values and ranges such as `1-5,8,100-200`, in any order and possibly overlapping.
+///
+/// It is the default value parser of `RangeSet`, so no attribute is needed with the derive API.
+///
+/// # Example:
+///
+/// ```
+/// use clap::Parser;
+/// use irange::RangeSet;
+///
+/// #[derive(Parser, Debug)]
+/// struct Cli {
+///     #[arg(long)]
+///     ids: RangeSet<u64>,
+/// }
+///
+/// let cli = Cli::try_parse_from(["cli", "--ids", "1-5,8,100-200"]).unwrap();
+/// assert_eq!(RangeSet(vec![1, 5, 8, 8, 100, 200]), cli.ids);
+///
+/// let error = Cli::try_parse_from(["cli", "--ids", "1-5,x"]).unwrap_err();
+/// assert!(error.to_string().contains("invalid value '1-5,x' for '--ids <IDS>': invalid range `x`"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct RangeSetValueParser<T: NumericInteger>(PhantomData<T>);
+
+impl<T: NumericInteger> RangeSetValueParser<T> {
+    pub fn new() -> RangeSetValueParser<T> {
+        RangeSetValueParser(PhantomData)
+    }
+}
+
+impl<T: NumericInteger> Default for RangeSetValueParser<T> {
+    fn default() -> Self {
+        RangeSetValueParser::new()
+    }
+}
+
+impl<T: NumericInteger + Send + Sync + 'static> TypedValueParser for RangeSetValueParser<T> {
+    type Value = RangeSet<T>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+        RangeSet::parse_compact(value).map_err(|error| {
+            let arg = arg.map_or_else(|| "...".to_string(), |arg| arg.to_string());
+            Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid value '{value}' for '{arg}': {error}\n"),
+            )
+            .with_cmd(cmd)
+        })
+    }
+}
+
+impl<T: NumericInteger + Send + Sync + 'static> ValueParserFactory for RangeSet<T> {
+    type Parser = RangeSetValueParser<T>;
+
+    fn value_parser() -> Self::Parser {
+        RangeSetValueParser::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_parser() -> Result<(), String> {
+        let command = Command::new("cli").arg(
+            Arg::new("ids")
+                .long("ids")
+                .value_parser(RangeSetValueParser::<i32>::new()),
+        );
+
+        for (value, expected) in [
+            ("1-5,8,100-200", RangeSet(vec![1, 5, 8, 8, 100, 200])),
+            (" 8, 1 - 5 ,3-6", RangeSet(vec![1, 6, 8, 8])),
+            ("-10--5,-1", RangeSet(vec![-10, -5, -1, -1])),
+            ("", RangeSet::empty()),
+        ] {
+            let matches = command
+                .clone()
+                .try_get_matches_from(["cli".to_string(), format!("--ids={value}")])
+                .map_err(|e| e.to_string())?;
+            assert_eq!(Some(&expected), matches.get_one::<RangeSet<i32>>("ids"));
+        }
+
+        for (value, message) in [
+            (
+                "5-1",
+                "invalid range `5-1`: the minimum 5 is greater than the maximum 1",
+            ),
+            ("1-x", "invalid range `1-x`: invalid digit found in string"),
+            ("1,é", "invalid range `é`: invalid digit found in string"),
+        ] {
+            let error = command
+                .clone()
+                .try_get_matches_from(["cli".to_string(), format!("--ids={value}")])
+                .unwrap_err();
+            assert_eq!(ErrorKind::ValueValidation, error.kind());
+            assert!(error.to_string().contains(message), "{error}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use std::ops::RangeInclusive;
+
+use crate::{integer::NumericInteger, RangeSet};
+
+/// A `RangeSet` storing the starts and the ends of its ranges in two separate arrays.
+///
+/// Looking up a value only searches the starts, so each probe of the binary search touches half as much memory as with the interleaved bounds of `RangeSet`.
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct ColumnarRangeSet<T: NumericInteger> {
+    starts: Vec<T>,
+    ends: Vec<T>,
+}
+
+impl<T: NumericInteger> ColumnarRangeSet<T> {
+    /// Return the smallest value of each range, in increasing order.
+    #[inline]
+    pub fn starts(&self) -> &[T] {
+        &self.starts
+    }
+
+    /// Return the largest value of each range, in increasing order.
+    #[inline]
+    pub fn ends(&self) -> &[T] {
+        &self.ends
+    }
+
+    /// Return the number of ranges.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Return `true` if it contains the given value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::columnar::ColumnarRangeSet;
+    ///
+    /// let range = ColumnarRangeSet::from(&RangeSet::<u32>(vec![2, 4, 10, 20]));
+    /// assert_eq!(&[2, 10], range.starts());
+    /// assert_eq!(&[4, 20], range.ends());
+    /// assert!(range.contains(15));
+    /// assert!(!range.contains(5));
+    /// ```
+    pub fn contains(&self, value: T) -> bool {
+        let position = self.starts.partition_point(|&start| start <= value);
+        position > 0 && value <= self.ends[position - 1]
+    }
+
+    /// Return an iterator over the ranges, in order.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.starts
+            .iter()
+            .zip(&self.ends)
+            .map(|(&start, &end)| start..=end)
+    }
+}
+
+impl<T: NumericInteger> From<&RangeSet<T>> for ColumnarRangeSet<T> {
+    fn from(value: &RangeSet<T>) -> Self {
+        ColumnarRangeSet {
+            starts: value.starts().collect(),
+            ends: value.ends().collect(),
+        }
+    }
+}
+
+impl<T: NumericInteger> From<&ColumnarRangeSet<T>> for RangeSet<T> {
+    fn from(value: &ColumnarRangeSet<T>) -> Self {
+        let mut bounds = Vec::with_capacity(value.len() * 2);
+        for (&start, &end) in value.starts.iter().zip(&value.ends) {
+            bounds.push(start);
+            bounds.push(end);
+        }
+        RangeSet(bounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> Result<(), String> {
+        let range = RangeSet::<i16>(vec![i16::MIN, -5, 0, 0, 10, i16::MAX]);
+        let columnar = ColumnarRangeSet::from(&range);
+        assert_eq!(&[i16::MIN, 0, 10], columnar.starts());
+        assert_eq!(&[-5, 0, i16::MAX], columnar.ends());
+        assert_eq!(3, columnar.len());
+        assert_eq!(
+            vec![i16::MIN..=-5, 0..=0, 10..=i16::MAX],
+            columnar.ranges().collect::<Vec<_>>()
+        );
+        assert_eq!(range, RangeSet::from(&columnar));
+
+        let empty = ColumnarRangeSet::from(&RangeSet::<u8>::empty());
+        assert!(empty.is_empty());
+        assert!(!empty.contains(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains() -> Result<(), String> {
+        let range = RangeSet::<u8>(vec![0, 3, 10, 12, 20, 20, 250, 255]);
+        let columnar = ColumnarRangeSet::from(&range);
+        for value in 0..=255 {
+            assert_eq!(range.contains(value), columnar.contains(value));
+        }
+
+        Ok(())
+    }
+}
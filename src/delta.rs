@@ -0,0 +1,160 @@
+use crate::{integer::NumericInteger, RangeSet};
+
+/// The values added to and removed from a `RangeSet` by a change.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct RangeSetDelta<T: NumericInteger> {
+    /// The values contained after the change but not before.
+    pub added: RangeSet<T>,
+    /// The values contained before the change but not after.
+    pub removed: RangeSet<T>,
+}
+
+impl<T: NumericInteger> RangeSetDelta<T> {
+    /// Compute the change turning `old` into `new`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::delta::RangeSetDelta;
+    ///
+    /// let old = RangeSet::<u32>(vec![0, 9, 20, 29]);
+    /// let new = RangeSet::<u32>(vec![5, 24]);
+    ///
+    /// let delta = RangeSetDelta::between(&old, &new);
+    /// assert_eq!(RangeSet(vec![10, 19]), delta.added);
+    /// assert_eq!(RangeSet(vec![0, 4, 25, 29]), delta.removed);
+    /// assert_eq!(new, delta.apply(&old));
+    /// assert_eq!(old, delta.inverse().apply(&new));
+    /// ```
+    pub fn between(old: &RangeSet<T>, new: &RangeSet<T>) -> RangeSetDelta<T> {
+        RangeSetDelta {
+            added: new.difference(old),
+            removed: old.difference(new),
+        }
+    }
+
+    /// Return `true` if the change does not add nor remove any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Return the given `RangeSet` with the change applied.
+    pub fn apply(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        set.union(&self.added).difference(&self.removed)
+    }
+
+    /// Return the change reverting this one.
+    pub fn inverse(&self) -> RangeSetDelta<T> {
+        RangeSetDelta {
+            added: self.removed.clone(),
+            removed: self.added.clone(),
+        }
+    }
+}
+
+/// Implement the mutations of a wrapper holding a `RangeSet<T>` in its field `set`.
+///
+/// The change made by each mutation is computed from its operand, without comparing the sets before and after,
+/// and passed to the method `changed(&mut self, delta: RangeSetDelta<T>)` of the wrapper if it is not empty.
+macro_rules! impl_delta_mutations {
+    () => {
+        /// Add the values of the given range.
+        pub fn insert_range<R: std::ops::RangeBounds<T>>(&mut self, range: R) {
+            let (min, max) = $crate::range_to_bounds(&range);
+            if min > max {
+                return;
+            }
+            let added = RangeSet::new_from_iter(self.set.gaps_within(min..=max));
+            self.set = self.set.union_range(min..=max);
+            self.changed(RangeSetDelta {
+                added,
+                removed: RangeSet::empty(),
+            });
+        }
+
+        /// Remove the values of the given range.
+        pub fn remove_range<R: std::ops::RangeBounds<T>>(&mut self, range: R) {
+            let (min, max) = $crate::range_to_bounds(&range);
+            let removed = self.set.intersection_range(min..=max);
+            self.set = self.set.difference_range(min..=max);
+            self.changed(RangeSetDelta {
+                added: RangeSet::empty(),
+                removed,
+            });
+        }
+
+        /// Replace with the union with the given `RangeSet`.
+        pub fn union_with(&mut self, that: &RangeSet<T>) {
+            let added = that.difference(&self.set);
+            self.set = self.set.union(&added);
+            self.changed(RangeSetDelta {
+                added,
+                removed: RangeSet::empty(),
+            });
+        }
+
+        /// Replace with the intersection with the given `RangeSet`.
+        pub fn intersect_with(&mut self, that: &RangeSet<T>) {
+            let removed = self.set.difference(that);
+            self.set = self.set.intersection(that);
+            self.changed(RangeSetDelta {
+                added: RangeSet::empty(),
+                removed,
+            });
+        }
+
+        /// Replace with the difference with the given `RangeSet`.
+        pub fn difference_with(&mut self, that: &RangeSet<T>) {
+            let removed = self.set.intersection(that);
+            self.set = self.set.difference(&removed);
+            self.changed(RangeSetDelta {
+                added: RangeSet::empty(),
+                removed,
+            });
+        }
+
+        /// Replace with the complement.
+        pub fn complement(&mut self) {
+            let added = self.set.complement();
+            let removed = std::mem::replace(&mut self.set, added.clone());
+            self.changed(RangeSetDelta { added, removed });
+        }
+
+        /// Replace with the given `RangeSet`.
+        pub fn replace(&mut self, new: RangeSet<T>) {
+            let delta = RangeSetDelta::between(&self.set, &new);
+            self.set = new;
+            self.changed(delta);
+        }
+    };
+}
+
+pub(crate) use impl_delta_mutations;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_between() -> Result<(), String> {
+        let sets = [
+            RangeSet::<i8>::empty(),
+            RangeSet::<i8>::total(),
+            RangeSet::<i8>(vec![-128, -100, 0, 0, 10, 127]),
+            RangeSet::<i8>(vec![-110, 5, 100, 120]),
+        ];
+        for old in &sets {
+            for new in &sets {
+                let delta = RangeSetDelta::between(old, new);
+                assert_eq!(old == new, delta.is_empty());
+                assert!(!delta.added.has_intersection(&delta.removed));
+                assert_eq!(new, &delta.apply(old));
+                assert_eq!(old, &delta.inverse().apply(new));
+            }
+        }
+
+        Ok(())
+    }
+}
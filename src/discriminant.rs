@@ -0,0 +1,102 @@
+use crate::{integer::NumericInteger, RangeSet};
+
+#[cfg(feature = "derive")]
+pub use irange_derive::Discriminants;
+
+/// A fieldless enum whose discriminants can be collected into a `RangeSet`.
+///
+/// It can be derived with the `derive` feature flag, the discriminants having the type given by `#[repr(...)]` or `isize` otherwise.
+///
+/// # Example:
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use irange::{range_set_of, RangeSet};
+/// use irange::discriminant::Discriminants;
+///
+/// #[derive(Discriminants)]
+/// #[repr(u16)]
+/// enum ErrorCode {
+///     NotFound = 404,
+///     Gone = 410,
+///     LengthRequired,
+///     InternalError = 500,
+/// }
+///
+/// let known: RangeSet<u16> = range_set_of!(ErrorCode);
+/// assert_eq!(RangeSet(vec![404, 404, 410, 411, 500, 500]), known);
+/// assert!(known.contains(411));
+/// # }
+/// ```
+pub trait Discriminants {
+    /// The type of the discriminants.
+    type Repr: NumericInteger;
+
+    /// Return the set of the discriminants of the variants.
+    fn discriminants() -> RangeSet<Self::Repr>;
+}
+
+/// Return the `RangeSet` of the discriminants of the given enum implementing `irange::discriminant::Discriminants`.
+#[macro_export]
+macro_rules! range_set_of {
+    ($enum:ty) => {
+        <$enum as $crate::discriminant::Discriminants>::discriminants()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discriminants() -> Result<(), String> {
+        #[allow(dead_code)]
+        #[repr(i8)]
+        enum Manual {
+            Low = -3,
+            Zero = 0,
+            One,
+        }
+
+        impl Discriminants for Manual {
+            type Repr = i8;
+
+            fn discriminants() -> RangeSet<i8> {
+                RangeSet::new_from_iter([-3..=-3, 0..=1])
+            }
+        }
+
+        assert_eq!(RangeSet(vec![-3, -3, 0, 1]), range_set_of!(Manual));
+
+        #[cfg(feature = "derive")]
+        {
+            #[allow(dead_code)]
+            #[derive(Discriminants)]
+            enum Implicit {
+                A,
+                B,
+                C = 10,
+                D,
+            }
+            assert_eq!(
+                RangeSet::<isize>(vec![0, 1, 10, 11]),
+                range_set_of!(Implicit)
+            );
+
+            #[allow(dead_code)]
+            #[derive(Discriminants)]
+            #[repr(u8)]
+            enum Reserved {
+                First = 255,
+                Second = 0,
+            }
+            assert_eq!(
+                RangeSet::<u8>(vec![0, 0, 255, 255]),
+                range_set_of!(Reserved)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use core::fmt::{Binary, Display, Formatter, LowerHex, Octal, Result, UpperHex};
+
+use crate::{integer::NumericInteger, RangeSet};
+
+/// A helper to display a `RangeSet` in mathematical interval notation, such as `[2,4] ∪ [7,9]`.
+///
+/// Constructed with `RangeSet::display_math`.
+pub struct MathDisplay<'a, T: NumericInteger> {
+    range_set: &'a RangeSet<T>,
+    half_open: bool,
+}
+
+impl<'a, T: NumericInteger> MathDisplay<'a, T> {
+    pub(crate) fn new(range_set: &'a RangeSet<T>) -> MathDisplay<'a, T> {
+        MathDisplay {
+            range_set,
+            half_open: false,
+        }
+    }
+
+    /// Display the ranges as half-open intervals such as `[7,10)`.
+    ///
+    /// A range ending on the maximum value of the type is still displayed as a closed interval.
+    pub fn half_open(mut self) -> MathDisplay<'a, T> {
+        self.half_open = true;
+        self
+    }
+
+    /// Display the ranges as closed intervals such as `[7,9]`, which is the default.
+    pub fn closed(mut self) -> MathDisplay<'a, T> {
+        self.half_open = false;
+        self
+    }
+}
+
+impl<T: NumericInteger> Display for MathDisplay<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let bounds = &self.range_set.0;
+        if bounds.is_empty() {
+            return write!(f, "∅");
+        }
+
+        for i in (0..bounds.len()).step_by(2) {
+            let (min, max) = (bounds[i], bounds[i + 1]);
+            if i > 0 {
+                write!(f, " ∪ ")?;
+            }
+            if self.half_open && max < T::max_value() {
+                write!(f, "[{},{})", min, max + T::one())?;
+            } else {
+                write!(f, "[{},{}]", min, max)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A helper to display a `RangeSet` in the format of `Display` with a bounded number of ranges, such as `[ 0..=1 4..=5 ... 98..=99 ] (40 ranges)`.
+///
+/// Constructed with `RangeSet::display_limited`.
+pub struct LimitedDisplay<'a, T: NumericInteger> {
+    range_set: &'a RangeSet<T>,
+    max_ranges: usize,
+}
+
+impl<'a, T: NumericInteger> LimitedDisplay<'a, T> {
+    pub(crate) fn new(range_set: &'a RangeSet<T>, max_ranges: usize) -> LimitedDisplay<'a, T> {
+        LimitedDisplay {
+            range_set,
+            max_ranges,
+        }
+    }
+}
+
+impl<T: NumericInteger> Display for LimitedDisplay<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let ranges = self.range_set.0.chunks_exact(2);
+        let count = ranges.len();
+        if count <= self.max_ranges {
+            return Display::fmt(self.range_set, f);
+        }
+
+        // The first ranges get the extra one when `max_ranges` is odd.
+        let tail = self.max_ranges / 2;
+        let head = self.max_ranges - tail;
+        write!(f, "[ ")?;
+        for range in ranges.clone().take(head) {
+            write!(f, "{}..={} ", range[0], range[1])?;
+        }
+        write!(f, "... ")?;
+        for range in ranges.skip(count - tail) {
+            write!(f, "{}..={} ", range[0], range[1])?;
+        }
+        write!(f, "] ({} ranges)", count)
+    }
+}
+
+macro_rules! impl_radix_format {
+    ($($format:ident),*) => {
+        $(
+            /// Display the bounds in the same layout as `Display`, the flags such as `#` applying to each bound.
+            impl<T: NumericInteger + $format> $format for RangeSet<T> {
+                fn fmt(&self, f: &mut Formatter) -> Result {
+                    write!(f, "[ ")?;
+                    for range in self.0.chunks_exact(2) {
+                        $format::fmt(&range[0], f)?;
+                        write!(f, "..=")?;
+                        $format::fmt(&range[1], f)?;
+                        write!(f, " ")?;
+                    }
+                    write!(f, "]")
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_format!(LowerHex, UpperHex, Octal, Binary);
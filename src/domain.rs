@@ -0,0 +1,204 @@
+use std::ops::{RangeBounds, RangeInclusive};
+
+use crate::{integer::NumericInteger, range_to_bounds, RangeSet};
+
+/// A `RangeSet` restricted to a caller-defined universe, such as the valid port numbers `1..=65535`.
+///
+/// The values outside of the domain are ignored by the operations, and `complement`, `is_total` and `total` are relative to the domain instead of all the values of `T`.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct DomainRangeSet<T: NumericInteger> {
+    set: RangeSet<T>,
+    min: T,
+    max: T,
+}
+
+impl<T: NumericInteger> std::fmt::Display for DomainRangeSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.set.fmt(f)
+    }
+}
+
+impl<T: NumericInteger> DomainRangeSet<T> {
+    /// Create a new instance that does not contain any value of the given domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain is empty.
+    pub fn empty<R: RangeBounds<T>>(domain: R) -> DomainRangeSet<T> {
+        let (min, max) = range_to_bounds(&domain);
+        assert!(min <= max, "the domain must not be empty");
+        DomainRangeSet {
+            set: RangeSet::empty(),
+            min,
+            max,
+        }
+    }
+
+    /// Create a new instance that contains all the values of the given domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain is empty.
+    pub fn total<R: RangeBounds<T>>(domain: R) -> DomainRangeSet<T> {
+        let mut range = DomainRangeSet::empty(domain);
+        range.set = RangeSet(vec![range.min, range.max]);
+        range
+    }
+
+    /// Create a new instance with the values of the given `RangeSet` within the given domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain is empty.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::domain::DomainRangeSet;
+    ///
+    /// let used = DomainRangeSet::new(RangeSet::<u16>(vec![0, 1023, 8080, 8080]), 1..=65535);
+    /// assert_eq!(&RangeSet(vec![1, 1023, 8080, 8080]), used.as_range_set());
+    ///
+    /// let free = used.complement();
+    /// assert_eq!(&RangeSet(vec![1024, 8079, 8081, 65535]), free.as_range_set());
+    /// assert!(used.union(free.as_range_set()).is_total());
+    /// ```
+    pub fn new<R: RangeBounds<T>>(set: RangeSet<T>, domain: R) -> DomainRangeSet<T> {
+        let mut range = DomainRangeSet::empty(domain);
+        range.set = set.intersection_range(range.min..=range.max);
+        range
+    }
+
+    /// Return the domain.
+    #[inline]
+    pub fn domain(&self) -> RangeInclusive<T> {
+        self.min..=self.max
+    }
+
+    /// Return the values contained as a `RangeSet`.
+    #[inline]
+    pub fn as_range_set(&self) -> &RangeSet<T> {
+        &self.set
+    }
+
+    /// Return the values contained as a `RangeSet`.
+    #[inline]
+    pub fn into_inner(self) -> RangeSet<T> {
+        self.set
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Return `true` if it contains all the values of the domain.
+    #[inline]
+    pub fn is_total(&self) -> bool {
+        self.set.0 == [self.min, self.max]
+    }
+
+    /// Return `true` if it contains the given value.
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.set.contains(value)
+    }
+
+    /// Return the values of the domain that are not contained.
+    pub fn complement(&self) -> DomainRangeSet<T> {
+        self.with_set(RangeSet(vec![self.min, self.max]).difference(&self.set))
+    }
+
+    /// Return the union with the given `RangeSet`, ignoring its values outside of the domain.
+    pub fn union(&self, that: &RangeSet<T>) -> DomainRangeSet<T> {
+        self.with_set(self.set.union(that).intersection_range(self.min..=self.max))
+    }
+
+    /// Return the intersection with the given `RangeSet`.
+    pub fn intersection(&self, that: &RangeSet<T>) -> DomainRangeSet<T> {
+        self.with_set(self.set.intersection(that))
+    }
+
+    /// Return the difference with the given `RangeSet`.
+    pub fn difference(&self, that: &RangeSet<T>) -> DomainRangeSet<T> {
+        self.with_set(self.set.difference(that))
+    }
+
+    /// Insert the values of the given range that are within the domain.
+    pub fn insert_range<R: RangeBounds<T>>(&mut self, range: R) {
+        let (min, max) = range_to_bounds(&range);
+        let (min, max) = (min.max(self.min), max.min(self.max));
+        if min <= max {
+            self.set = self.set.union_range(min..=max);
+        }
+    }
+
+    /// Remove the values of the given range.
+    pub fn remove_range<R: RangeBounds<T>>(&mut self, range: R) {
+        self.set = self.set.difference_range(range);
+    }
+
+    fn with_set(&self, set: RangeSet<T>) -> DomainRangeSet<T> {
+        DomainRangeSet {
+            set,
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
+impl<T: NumericInteger> From<DomainRangeSet<T>> for RangeSet<T> {
+    fn from(value: DomainRangeSet<T>) -> Self {
+        value.set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations() -> Result<(), String> {
+        let total = DomainRangeSet::<i8>::total(-10..10);
+        assert_eq!(-10..=9, total.domain());
+        assert!(total.is_total());
+        assert!(total.complement().is_empty());
+        assert!(DomainRangeSet::<i8>::empty(-10..10).complement().is_total());
+
+        let mut range = DomainRangeSet::new(RangeSet::<i8>(vec![-128, -5, 5, 127]), -10..10);
+        assert_eq!("[ -10..=-5 5..=9 ]", range.to_string());
+        assert_eq!(&RangeSet(vec![-4, 4]), range.complement().as_range_set());
+        assert_eq!(range, range.complement().complement());
+
+        range.insert_range(-20..=-1);
+        range.insert_range(50..);
+        assert_eq!(&RangeSet(vec![-10, -1, 5, 9]), range.as_range_set());
+        range.remove_range(..-5);
+        assert_eq!(&RangeSet(vec![-5, -1, 5, 9]), range.as_range_set());
+        assert!(range.contains(-5));
+
+        let other = RangeSet::<i8>(vec![-100, -3, 100, 100]);
+        assert_eq!(
+            &RangeSet(vec![-10, -1, 5, 9]),
+            range.union(&other).as_range_set()
+        );
+        assert_eq!(
+            &RangeSet(vec![-5, -3]),
+            range.intersection(&other).as_range_set()
+        );
+        assert_eq!(
+            RangeSet(vec![-2, -1, 5, 9]),
+            RangeSet::from(range.difference(&other))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "the domain must not be empty")]
+    fn test_empty_domain() {
+        DomainRangeSet::<u8>::total(5..5);
+    }
+}
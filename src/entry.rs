@@ -0,0 +1,104 @@
+use core::ops::RangeInclusive;
+
+use crate::{integer::NumericInteger, RangeSet};
+
+/// A view into a value or a range of a `RangeSet`, which is either occupied or vacant.
+///
+/// Constructed with `RangeSet::entry` or `RangeSet::entry_range`.
+pub enum Entry<'a, T: NumericInteger> {
+    /// At least one of the values is contained.
+    Occupied(OccupiedEntry<'a, T>),
+    /// None of the values are contained.
+    Vacant(VacantEntry<'a, T>),
+}
+
+/// A view into an occupied entry of a `RangeSet`.
+pub struct OccupiedEntry<'a, T: NumericInteger> {
+    range_set: &'a mut RangeSet<T>,
+    bounds: (T, T),
+    index: usize,
+}
+
+/// A view into a vacant entry of a `RangeSet`.
+pub struct VacantEntry<'a, T: NumericInteger> {
+    range_set: &'a mut RangeSet<T>,
+    bounds: (T, T),
+    index: usize,
+}
+
+impl<'a, T: NumericInteger> Entry<'a, T> {
+    pub(crate) fn new(range_set: &'a mut RangeSet<T>, min: T, max: T) -> Entry<'a, T> {
+        let index = range_set.0.partition_point(|&x| x < min);
+        if index % 2 != 0 || range_set.0.get(index).is_some_and(|&x| x <= max) {
+            Entry::Occupied(OccupiedEntry {
+                range_set,
+                bounds: (min, max),
+                index: index - index % 2,
+            })
+        } else {
+            Entry::Vacant(VacantEntry {
+                range_set,
+                bounds: (min, max),
+                index,
+            })
+        }
+    }
+
+    /// Insert the values if the entry is vacant.
+    pub fn or_insert(self) {
+        if let Entry::Vacant(entry) = self {
+            entry.insert();
+        }
+    }
+
+    /// Return `true` if at least one of the values is contained.
+    #[inline]
+    pub fn is_occupied(&self) -> bool {
+        matches!(self, Entry::Occupied(_))
+    }
+
+    /// Return `true` if none of the values are contained.
+    #[inline]
+    pub fn is_vacant(&self) -> bool {
+        matches!(self, Entry::Vacant(_))
+    }
+}
+
+impl<'a, T: NumericInteger> OccupiedEntry<'a, T> {
+    /// Return the first range of the `RangeSet` holding some of the values of the entry.
+    pub fn range(&self) -> RangeInclusive<T> {
+        self.range_set.0[self.index]..=self.range_set.0[self.index + 1]
+    }
+
+    /// Remove all the values of the entry from the `RangeSet`.
+    pub fn remove(self) {
+        let (min, max) = self.bounds;
+        *self.range_set = self
+            .range_set
+            .difference(&RangeSet::new_from_range(min..=max));
+    }
+}
+
+impl<'a, T: NumericInteger> VacantEntry<'a, T> {
+    /// Insert the values of the entry into the `RangeSet`, merging it with the adjacent ranges.
+    pub fn insert(self) {
+        let (min, max) = self.bounds;
+        let bounds = &mut self.range_set.0;
+        let index = self.index;
+
+        let merge_previous = index > 0 && bounds[index - 1] + T::one() == min;
+        let merge_next =
+            index < bounds.len() && max < T::max_value() && max + T::one() == bounds[index];
+
+        match (merge_previous, merge_next) {
+            (true, true) => {
+                bounds.drain(index - 1..=index);
+            }
+            (true, false) => bounds[index - 1] = max,
+            (false, true) => bounds[index] = min,
+            (false, false) => {
+                bounds.splice(index..index, [min, max]);
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{integer::NumericInteger, RangeSet};
+
+/// A read-only `RangeSet` optimized for membership queries on large sets.
+///
+/// The ranges are stored in Eytzinger order (the breadth-first layout of a complete binary search tree), so a lookup walks down the tree without any branch on the comparisons and the first levels stay in cache.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct EytzingerRangeSet<T: NumericInteger> {
+    /// The `(max, min)` of each range in Eytzinger order, starting at index `1`.
+    nodes: Vec<(T, T)>,
+}
+
+impl<T: NumericInteger> EytzingerRangeSet<T> {
+    /// Return the number of ranges.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return `true` if it contains the given value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::eytzinger::EytzingerRangeSet;
+    ///
+    /// let range = EytzingerRangeSet::from(&RangeSet::<u32>(vec![2, 4, 10, 20, 30, 30]));
+    /// assert!(range.contains(15));
+    /// assert!(range.contains(30));
+    /// assert!(!range.contains(5));
+    /// ```
+    pub fn contains(&self, value: T) -> bool {
+        // Find the first range whose maximum is greater than or equal to the value.
+        let mut k = 1;
+        while k < self.nodes.len() {
+            k = 2 * k + usize::from(self.nodes[k].0 < value);
+        }
+        k >>= k.trailing_ones() + 1;
+
+        k != 0 && self.nodes[k].1 <= value
+    }
+}
+
+impl<T: NumericInteger> From<&RangeSet<T>> for EytzingerRangeSet<T> {
+    fn from(value: &RangeSet<T>) -> Self {
+        fn fill<T: Copy>(sorted: &[(T, T)], nodes: &mut [(T, T)], next: &mut usize, k: usize) {
+            if k < nodes.len() {
+                fill(sorted, nodes, next, 2 * k);
+                nodes[k] = sorted[*next];
+                *next += 1;
+                fill(sorted, nodes, next, 2 * k + 1);
+            }
+        }
+
+        let sorted: Vec<(T, T)> = value
+            .0
+            .chunks_exact(2)
+            .map(|range| (range[1], range[0]))
+            .collect();
+        let mut nodes = vec![(T::min_value(), T::min_value()); sorted.len() + 1];
+        fill(&sorted, &mut nodes, &mut 0, 1);
+
+        EytzingerRangeSet { nodes }
+    }
+}
+
+impl<T: NumericInteger> From<&EytzingerRangeSet<T>> for RangeSet<T> {
+    fn from(value: &EytzingerRangeSet<T>) -> Self {
+        fn collect<T: Copy>(nodes: &[(T, T)], bounds: &mut Vec<T>, k: usize) {
+            if k < nodes.len() {
+                collect(nodes, bounds, 2 * k);
+                bounds.push(nodes[k].1);
+                bounds.push(nodes[k].0);
+                collect(nodes, bounds, 2 * k + 1);
+            }
+        }
+
+        let mut bounds = Vec::with_capacity(value.len() * 2);
+        collect(&value.nodes, &mut bounds, 1);
+        RangeSet(bounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_contains() -> Result<(), String> {
+        let mut rng = StdRng::seed_from_u64(154);
+        for len in 0..40 {
+            let mut values: Vec<u8> = (0..len * 2).map(|_| rng.gen_range(0..=255)).collect();
+            values.sort();
+            let range = RangeSet::new_from_iter(values.chunks_exact(2).map(|r| r[0]..=r[1]));
+
+            let eytzinger = EytzingerRangeSet::from(&range);
+            assert_eq!(range.0.len() / 2, eytzinger.len());
+            assert_eq!(range, RangeSet::from(&eytzinger));
+            for value in 0..=255 {
+                assert_eq!(range.contains(value), eytzinger.contains(value));
+            }
+        }
+
+        let eytzinger = EytzingerRangeSet::from(&RangeSet::<i8>::total());
+        assert!(eytzinger.contains(i8::MIN));
+        assert!(eytzinger.contains(i8::MAX));
+        assert!(EytzingerRangeSet::from(&RangeSet::<i8>::empty()).is_empty());
+
+        Ok(())
+    }
+}
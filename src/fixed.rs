@@ -0,0 +1,455 @@
+use core::ops::RangeBounds;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::RangeSet;
+use crate::{integer::NumericInteger, range_to_bounds};
+
+/// Error returned when an operation on a `FixedRangeSet` would need more ranges than its capacity.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct CapacityExceeded;
+
+impl core::fmt::Display for CapacityExceeded {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "the capacity of the FixedRangeSet is exceeded")
+    }
+}
+
+impl core::error::Error for CapacityExceeded {}
+
+/// A structure holding at most `N` ranges inline, without any heap allocation.
+///
+/// Every operation that would produce more than `N` ranges returns `Err(CapacityExceeded)`.
+/// It does not need `std` nor `alloc`: with the default features disabled, it can be used in a `no_std` environment without a heap.
+#[derive(Clone, Copy)]
+pub struct FixedRangeSet<T: NumericInteger, const N: usize> {
+    ranges: [(T, T); N],
+    len: usize,
+}
+
+impl<T: NumericInteger, const N: usize> PartialEq for FixedRangeSet<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ranges() == other.ranges()
+    }
+}
+
+impl<T: NumericInteger, const N: usize> Eq for FixedRangeSet<T, N> {}
+
+impl<T: NumericInteger + core::fmt::Debug, const N: usize> core::fmt::Debug
+    for FixedRangeSet<T, N>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_tuple("FixedRangeSet")
+            .field(&self.ranges())
+            .finish()
+    }
+}
+
+impl<T: NumericInteger + core::hash::Hash, const N: usize> core::hash::Hash
+    for FixedRangeSet<T, N>
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.ranges().hash(state);
+    }
+}
+
+impl<T: NumericInteger, const N: usize> core::fmt::Display for FixedRangeSet<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "[ ")?;
+        for (min, max) in self.ranges() {
+            write!(f, "{}..={} ", min, max)?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: NumericInteger + defmt::Format, const N: usize> defmt::Format for FixedRangeSet<T, N> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "[ ");
+        for (min, max) in self.ranges() {
+            defmt::write!(f, "{}..={} ", min, max);
+        }
+        defmt::write!(f, "]");
+    }
+}
+
+impl<T: NumericInteger, const N: usize> Default for FixedRangeSet<T, N> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T: NumericInteger, const N: usize> FixedRangeSet<T, N> {
+    /// Create a new instance that does not contain any value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::fixed::FixedRangeSet;
+    ///
+    /// // Contains nothing, can hold up to 4 ranges
+    /// FixedRangeSet::<u32, 4>::empty();
+    /// ```
+    #[inline]
+    pub fn empty() -> FixedRangeSet<T, N> {
+        FixedRangeSet {
+            ranges: [(T::min_value(), T::min_value()); N],
+            len: 0,
+        }
+    }
+
+    /// Create a new instance that contains all possible values.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::fixed::FixedRangeSet;
+    ///
+    /// assert!(FixedRangeSet::<u8, 1>::total().unwrap().is_total());
+    /// assert!(FixedRangeSet::<u8, 0>::total().is_err());
+    /// ```
+    pub fn total() -> Result<FixedRangeSet<T, N>, CapacityExceeded> {
+        Self::new_from_range(..)
+    }
+
+    /// Create a new instance from the given range.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::fixed::FixedRangeSet;
+    ///
+    /// // Contains 2, 3
+    /// FixedRangeSet::<i32, 2>::new_from_range(2..4).unwrap();
+    /// ```
+    pub fn new_from_range<R: RangeBounds<T>>(
+        range: R,
+    ) -> Result<FixedRangeSet<T, N>, CapacityExceeded> {
+        let mut new_range = Self::empty();
+        let (min, max) = range_to_bounds(&range);
+        if max >= min {
+            new_range.push(min, max)?;
+        }
+        Ok(new_range)
+    }
+
+    /// Return the ranges contained, in order, as `(min, max)` inclusive pairs.
+    #[inline]
+    pub fn ranges(&self) -> &[(T, T)] {
+        &self.ranges[..self.len]
+    }
+
+    /// Return the maximum number of ranges that can be held.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return `true` if it contains all the possible values.
+    #[inline]
+    pub fn is_total(&self) -> bool {
+        self.len == 1 && self.ranges[0] == (T::min_value(), T::max_value())
+    }
+
+    /// Return `true` if it contains the given value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::fixed::FixedRangeSet;
+    ///
+    /// let range = FixedRangeSet::<i64, 2>::new_from_range(2..4).unwrap();
+    /// assert!(range.contains(3));
+    /// assert!(!range.contains(4));
+    /// ```
+    pub fn contains(&self, value: T) -> bool {
+        let ranges = self.ranges();
+        let position = ranges.partition_point(|&(_, max)| max < value);
+
+        position < ranges.len() && ranges[position].0 <= value
+    }
+
+    /// Add the given range, failing without modification if the capacity is exceeded.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::fixed::FixedRangeSet;
+    ///
+    /// let mut range = FixedRangeSet::<u16, 2>::empty();
+    /// range.insert_range(2..=4).unwrap();
+    /// range.insert_range(8..=9).unwrap();
+    /// range.insert_range(5..=7).unwrap(); // Merged into 2..=9
+    /// range.insert_range(20..=21).unwrap();
+    /// assert!(range.insert_range(30..=31).is_err());
+    /// ```
+    pub fn insert_range<R: RangeBounds<T>>(&mut self, range: R) -> Result<(), CapacityExceeded> {
+        *self = self.union(&Self::single(range))?;
+        Ok(())
+    }
+
+    /// Remove the given range, failing without modification if the capacity is exceeded.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::fixed::FixedRangeSet;
+    ///
+    /// let mut range = FixedRangeSet::<u16, 1>::new_from_range(2..=9).unwrap();
+    /// range.remove_range(7..).unwrap();
+    /// assert!(range.remove_range(4..=4).is_err());
+    /// ```
+    pub fn remove_range<R: RangeBounds<T>>(&mut self, range: R) -> Result<(), CapacityExceeded> {
+        *self = self.difference(&Self::single(range))?;
+        Ok(())
+    }
+
+    /// Return the union with the given `FixedRangeSet`.
+    pub fn union<const M: usize>(
+        &self,
+        that: &FixedRangeSet<T, M>,
+    ) -> Result<FixedRangeSet<T, N>, CapacityExceeded> {
+        let mut new_range = Self::empty();
+
+        let (mut i, mut j) = (0, 0);
+        let (a, b) = (self.ranges(), that.ranges());
+        while i < a.len() || j < b.len() {
+            let (min, max) = if j >= b.len() || (i < a.len() && a[i].0 <= b[j].0) {
+                i += 1;
+                a[i - 1]
+            } else {
+                j += 1;
+                b[j - 1]
+            };
+            new_range.push(min, max)?;
+        }
+
+        Ok(new_range)
+    }
+
+    /// Return the intersection with the given `FixedRangeSet`.
+    pub fn intersection<const M: usize>(
+        &self,
+        that: &FixedRangeSet<T, M>,
+    ) -> Result<FixedRangeSet<T, N>, CapacityExceeded> {
+        let mut new_range = Self::empty();
+
+        let (mut i, mut j) = (0, 0);
+        let (a, b) = (self.ranges(), that.ranges());
+        while i < a.len() && j < b.len() {
+            let (self_min, self_max) = a[i];
+            let (that_min, that_max) = b[j];
+
+            if self_max < that_min {
+                i += 1;
+            } else if that_max < self_min {
+                j += 1;
+            } else {
+                new_range.push(
+                    core::cmp::max(self_min, that_min),
+                    core::cmp::min(self_max, that_max),
+                )?;
+                if self_max < that_max {
+                    i += 1;
+                } else {
+                    j += 1;
+                }
+            }
+        }
+
+        Ok(new_range)
+    }
+
+    /// Return the complement.
+    pub fn complement(&self) -> Result<FixedRangeSet<T, N>, CapacityExceeded> {
+        let mut new_range = Self::empty();
+
+        let mut next_min = Some(T::min_value());
+        for &(min, max) in self.ranges() {
+            if let Some(next) = next_min {
+                if min > next {
+                    new_range.push(next, min - T::one())?;
+                }
+            }
+            next_min = if max < T::max_value() {
+                Some(max + T::one())
+            } else {
+                None
+            };
+        }
+        if let Some(next) = next_min {
+            new_range.push(next, T::max_value())?;
+        }
+
+        Ok(new_range)
+    }
+
+    /// Return the difference with the given `FixedRangeSet`.
+    pub fn difference<const M: usize>(
+        &self,
+        that: &FixedRangeSet<T, M>,
+    ) -> Result<FixedRangeSet<T, N>, CapacityExceeded> {
+        let mut new_range = Self::empty();
+
+        let mut j = 0;
+        let b = that.ranges();
+        for &(min, max) in self.ranges() {
+            while j < b.len() && b[j].1 < min {
+                j += 1;
+            }
+
+            let mut current_min = Some(min);
+            let mut k = j;
+            while let Some(cmin) = current_min {
+                if k == b.len() || b[k].0 > max {
+                    break;
+                }
+                let (that_min, that_max) = b[k];
+                if that_min > cmin {
+                    new_range.push(cmin, that_min - T::one())?;
+                }
+                current_min = if that_max < max {
+                    Some(that_max + T::one())
+                } else {
+                    None
+                };
+                k += 1;
+            }
+            if let Some(cmin) = current_min {
+                new_range.push(cmin, max)?;
+            }
+        }
+
+        Ok(new_range)
+    }
+
+    /// Return a `RangeSet` holding the same values.
+    #[cfg(feature = "alloc")]
+    pub fn to_range_set(&self) -> RangeSet<T> {
+        let mut bounds = Vec::with_capacity(self.len * 2);
+        for &(min, max) in self.ranges() {
+            bounds.push(min);
+            bounds.push(max);
+        }
+        RangeSet(bounds)
+    }
+
+    fn single<R: RangeBounds<T>>(range: R) -> FixedRangeSet<T, 1> {
+        let mut new_range = FixedRangeSet::empty();
+        let (min, max) = range_to_bounds(&range);
+        if max >= min {
+            new_range.ranges[0] = (min, max);
+            new_range.len = 1;
+        }
+        new_range
+    }
+
+    /// Append a range that starts after the start of the last range, merging them if they overlap or touch.
+    fn push(&mut self, min: T, max: T) -> Result<(), CapacityExceeded> {
+        if self.len > 0 {
+            let last = &mut self.ranges[self.len - 1];
+            if last.1 == T::max_value() || min <= last.1 + T::one() {
+                if max > last.1 {
+                    last.1 = max;
+                }
+                return Ok(());
+            }
+        }
+        if self.len == N {
+            return Err(CapacityExceeded);
+        }
+        self.ranges[self.len] = (min, max);
+        self.len += 1;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: NumericInteger, const N: usize> From<&FixedRangeSet<T, N>> for RangeSet<T> {
+    fn from(value: &FixedRangeSet<T, N>) -> Self {
+        value.to_range_set()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: NumericInteger, const N: usize> TryFrom<&RangeSet<T>> for FixedRangeSet<T, N> {
+    type Error = CapacityExceeded;
+
+    fn try_from(value: &RangeSet<T>) -> Result<Self, Self::Error> {
+        let mut new_range = Self::empty();
+        for i in (0..value.0.len()).step_by(2) {
+            new_range.push(value.0[i], value.0[i + 1])?;
+        }
+        Ok(new_range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations() -> Result<(), CapacityExceeded> {
+        let range1 = FixedRangeSet::<u8, 4>::try_from(&RangeSet(vec![2, 10, 20, 30, 40, 50]))?;
+        let range2 = FixedRangeSet::<u8, 4>::try_from(&RangeSet(vec![5, 25, 45, 255]))?;
+
+        assert_eq!(
+            RangeSet(vec![2, 30, 40, 255]),
+            range1.union(&range2)?.to_range_set()
+        );
+        assert_eq!(
+            RangeSet(vec![5, 10, 20, 25, 45, 50]),
+            range1.intersection(&range2)?.to_range_set()
+        );
+        assert_eq!(
+            RangeSet(vec![2, 4, 26, 30, 40, 44]),
+            range1.difference(&range2)?.to_range_set()
+        );
+        assert_eq!(
+            RangeSet(vec![0, 1, 11, 19, 31, 39, 51, 255]),
+            range1.complement()?.to_range_set()
+        );
+        assert_eq!(
+            Err(CapacityExceeded),
+            FixedRangeSet::<u8, 3>::try_from(&RangeSet(vec![2, 10, 20, 30, 40, 50]))?.complement()
+        );
+
+        let range3 = FixedRangeSet::<u8, 4>::try_from(&RangeSet(vec![0, 3, 6, 6, 9, 9]))?;
+        assert_eq!(Err(CapacityExceeded), range1.difference(&range3));
+        assert!(FixedRangeSet::<u8, 4>::total()?.complement()?.is_empty());
+        assert!(FixedRangeSet::<u8, 4>::empty().complement()?.is_total());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_and_remove() -> Result<(), CapacityExceeded> {
+        let mut range = FixedRangeSet::<i8, 2>::empty();
+        range.insert_range(-5..=-1)?;
+        range.insert_range(3..=i8::MAX)?;
+        assert_eq!(Err(CapacityExceeded), range.insert_range(1..=1));
+        range.insert_range(0..3)?;
+        assert_eq!(RangeSet(vec![-5, i8::MAX]), range.to_range_set());
+
+        range.remove_range(..=0)?;
+        range.remove_range(10..)?;
+        assert_eq!(
+            Err(CapacityExceeded),
+            range.remove_range(3..5).and(range.remove_range(7..8))
+        );
+        assert_eq!(RangeSet(vec![1, 2, 5, 9]), range.to_range_set());
+        assert!(range.contains(9));
+        assert!(!range.contains(3));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::VecDeque;
+
+use crate::{
+    delta::{impl_delta_mutations, RangeSetDelta},
+    integer::NumericInteger,
+    RangeSet,
+};
+
+/// A `RangeSet` wrapper recording the changes made by each mutation, to undo and redo them.
+///
+/// Only the values added and removed are kept, and the oldest changes are forgotten beyond the given capacity.
+/// A mutation changing the set discards the undone changes.
+#[derive(Clone, Debug)]
+pub struct HistoryRangeSet<T: NumericInteger> {
+    set: RangeSet<T>,
+    undo: VecDeque<RangeSetDelta<T>>,
+    redo: Vec<RangeSetDelta<T>>,
+    capacity: usize,
+}
+
+impl<T: NumericInteger> HistoryRangeSet<T> {
+    /// Create a new instance holding the given `RangeSet` and remembering up to `capacity` changes.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::history::HistoryRangeSet;
+    ///
+    /// let mut selection = HistoryRangeSet::new(RangeSet::<u32>::empty(), 100);
+    ///
+    /// selection.insert_range(0..10);
+    /// selection.remove_range(3..5);
+    /// assert_eq!(&RangeSet(vec![0, 2, 5, 9]), selection.as_range_set());
+    ///
+    /// assert!(selection.undo());
+    /// assert_eq!(&RangeSet(vec![0, 9]), selection.as_range_set());
+    /// assert!(selection.undo());
+    /// assert!(selection.as_range_set().is_empty());
+    /// assert!(!selection.undo());
+    ///
+    /// assert!(selection.redo());
+    /// assert_eq!(&RangeSet(vec![0, 9]), selection.as_range_set());
+    /// ```
+    pub fn new(set: RangeSet<T>, capacity: usize) -> HistoryRangeSet<T> {
+        HistoryRangeSet {
+            set,
+            undo: VecDeque::new(),
+            redo: vec![],
+            capacity,
+        }
+    }
+
+    /// Return the current `RangeSet`.
+    #[inline]
+    pub fn as_range_set(&self) -> &RangeSet<T> {
+        &self.set
+    }
+
+    /// Return the current `RangeSet`, dropping the history.
+    #[inline]
+    pub fn into_inner(self) -> RangeSet<T> {
+        self.set
+    }
+
+    /// Return `true` if there is a change to undo.
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Return `true` if there is an undone change to redo.
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Revert the last change, return `false` if there is none.
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop_back() {
+            Some(delta) => {
+                self.set = delta.inverse().apply(&self.set);
+                self.redo.push(delta);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Apply again the last undone change, return `false` if there is none.
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(delta) => {
+                self.set = delta.apply(&self.set);
+                self.undo.push_back(delta);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forget all the recorded changes.
+    pub fn clear_history(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    impl_delta_mutations!();
+
+    /// Record the change, discarding the undone changes.
+    fn changed(&mut self, delta: RangeSetDelta<T>) {
+        if delta.is_empty() {
+            return;
+        }
+
+        self.redo.clear();
+        if self.capacity == 0 {
+            return;
+        }
+        if self.undo.len() == self.capacity {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_redo() -> Result<(), String> {
+        let mut set = HistoryRangeSet::new(RangeSet::<u8>(vec![10, 20]), 3);
+        let mut states = vec![set.as_range_set().clone()];
+
+        set.insert_range(15..=25);
+        states.push(set.as_range_set().clone());
+        set.insert_range(15..=25);
+        set.intersect_with(&RangeSet(vec![0, 22]));
+        states.push(set.as_range_set().clone());
+        set.complement();
+        states.push(set.as_range_set().clone());
+        set.union_with(&RangeSet(vec![12, 12]));
+        states.push(set.as_range_set().clone());
+
+        for state in states.iter().rev().skip(1).take(3) {
+            assert!(set.undo());
+            assert_eq!(state, set.as_range_set());
+        }
+        assert!(!set.can_undo());
+        assert!(!set.undo());
+
+        for state in states.iter().skip(2) {
+            assert!(set.redo());
+            assert_eq!(state, set.as_range_set());
+        }
+        assert!(!set.redo());
+
+        set.undo();
+        set.difference_with(&RangeSet(vec![0, 50]));
+        assert!(!set.can_redo());
+        assert_eq!(RangeSet(vec![51, 255]), set.into_inner());
+
+        let mut set = HistoryRangeSet::new(RangeSet::<u8>::empty(), 0);
+        set.insert_range(..);
+        assert!(!set.undo());
+        assert!(set.as_range_set().is_total());
+
+        Ok(())
+    }
+}
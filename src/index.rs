@@ -0,0 +1,197 @@
+use std::{collections::BTreeSet, ops::RangeBounds};
+
+use crate::{integer::NumericInteger, range_to_bounds, RangeSet};
+
+/// A structure indexing many labelled `RangeSet` to find the ones containing a value or overlapping a range.
+///
+/// The bounds of all the sets are merged into a single sorted list of segments, each segment holding the keys of the sets covering it.
+/// A query is then answered with one binary search instead of probing every set.
+#[derive(Clone, Debug)]
+pub struct RangeSetIndex<T: NumericInteger, K> {
+    keys: Vec<K>,
+    /// The smallest value of each segment, in increasing order.
+    starts: Vec<T>,
+    /// The indices in `keys` of the sets covering each segment, in increasing order.
+    members: Vec<Vec<usize>>,
+}
+
+impl<T: NumericInteger, K> Default for RangeSetIndex<T, K> {
+    fn default() -> Self {
+        RangeSetIndex {
+            keys: vec![],
+            starts: vec![],
+            members: vec![],
+        }
+    }
+}
+
+impl<T: NumericInteger, K> FromIterator<(K, RangeSet<T>)> for RangeSetIndex<T, K> {
+    fn from_iter<I: IntoIterator<Item = (K, RangeSet<T>)>>(iter: I) -> Self {
+        let mut keys = vec![];
+        let mut events = vec![];
+        for (key, set) in iter {
+            let index = keys.len();
+            keys.push(key);
+            for range in set.0.chunks_exact(2) {
+                events.push((range[0], index, true));
+                if range[1] < T::max_value() {
+                    events.push((range[1] + T::one(), index, false));
+                }
+            }
+        }
+        events.sort_by_key(|&(point, _, _)| point);
+
+        let mut starts = vec![];
+        let mut members = vec![];
+        let mut active = BTreeSet::new();
+        let mut i = 0;
+        while i < events.len() {
+            let point = events[i].0;
+            while i < events.len() && events[i].0 == point {
+                let (_, index, start) = events[i];
+                if start {
+                    active.insert(index);
+                } else {
+                    active.remove(&index);
+                }
+                i += 1;
+            }
+            starts.push(point);
+            members.push(active.iter().copied().collect());
+        }
+
+        RangeSetIndex {
+            keys,
+            starts,
+            members,
+        }
+    }
+}
+
+impl<T: NumericInteger, K> RangeSetIndex<T, K> {
+    /// Return the number of indexed sets.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Return `true` if no set is indexed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Return an iterator over the keys of the sets containing the given value, in insertion order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::index::RangeSetIndex;
+    ///
+    /// let index: RangeSetIndex<u16, &str> = [
+    ///     ("http", RangeSet::new_from_iter([80..=80, 8080..=8080])),
+    ///     ("registered", RangeSet::new_from_range(1024..=49151)),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(vec![&"http", &"registered"], index.stab(8080).collect::<Vec<_>>());
+    /// assert_eq!(vec![&"http"], index.stab(80).collect::<Vec<_>>());
+    /// assert_eq!(0, index.stab(443).count());
+    /// ```
+    pub fn stab(&self, value: T) -> impl Iterator<Item = &K> {
+        let position = self.starts.partition_point(|&start| start <= value);
+        let members = match position {
+            0 => &[][..],
+            _ => &self.members[position - 1][..],
+        };
+        members.iter().map(|&index| &self.keys[index])
+    }
+
+    /// Return the keys of the sets having at least one value in common with the given range, in insertion order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::index::RangeSetIndex;
+    ///
+    /// let index: RangeSetIndex<u16, &str> = [
+    ///     ("http", RangeSet::new_from_iter([80..=80, 8080..=8080])),
+    ///     ("registered", RangeSet::new_from_range(1024..=49151)),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(vec![&"http"], index.overlapping(0..1024));
+    /// ```
+    pub fn overlapping<R: RangeBounds<T>>(&self, range: R) -> Vec<&K> {
+        let (min, max) = range_to_bounds(&range);
+        if min > max {
+            return vec![];
+        }
+
+        let first = self.starts.partition_point(|&start| start <= min);
+        let last = self.starts.partition_point(|&start| start <= max);
+        let mut indices: Vec<usize> = self.members[first.saturating_sub(1)..last]
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+        indices.sort();
+        indices.dedup();
+
+        indices.into_iter().map(|index| &self.keys[index]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_queries() -> Result<(), String> {
+        let sets = [
+            RangeSet::<u8>(vec![0, 10, 20, 30]),
+            RangeSet::<u8>(vec![5, 25]),
+            RangeSet::<u8>(vec![250, 255]),
+            RangeSet::<u8>::empty(),
+            RangeSet::<u8>::total(),
+            RangeSet::<u8>(vec![31, 31]),
+        ];
+        let index: RangeSetIndex<u8, usize> = sets.iter().cloned().enumerate().collect();
+        assert_eq!(6, index.len());
+
+        for value in 0..=255 {
+            let expected: Vec<usize> = (0..sets.len())
+                .filter(|&i| sets[i].contains(value))
+                .collect();
+            assert_eq!(expected, index.stab(value).copied().collect::<Vec<_>>());
+        }
+
+        for (min, max) in [(0, 255), (11, 19), (26, 31), (32, 249), (255, 255)] {
+            let range = RangeSet::new_from_range(min..=max);
+            let expected: Vec<usize> = (0..sets.len())
+                .filter(|&i| sets[i].has_intersection(&range))
+                .collect();
+            assert_eq!(
+                expected,
+                index
+                    .overlapping(min..=max)
+                    .into_iter()
+                    .copied()
+                    .collect::<Vec<_>>()
+            );
+        }
+        assert!(index.overlapping(9..3).is_empty());
+
+        let index = RangeSetIndex::<u8, ()>::default();
+        assert!(index.is_empty());
+        assert_eq!(0, index.stab(0).count());
+        assert!(index.overlapping(..).is_empty());
+
+        Ok(())
+    }
+}
@@ -1,15 +1,34 @@
-use std::{
+use core::{
     fmt::Display,
+    num::ParseIntError,
     ops::{Add, AddAssign, Sub},
 };
 
 pub trait NumericInteger:
-    Display + Copy + Ord + Add<Output = Self> + Sub<Output = Self> + AddAssign + Bounded
+    Display
+    + Copy
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + AddAssign
+    + Bounded
+    + Distance
+    + Arithmetic
+    + FromStrRadix
 {
 }
 
 impl<T> NumericInteger for T where
-    T: Display + Copy + Ord + Add<Output = T> + Sub<Output = T> + AddAssign + Bounded
+    T: Display
+        + Copy
+        + Ord
+        + Add<Output = T>
+        + Sub<Output = T>
+        + AddAssign
+        + Bounded
+        + Distance
+        + Arithmetic
+        + FromStrRadix
 {
 }
 
@@ -17,8 +36,204 @@ pub trait Bounded {
     fn min_value() -> Self;
     fn max_value() -> Self;
     fn one() -> Self;
+    fn zero() -> Self;
 }
 
+/// Measure the distance between two values with an unsigned type of the same width, which cannot overflow.
+///
+/// The operations wrap around the domain, as `wrapping_sub` and `wrapping_add` do on the primitive integers.
+/// The sequence numbers of `irange::replay::ReplayWindow` and the modular arithmetic of `RangeSet` rely on it, so an implementation must not saturate or panic when `from > to`.
+///
+/// # Example:
+///
+/// ```
+/// use irange::integer::Distance;
+///
+/// assert_eq!(10, u8::distance(250, 4));
+/// assert_eq!(255, i8::distance(0, -1));
+/// assert_eq!(4, 250u8.add_distance(10));
+/// assert_eq!(250, 4u8.sub_distance(10));
+/// ```
+pub trait Distance {
+    /// The unsigned integer type of the same width.
+    type DistanceType: NumericInteger;
+
+    /// Return `to - from`, wrapping around the domain if `from > to`.
+    fn distance(from: Self, to: Self) -> Self::DistanceType;
+
+    /// Return `self + distance`, wrapping around the domain if it overflows.
+    fn add_distance(self, distance: Self::DistanceType) -> Self;
+
+    /// Return `self - distance`, wrapping around the domain if it overflows.
+    fn sub_distance(self, distance: Self::DistanceType) -> Self;
+
+    /// Convert a distance to `u128`.
+    fn distance_to_u128(distance: Self::DistanceType) -> u128;
+
+    /// Convert a `u128` to a distance, the value must fit.
+    fn distance_from_u128(value: u128) -> Self::DistanceType;
+}
+
+/// Convert the value to `i128`, `None` if it does not fit.
+#[cfg(feature = "alloc")]
+pub(crate) fn to_i128<T: NumericInteger>(value: T) -> Option<i128> {
+    let zero = T::zero();
+    if value >= zero {
+        i128::try_from(T::distance_to_u128(T::distance(zero, value))).ok()
+    } else {
+        0i128.checked_sub_unsigned(T::distance_to_u128(T::distance(value, zero)))
+    }
+}
+
+/// Checked and Euclidean arithmetic, with the semantic of the methods of the same name of the primitive integers.
+pub trait Arithmetic: Sized {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    fn div_euclid(self, rhs: Self) -> Self;
+
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_arithmetic {
+    ($($t:ty),*) => {
+        $(
+            impl Arithmetic for $t {
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                #[inline]
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                #[inline]
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                #[inline]
+                fn div_euclid(self, rhs: Self) -> Self {
+                    <$t>::div_euclid(self, rhs)
+                }
+
+                #[inline]
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_arithmetic!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Parse a value written in the given radix, with the semantic of the `from_str_radix` method of the primitive integers.
+pub trait FromStrRadix: Sized {
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError>;
+}
+
+macro_rules! impl_from_str_radix {
+    ($($t:ty),*) => {
+        $(
+            impl FromStrRadix for $t {
+                #[inline]
+                fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+                    <$t>::from_str_radix(src, radix)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_str_radix!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Measure the number of values of a range with a caller-chosen type.
+///
+/// It is implemented by the unsigned integers for every `NumericInteger`, and can be implemented by other types to define their own length, such as a duration for a set of timestamps.
+pub trait Measure<T>: Sized {
+    /// Return the measure of the empty set.
+    fn zero() -> Self;
+
+    /// Return the measure of the values from `min` to `max` included, `None` if it does not fit.
+    fn measure(min: T, max: T) -> Option<Self>;
+
+    /// Return `self + other`, `None` if it does not fit.
+    fn checked_sum(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_measure {
+    ($($m:ty),*) => {
+        $(
+            impl<T: NumericInteger> Measure<T> for $m {
+                #[inline]
+                fn zero() -> Self {
+                    0
+                }
+
+                #[inline]
+                fn measure(min: T, max: T) -> Option<Self> {
+                    <$m>::try_from(T::distance_to_u128(T::distance(min, max)))
+                        .ok()?
+                        .checked_add(1)
+                }
+
+                #[inline]
+                fn checked_sum(self, other: Self) -> Option<Self> {
+                    self.checked_add(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_measure!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_distance {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl Distance for $t {
+                type DistanceType = $u;
+
+                #[inline]
+                fn distance(from: Self, to: Self) -> Self::DistanceType {
+                    to.wrapping_sub(from) as $u
+                }
+
+                #[inline]
+                fn add_distance(self, distance: Self::DistanceType) -> Self {
+                    self.wrapping_add(distance as $t)
+                }
+
+                #[inline]
+                fn sub_distance(self, distance: Self::DistanceType) -> Self {
+                    self.wrapping_sub(distance as $t)
+                }
+
+                #[inline]
+                fn distance_to_u128(distance: Self::DistanceType) -> u128 {
+                    distance as u128
+                }
+
+                #[inline]
+                fn distance_from_u128(value: u128) -> Self::DistanceType {
+                    value as $u
+                }
+            }
+        )*
+    };
+}
+
+impl_distance!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+
 impl Bounded for u8 {
     fn min_value() -> Self {
         u8::MIN
@@ -31,6 +246,10 @@ impl Bounded for u8 {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for u16 {
@@ -45,6 +264,10 @@ impl Bounded for u16 {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for u32 {
@@ -59,6 +282,10 @@ impl Bounded for u32 {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for u64 {
@@ -73,6 +300,10 @@ impl Bounded for u64 {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for u128 {
@@ -87,6 +318,10 @@ impl Bounded for u128 {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for usize {
@@ -101,6 +336,10 @@ impl Bounded for usize {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for i8 {
@@ -115,6 +354,10 @@ impl Bounded for i8 {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for i16 {
@@ -129,6 +372,10 @@ impl Bounded for i16 {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for i32 {
@@ -143,6 +390,10 @@ impl Bounded for i32 {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for i64 {
@@ -157,6 +408,10 @@ impl Bounded for i64 {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for i128 {
@@ -171,6 +426,10 @@ impl Bounded for i128 {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
 
 impl Bounded for isize {
@@ -185,4 +444,8 @@ impl Bounded for isize {
     fn one() -> Self {
         1
     }
+
+    fn zero() -> Self {
+        0
+    }
 }
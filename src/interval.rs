@@ -0,0 +1,313 @@
+use std::{
+    cmp::Ordering,
+    ops::{Bound, RangeBounds},
+};
+
+/// A structure holding a collection of intervals of a continuous domain such as `f64`, with open, closed or unbounded endpoints.
+///
+/// Unlike `RangeSet`, two intervals are merged only if they overlap or share an endpoint included in one of them: `[0, 1)` and `[1, 2]` are merged while `[0, 1)` and `(1, 2]` are not.
+/// The values must be totally ordered, comparing `f64::NAN` panics.
+#[derive(PartialEq, Clone, Debug)]
+pub struct IntervalSet<T: PartialOrd + Copy> {
+    /// The disjoint intervals, sorted and separated by at least one value.
+    intervals: Vec<(Bound<T>, Bound<T>)>,
+}
+
+impl<T: PartialOrd + Copy + std::fmt::Display> std::fmt::Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.intervals.is_empty() {
+            return write!(f, "∅");
+        }
+        for (i, (lower, upper)) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ∪ ")?;
+            }
+            match lower {
+                Bound::Included(value) => write!(f, "[{value}")?,
+                Bound::Excluded(value) => write!(f, "({value}")?,
+                Bound::Unbounded => write!(f, "(-∞")?,
+            }
+            match upper {
+                Bound::Included(value) => write!(f, ", {value}]")?,
+                Bound::Excluded(value) => write!(f, ", {value})")?,
+                Bound::Unbounded => write!(f, ", +∞)")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: PartialOrd + Copy> IntervalSet<T> {
+    /// Create a new instance that does not contain any value.
+    #[inline]
+    pub fn empty() -> IntervalSet<T> {
+        IntervalSet { intervals: vec![] }
+    }
+
+    /// Create a new instance that contains all possible values.
+    #[inline]
+    pub fn total() -> IntervalSet<T> {
+        IntervalSet {
+            intervals: vec![(Bound::Unbounded, Bound::Unbounded)],
+        }
+    }
+
+    /// Create a new instance from the given range.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use irange::interval::IntervalSet;
+    ///
+    /// let band = IntervalSet::new_from_range(0.5..2.0);
+    /// assert!(band.contains(0.5));
+    /// assert!(band.contains(1.999));
+    /// assert!(!band.contains(2.0));
+    ///
+    /// let positive = IntervalSet::new_from_range((Bound::Excluded(0.0), Bound::Unbounded));
+    /// assert_eq!("(0, +∞)", positive.to_string());
+    /// ```
+    pub fn new_from_range<R: RangeBounds<T>>(range: R) -> IntervalSet<T> {
+        let interval = (range.start_bound().cloned(), range.end_bound().cloned());
+        if is_empty(&interval) {
+            IntervalSet::empty()
+        } else {
+            IntervalSet {
+                intervals: vec![interval],
+            }
+        }
+    }
+
+    /// Return the intervals, sorted and disjoint.
+    #[inline]
+    pub fn intervals(&self) -> &[(Bound<T>, Bound<T>)] {
+        &self.intervals
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Return `true` if it contains all the possible values.
+    #[inline]
+    pub fn is_total(&self) -> bool {
+        matches!(self.intervals[..], [(Bound::Unbounded, Bound::Unbounded)])
+    }
+
+    /// Return `true` if it contains the given value.
+    pub fn contains(&self, value: T) -> bool {
+        let position = self
+            .intervals
+            .partition_point(|(lower, _)| cmp_lower(lower, &Bound::Included(value)).is_le());
+        position > 0 && self.intervals[position - 1].contains(&value)
+    }
+
+    /// Return the union with the given `IntervalSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::interval::IntervalSet;
+    ///
+    /// let low = IntervalSet::new_from_range(0.0..1.0);
+    /// let high = IntervalSet::new_from_range(1.0..=2.0);
+    ///
+    /// assert_eq!("[0, 2]", low.union(&high).to_string());
+    /// ```
+    pub fn union(&self, that: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut sorted: Vec<(Bound<T>, Bound<T>)> = self
+            .intervals
+            .iter()
+            .chain(&that.intervals)
+            .copied()
+            .collect();
+        sorted.sort_by(|a, b| cmp_lower(&a.0, &b.0));
+
+        let mut intervals: Vec<(Bound<T>, Bound<T>)> = Vec::with_capacity(sorted.len());
+        for (lower, upper) in sorted {
+            match intervals.last_mut() {
+                Some(last) if touches(&last.1, &lower) => {
+                    if cmp_upper(&upper, &last.1).is_gt() {
+                        last.1 = upper;
+                    }
+                }
+                _ => intervals.push((lower, upper)),
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// Return the complement.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::interval::IntervalSet;
+    ///
+    /// let band = IntervalSet::new_from_range(0.0..1.0);
+    ///
+    /// assert_eq!("(-∞, 0) ∪ [1, +∞)", band.complement().to_string());
+    /// ```
+    pub fn complement(&self) -> IntervalSet<T> {
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+        let mut lower = Some(Bound::Unbounded);
+        for &(start, end) in &self.intervals {
+            if let (Some(gap_lower), Some(gap_upper)) = (lower, flip(start)) {
+                intervals.push((gap_lower, gap_upper));
+            }
+            lower = flip(end);
+        }
+        if let Some(gap_lower) = lower {
+            intervals.push((gap_lower, Bound::Unbounded));
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// Return the intersection with the given `IntervalSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::interval::IntervalSet;
+    ///
+    /// let low = IntervalSet::new_from_range(0.0..=1.0);
+    /// let high = IntervalSet::new_from_range(1.0..2.0);
+    ///
+    /// assert_eq!("[1, 1]", low.intersection(&high).to_string());
+    /// ```
+    pub fn intersection(&self, that: &IntervalSet<T>) -> IntervalSet<T> {
+        self.complement().union(&that.complement()).complement()
+    }
+
+    /// Return the difference with the given `IntervalSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::interval::IntervalSet;
+    ///
+    /// let band = IntervalSet::new_from_range(0.0..=2.0);
+    /// let hole = IntervalSet::new_from_range(0.5..1.0);
+    ///
+    /// assert_eq!("[0, 0.5) ∪ [1, 2]", band.difference(&hole).to_string());
+    /// ```
+    pub fn difference(&self, that: &IntervalSet<T>) -> IntervalSet<T> {
+        self.intersection(&that.complement())
+    }
+}
+
+/// Compare two values, panicking if they are not comparable.
+fn cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).expect("the values must be comparable")
+}
+
+/// Compare two lower bounds, the smallest one starting first.
+fn cmp_lower<T: PartialOrd>(a: &Bound<T>, b: &Bound<T>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Less,
+        (_, Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => {
+            cmp(a, b)
+        }
+        (Bound::Included(a), Bound::Excluded(b)) => cmp(a, b).then(Ordering::Less),
+        (Bound::Excluded(a), Bound::Included(b)) => cmp(a, b).then(Ordering::Greater),
+    }
+}
+
+/// Compare two upper bounds, the smallest one ending first.
+fn cmp_upper<T: PartialOrd>(a: &Bound<T>, b: &Bound<T>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Greater,
+        (_, Bound::Unbounded) => Ordering::Less,
+        (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => {
+            cmp(a, b)
+        }
+        (Bound::Included(a), Bound::Excluded(b)) => cmp(a, b).then(Ordering::Greater),
+        (Bound::Excluded(a), Bound::Included(b)) => cmp(a, b).then(Ordering::Less),
+    }
+}
+
+/// Return `true` if an interval ending at `upper` and another one starting at `lower` overlap or form a single interval.
+fn touches<T: PartialOrd>(upper: &Bound<T>, lower: &Bound<T>) -> bool {
+    match (upper, lower) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+        (Bound::Excluded(a), Bound::Excluded(b)) => cmp(b, a).is_lt(),
+        (Bound::Included(a), Bound::Included(b) | Bound::Excluded(b))
+        | (Bound::Excluded(a), Bound::Included(b)) => cmp(b, a).is_le(),
+    }
+}
+
+/// Return the bound delimiting the values on the other side of the given one, `None` if there is none.
+fn flip<T>(bound: Bound<T>) -> Option<Bound<T>> {
+    match bound {
+        Bound::Included(value) => Some(Bound::Excluded(value)),
+        Bound::Excluded(value) => Some(Bound::Included(value)),
+        Bound::Unbounded => None,
+    }
+}
+
+/// Return `true` if the given interval does not contain any value.
+fn is_empty<T: PartialOrd>((lower, upper): &(Bound<T>, Bound<T>)) -> bool {
+    match (lower, upper) {
+        (Bound::Included(a), Bound::Included(b)) => cmp(a, b).is_gt(),
+        (Bound::Included(a), Bound::Excluded(b))
+        | (Bound::Excluded(a), Bound::Included(b))
+        | (Bound::Excluded(a), Bound::Excluded(b)) => cmp(a, b).is_ge(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations() -> Result<(), String> {
+        let empty = IntervalSet::<f64>::empty();
+        let total = IntervalSet::<f64>::total();
+        assert_eq!(total, empty.complement());
+        assert_eq!(empty, total.complement());
+        assert!(IntervalSet::new_from_range(1.0..1.0).is_empty());
+        assert!(!IntervalSet::new_from_range(1.0..=1.0).is_empty());
+        assert!(
+            IntervalSet::new_from_range((Bound::Excluded(1.0), Bound::Included(1.0))).is_empty()
+        );
+
+        let low = IntervalSet::new_from_range(0.0..1.0);
+        let high = IntervalSet::new_from_range((Bound::Excluded(1.0), Bound::Included(2.0)));
+        let union = low.union(&high);
+        assert_eq!("[0, 1) ∪ (1, 2]", union.to_string());
+        assert!(union.contains(0.0));
+        assert!(!union.contains(1.0));
+        assert!(union.contains(2.0));
+        assert!(!union.contains(-0.5));
+        assert_eq!("(-∞, 0) ∪ [1, 1] ∪ (2, +∞)", union.complement().to_string());
+        assert_eq!(union, union.complement().complement());
+        assert!(union.union(&union.complement()).is_total());
+        assert!(low.intersection(&high).is_empty());
+
+        let middle = IntervalSet::new_from_range(0.5..=1.5);
+        assert_eq!(
+            "[0.5, 1) ∪ (1, 1.5]",
+            union.intersection(&middle).to_string()
+        );
+        assert_eq!("[0, 0.5) ∪ (1.5, 2]", union.difference(&middle).to_string());
+        assert_eq!("[0, 2]", union.union(&middle).to_string());
+        assert_eq!(
+            "(-∞, 1) ∪ (1, +∞)",
+            union
+                .union(&IntervalSet::new_from_range(..0.5))
+                .union(&IntervalSet::new_from_range(1.5..))
+                .to_string()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use std::ops::RangeBounds;
+
+use crate::{integer::NumericInteger, range_to_bounds};
+
+/// A structure holding possibly overlapping intervals with a value attached to each of them, to answer stabbing and overlap queries.
+///
+/// The intervals are stored sorted by their lower bound in an implicit balanced binary tree, each node being augmented with the greatest upper bound of its subtree.
+/// Queries are answered in `O(log n + k)` where `k` is the number of intervals returned.
+#[derive(Clone, Debug)]
+pub struct IntervalTree<T: NumericInteger, V> {
+    intervals: Vec<((T, T), V)>,
+    /// The greatest upper bound of the subtree rooted at each node.
+    max: Vec<T>,
+    /// The level of the root of the tree.
+    root_level: usize,
+}
+
+impl<T: NumericInteger, V> Default for IntervalTree<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: NumericInteger, R: RangeBounds<T>, V> FromIterator<(R, V)> for IntervalTree<T, V> {
+    fn from_iter<I: IntoIterator<Item = (R, V)>>(iter: I) -> Self {
+        let mut intervals: Vec<((T, T), V)> = iter
+            .into_iter()
+            .map(|(range, value)| (range_to_bounds(&range), value))
+            .filter(|((min, max), _)| min <= max)
+            .collect();
+        intervals.sort_by_key(|(bounds, _)| *bounds);
+
+        let mut tree = IntervalTree {
+            intervals,
+            max: vec![],
+            root_level: 0,
+        };
+        tree.index();
+        tree
+    }
+}
+
+impl<T: NumericInteger, V> IntervalTree<T, V> {
+    /// Create a new instance that does not hold any interval.
+    pub fn new() -> IntervalTree<T, V> {
+        IntervalTree {
+            intervals: vec![],
+            max: vec![],
+            root_level: 0,
+        }
+    }
+
+    /// Return the number of intervals held.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Return `true` if it does not hold any interval.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Return an iterator over all the intervals held, sorted by bounds.
+    pub fn iter(&self) -> impl Iterator<Item = (&(T, T), &V)> {
+        self.intervals.iter().map(|(bounds, value)| (bounds, value))
+    }
+
+    /// Add the given interval with its value. Empty ranges are ignored.
+    ///
+    /// The tree is rebuilt in `O(n)`, prefer collecting from an iterator to add many intervals.
+    pub fn insert<R: RangeBounds<T>>(&mut self, range: R, value: V) {
+        let bounds = range_to_bounds(&range);
+        if bounds.0 > bounds.1 {
+            return;
+        }
+        let position = self.intervals.partition_point(|(b, _)| *b <= bounds);
+        self.intervals.insert(position, (bounds, value));
+        self.index();
+    }
+
+    /// Return the intervals containing the given value, sorted by bounds.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::interval_tree::IntervalTree;
+    ///
+    /// let tree: IntervalTree<u32, &str> =
+    ///     [(0..10, "a"), (5..15, "b"), (20..30, "c")].into_iter().collect();
+    ///
+    /// let values: Vec<_> = tree.stab(7).map(|(_, value)| *value).collect();
+    /// assert_eq!(vec!["a", "b"], values);
+    /// ```
+    pub fn stab(&self, value: T) -> impl Iterator<Item = (&(T, T), &V)> {
+        self.overlapping(value..=value)
+    }
+
+    /// Return the intervals having at least one value in common with the given range, sorted by bounds.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::interval_tree::IntervalTree;
+    ///
+    /// let tree: IntervalTree<u32, &str> =
+    ///     [(0..10, "a"), (5..15, "b"), (20..30, "c")].into_iter().collect();
+    ///
+    /// let values: Vec<_> = tree.overlapping(12..=20).map(|(_, value)| *value).collect();
+    /// assert_eq!(vec!["b", "c"], values);
+    /// ```
+    pub fn overlapping<R: RangeBounds<T>>(&self, range: R) -> impl Iterator<Item = (&(T, T), &V)> {
+        let (min, max) = range_to_bounds(&range);
+        let mut found = Vec::new();
+        if min <= max && !self.is_empty() {
+            self.search(min, max, &mut found);
+        }
+        found.into_iter().map(|i| {
+            let (bounds, value) = &self.intervals[i];
+            (bounds, value)
+        })
+    }
+
+    /// Compute the greatest upper bound of each subtree.
+    fn index(&mut self) {
+        let n = self.intervals.len();
+        self.max = self.intervals.iter().map(|((_, max), _)| *max).collect();
+        if n == 0 {
+            self.root_level = 0;
+            return;
+        }
+
+        let mut last_i = (n - 1) & !1;
+        let mut last = self.max[last_i];
+        let mut k = 1;
+        while 1 << k <= n {
+            let x = 1 << (k - 1);
+            let mut i = (x << 1) - 1;
+            while i < n {
+                let left = self.max[i - x];
+                let right = if i + x < n { self.max[i + x] } else { last };
+                self.max[i] = std::cmp::max(self.max[i], std::cmp::max(left, right));
+                i += x << 2;
+            }
+            last_i = if (last_i >> k) & 1 == 1 {
+                last_i - x
+            } else {
+                last_i + x
+            };
+            if last_i < n && self.max[last_i] > last {
+                last = self.max[last_i];
+            }
+            k += 1;
+        }
+        self.root_level = k - 1;
+    }
+
+    fn search(&self, min: T, max: T, found: &mut Vec<usize>) {
+        let n = self.intervals.len();
+        let bounds = |i: usize| self.intervals[i].0;
+
+        // (node, level, left subtree already visited)
+        let mut stack = vec![((1 << self.root_level) - 1, self.root_level, false)];
+        while let Some((x, k, visited)) = stack.pop() {
+            if k <= 3 {
+                // Small subtree: linear scan
+                let i0 = x >> k << k;
+                let i1 = std::cmp::min(i0 + (1 << (k + 1)) - 1, n);
+                for i in i0..i1 {
+                    let (start, end) = bounds(i);
+                    if start > max {
+                        break;
+                    }
+                    if min <= end {
+                        found.push(i);
+                    }
+                }
+            } else if !visited {
+                stack.push((x, k, true));
+                let y = x - (1 << (k - 1));
+                if y >= n || self.max[y] >= min {
+                    stack.push((y, k - 1, false));
+                }
+            } else if x < n && bounds(x).0 <= max {
+                if min <= bounds(x).1 {
+                    found.push(x);
+                }
+                stack.push((x + (1 << (k - 1)), k - 1, false));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_against_linear_scan() -> Result<(), String> {
+        let mut rng = StdRng::seed_from_u64(42);
+        for n in [0, 1, 2, 3, 7, 8, 9, 15, 16, 17, 100, 1000] {
+            let intervals: Vec<(std::ops::RangeInclusive<u16>, usize)> = (0..n)
+                .map(|i| {
+                    let start = rng.gen_range(0..5000);
+                    (start..=start + rng.gen_range(0..200), i)
+                })
+                .collect();
+            let tree: IntervalTree<u16, usize> = intervals.iter().cloned().collect();
+            assert_eq!(n, tree.len());
+
+            for _ in 0..200 {
+                let min = rng.gen_range(0..5300);
+                let max = min + rng.gen_range(0..50);
+
+                let mut expected: Vec<usize> = intervals
+                    .iter()
+                    .filter(|(range, _)| *range.start() <= max && min <= *range.end())
+                    .map(|(_, i)| *i)
+                    .collect();
+                expected.sort();
+                let mut actual: Vec<usize> = tree.overlapping(min..=max).map(|(_, i)| *i).collect();
+                actual.sort();
+                assert_eq!(expected, actual);
+
+                let expected = intervals
+                    .iter()
+                    .filter(|(range, _)| range.contains(&min))
+                    .count();
+                assert_eq!(expected, tree.stab(min).count());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert() -> Result<(), String> {
+        let mut tree = IntervalTree::new();
+        tree.insert(10..=20, 'a');
+        tree.insert(0..=u8::MAX, 'b');
+        tree.insert(5..5, 'c');
+        tree.insert(15.., 'd');
+
+        assert_eq!(3, tree.len());
+        assert_eq!(
+            vec!['b', 'a', 'd'],
+            tree.stab(17).map(|(_, v)| *v).collect::<Vec<_>>()
+        );
+        assert_eq!(vec!['b'], tree.stab(0).map(|(_, v)| *v).collect::<Vec<_>>());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+//! Kernels of the merge loops of the set operations.
+//!
+//! When one set has many ranges lying between two ranges of the other, the merge loops skip them by blocks, or copy them at once for the union: the bounds of a block are compared with the pivot without branches, which is compiled to SIMD comparisons.
+//! On `x86_64` with the `std` feature flag, a version compiled for AVX2 is selected at runtime if the CPU supports it.
+
+use crate::integer::NumericInteger;
+
+/// The number of bounds compared at once.
+const BLOCK: usize = 64;
+
+/// Return the number of bounds of the block lower than `pivot`, the block being sorted.
+#[inline(always)]
+fn count_below<T: NumericInteger>(block: &[T], pivot: T) -> usize {
+    block.iter().map(|&bound| (bound < pivot) as usize).sum()
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+#[target_feature(enable = "avx2")]
+fn count_below_avx2<T: NumericInteger>(block: &[T], pivot: T) -> usize {
+    count_below(block, pivot)
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+fn skip_blocks<T: NumericInteger>(bounds: &[T], from: usize, pivot: T) -> usize {
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2.
+        skip_blocks_with(bounds, from, pivot, |block, pivot| unsafe {
+            count_below_avx2(block, pivot)
+        })
+    } else {
+        skip_blocks_with(bounds, from, pivot, count_below)
+    }
+}
+
+#[cfg(not(all(target_arch = "x86_64", feature = "std")))]
+fn skip_blocks<T: NumericInteger>(bounds: &[T], from: usize, pivot: T) -> usize {
+    skip_blocks_with(bounds, from, pivot, count_below)
+}
+
+#[inline(always)]
+fn skip_blocks_with<T: NumericInteger>(
+    bounds: &[T],
+    mut from: usize,
+    pivot: T,
+    count_below: impl Fn(&[T], T) -> usize,
+) -> usize {
+    while let Some(block) = bounds.get(from..from + BLOCK) {
+        let count = count_below(block, pivot);
+        if count < BLOCK {
+            return from + count;
+        }
+        from += BLOCK;
+    }
+    from + count_below(&bounds[from..], pivot)
+}
+
+/// Return the index of the first range from the one at index `from` whose maximum is greater than or equal to `pivot`, `bounds.len()` if there is none.
+///
+/// `from` and the returned index are indices of bounds, so they are even.
+#[inline]
+pub(crate) fn skip_ranges_below<T: NumericInteger>(bounds: &[T], from: usize, pivot: T) -> usize {
+    // The next range is checked first, since the sets usually interleave.
+    if bounds.get(from + 1).is_none_or(|&max| max >= pivot) {
+        return from;
+    }
+    let position = skip_blocks(bounds, from + 2, pivot);
+    // The range containing the pivot is not skipped.
+    position & !1
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::RangeSet;
+
+    use super::*;
+
+    #[test]
+    fn test_skip_ranges_below() -> Result<(), String> {
+        let mut rng = StdRng::seed_from_u64(194);
+        for len in [0, 1, 7, 8, 9, 50, 200] {
+            let mut values: Vec<i64> = (0..len * 2).map(|_| rng.gen_range(-1000..=1000)).collect();
+            values.sort();
+            let range = RangeSet::new_from_iter(values.chunks_exact(2).map(|r| r[0]..=r[1]));
+            for from in (0..=range.0.len()).step_by(2) {
+                for pivot in -1001..=1001 {
+                    let mut expected = from;
+                    while expected < range.0.len() && range.0[expected + 1] < pivot {
+                        expected += 2;
+                    }
+                    assert_eq!(expected, skip_ranges_below(&range.0, from, pivot));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
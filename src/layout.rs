@@ -0,0 +1,196 @@
+//! An explicit, endian-stable memory layout of the ranges of a `RangeSet`.
+//!
+//! With the `bytemuck` or `zerocopy` feature flags, slices of `BoundPair` can be cast from and to bytes without serialization, for instance to share them through a shared memory segment.
+
+use crate::{
+    is_canonical,
+    view::{PrimitiveInteger, ViewError},
+    RangeSet,
+};
+
+/// A primitive integer stored in little-endian byte order, whatever the endianness of the target.
+///
+/// It is `#[repr(transparent)]` so that it has exactly the size and alignment of the integer.
+#[repr(transparent)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
+pub struct LittleEndian<T: PrimitiveInteger>(T);
+
+impl<T: PrimitiveInteger> LittleEndian<T> {
+    /// Store the given value.
+    #[inline]
+    pub fn new(value: T) -> LittleEndian<T> {
+        LittleEndian(value.to_le())
+    }
+
+    /// Return the value stored.
+    #[inline]
+    pub fn get(self) -> T {
+        T::from_le(self.0)
+    }
+}
+
+/// The inclusive bounds `[min, max]` of a range, with a `#[repr(C)]` layout and no padding: the lower bound followed by the upper bound.
+#[repr(C)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
+pub struct BoundPair<T: PrimitiveInteger>([LittleEndian<T>; 2]);
+
+impl<T: PrimitiveInteger> BoundPair<T> {
+    /// Create a new instance from the given inclusive bounds.
+    #[inline]
+    pub fn new(min: T, max: T) -> BoundPair<T> {
+        BoundPair([LittleEndian::new(min), LittleEndian::new(max)])
+    }
+
+    /// Return the lower bound (inclusive).
+    #[inline]
+    pub fn min(&self) -> T {
+        self.0[0].get()
+    }
+
+    /// Return the upper bound (inclusive).
+    #[inline]
+    pub fn max(&self) -> T {
+        self.0[1].get()
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+// SAFETY: `LittleEndian` is a transparent wrapper around a primitive integer.
+unsafe impl<T: PrimitiveInteger> bytemuck::Zeroable for LittleEndian<T> {}
+
+#[cfg(feature = "bytemuck")]
+// SAFETY: `LittleEndian` is a transparent wrapper around a primitive integer, any bit pattern is valid.
+unsafe impl<T: PrimitiveInteger + 'static> bytemuck::Pod for LittleEndian<T> {}
+
+#[cfg(feature = "bytemuck")]
+// SAFETY: `BoundPair` is a `#[repr(C)]` struct holding an array of `LittleEndian`.
+unsafe impl<T: PrimitiveInteger> bytemuck::Zeroable for BoundPair<T> {}
+
+#[cfg(feature = "bytemuck")]
+// SAFETY: `BoundPair` is a `#[repr(C)]` struct holding an array of `LittleEndian`, without padding.
+unsafe impl<T: PrimitiveInteger + 'static> bytemuck::Pod for BoundPair<T> {}
+
+impl<T: PrimitiveInteger> RangeSet<T> {
+    /// Return the ranges contained as `BoundPair`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u16>(vec![2, 5, 10, 20]);
+    /// let pairs = range.to_bound_pairs();
+    ///
+    /// assert_eq!(2, pairs.len());
+    /// assert_eq!((10, 20), (pairs[1].min(), pairs[1].max()));
+    /// assert_eq!(Ok(range), RangeSet::from_bound_pairs(&pairs));
+    /// ```
+    pub fn to_bound_pairs(&self) -> Vec<BoundPair<T>> {
+        self.0
+            .chunks_exact(2)
+            .map(|bounds| BoundPair::new(bounds[0], bounds[1]))
+            .collect()
+    }
+
+    /// Create a new instance from the given `BoundPair`, checking that they are sorted, neither overlapping nor adjacent.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::layout::BoundPair;
+    /// use irange::view::ViewError;
+    ///
+    /// let pairs = [BoundPair::<u16>::new(2, 5), BoundPair::new(6, 20)];
+    /// assert_eq!(Err(ViewError::NotCanonical), RangeSet::from_bound_pairs(&pairs));
+    /// ```
+    pub fn from_bound_pairs(pairs: &[BoundPair<T>]) -> Result<RangeSet<T>, ViewError> {
+        let mut bounds = Vec::with_capacity(pairs.len() * 2);
+        for pair in pairs {
+            bounds.push(pair.min());
+            bounds.push(pair.max());
+        }
+        if !is_canonical(&bounds) {
+            return Err(ViewError::NotCanonical);
+        }
+        Ok(RangeSet(bounds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() -> Result<(), String> {
+        assert_eq!(16, std::mem::size_of::<BoundPair<u64>>());
+        assert_eq!(8, std::mem::align_of::<BoundPair<u64>>());
+
+        let pair = BoundPair::<u32>::new(0x01020304, 0x0a0b0c0d);
+        let bytes: [u8; 8] = unsafe { std::mem::transmute(pair) };
+        assert_eq!([4, 3, 2, 1, 0x0d, 0x0c, 0x0b, 0x0a], bytes);
+
+        let pairs = [BoundPair::<i8>::new(5, 10), BoundPair::new(-3, 0)];
+        assert_eq!(
+            Err(ViewError::NotCanonical),
+            RangeSet::from_bound_pairs(&pairs)
+        );
+        assert_eq!(
+            Err(ViewError::NotCanonical),
+            RangeSet::from_bound_pairs(&[BoundPair::<i8>::new(1, 0)])
+        );
+        assert_eq!(Ok(RangeSet::empty()), RangeSet::<u8>::from_bound_pairs(&[]));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "bytemuck")]
+    fn test_bytemuck() -> Result<(), String> {
+        let range = RangeSet::<i32>(vec![-5, 5, 10, 20]);
+        let pairs = range.to_bound_pairs();
+        let bytes: &[u8] = bytemuck::cast_slice(&pairs);
+        assert_eq!(16, bytes.len());
+        assert_eq!([0xfb, 0xff, 0xff, 0xff, 5, 0, 0, 0], bytes[..8]);
+
+        let pairs: &[BoundPair<i32>] = bytemuck::cast_slice(bytes);
+        assert_eq!(Ok(range), RangeSet::from_bound_pairs(pairs));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "zerocopy")]
+    fn test_zerocopy() -> Result<(), String> {
+        use zerocopy::{FromBytes, IntoBytes};
+
+        let range = RangeSet::<u64>(vec![1, 2, 3 << 40, u64::MAX]);
+        let pairs = range.to_bound_pairs();
+        let bytes = pairs.as_bytes();
+        assert_eq!(32, bytes.len());
+        assert_eq!([1, 0, 0, 0, 0, 0, 0, 0], bytes[..8]);
+
+        let pairs = <[BoundPair<u64>]>::ref_from_bytes(bytes).unwrap();
+        assert_eq!(Ok(range), RangeSet::from_bound_pairs(pairs));
+
+        Ok(())
+    }
+}
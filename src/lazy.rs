@@ -0,0 +1,194 @@
+use crate::{integer::NumericInteger, RangeSet};
+
+/// A `RangeSet` wrapper whose complement is computed in constant time.
+///
+/// The set is stored with a flag telling if it stands for the complement of the stored values.
+/// The set operations are rewritten with De Morgan's laws to work on the stored values directly, so the complement is only computed when the values are needed as a `RangeSet`.
+#[derive(Eq, Clone, Debug)]
+pub struct LazyRangeSet<T: NumericInteger> {
+    set: RangeSet<T>,
+    complemented: bool,
+}
+
+impl<T: NumericInteger> PartialEq for LazyRangeSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.complemented == other.complemented {
+            self.set == other.set
+        } else {
+            self.set == other.set.complement()
+        }
+    }
+}
+
+impl<T: NumericInteger> From<RangeSet<T>> for LazyRangeSet<T> {
+    fn from(set: RangeSet<T>) -> Self {
+        LazyRangeSet {
+            set,
+            complemented: false,
+        }
+    }
+}
+
+impl<T: NumericInteger> From<LazyRangeSet<T>> for RangeSet<T> {
+    fn from(value: LazyRangeSet<T>) -> Self {
+        value.into_range_set()
+    }
+}
+
+impl<T: NumericInteger> std::ops::Not for LazyRangeSet<T> {
+    type Output = LazyRangeSet<T>;
+
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+impl<T: NumericInteger> LazyRangeSet<T> {
+    /// Return the complement, in constant time.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::lazy::LazyRangeSet;
+    ///
+    /// let allowed = LazyRangeSet::from(RangeSet::<u32>(vec![0, 99]));
+    /// let blocked = LazyRangeSet::from(RangeSet::<u32>(vec![10, 19]));
+    ///
+    /// // Computed as `allowed - blocked`, without complementing `blocked`.
+    /// let result = allowed.intersection(&blocked.complement());
+    /// assert!(!result.is_complemented());
+    /// assert_eq!(RangeSet(vec![0, 9, 20, 99]), result.into_range_set());
+    /// ```
+    #[inline]
+    pub fn complement(self) -> LazyRangeSet<T> {
+        LazyRangeSet {
+            set: self.set,
+            complemented: !self.complemented,
+        }
+    }
+
+    /// Return `true` if the values contained are the complement of the stored ones.
+    #[inline]
+    pub fn is_complemented(&self) -> bool {
+        self.complemented
+    }
+
+    /// Return the values contained as a `RangeSet`, computing the complement if needed.
+    pub fn into_range_set(self) -> RangeSet<T> {
+        if self.complemented {
+            self.set.complement()
+        } else {
+            self.set
+        }
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        if self.complemented {
+            self.set.is_total()
+        } else {
+            self.set.is_empty()
+        }
+    }
+
+    /// Return `true` if it contains all the possible values.
+    #[inline]
+    pub fn is_total(&self) -> bool {
+        if self.complemented {
+            self.set.is_empty()
+        } else {
+            self.set.is_total()
+        }
+    }
+
+    /// Return `true` if it contains the given value.
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.set.contains(value) != self.complemented
+    }
+
+    /// Return the union with the given `LazyRangeSet`.
+    pub fn union(&self, that: &LazyRangeSet<T>) -> LazyRangeSet<T> {
+        let (a, b) = (&self.set, &that.set);
+        match (self.complemented, that.complemented) {
+            (false, false) => LazyRangeSet::from(a.union(b)),
+            (true, true) => LazyRangeSet::from(a.intersection(b)).complement(),
+            (false, true) => LazyRangeSet::from(b.difference(a)).complement(),
+            (true, false) => LazyRangeSet::from(a.difference(b)).complement(),
+        }
+    }
+
+    /// Return the intersection with the given `LazyRangeSet`.
+    pub fn intersection(&self, that: &LazyRangeSet<T>) -> LazyRangeSet<T> {
+        let (a, b) = (&self.set, &that.set);
+        match (self.complemented, that.complemented) {
+            (false, false) => LazyRangeSet::from(a.intersection(b)),
+            (true, true) => LazyRangeSet::from(a.union(b)).complement(),
+            (false, true) => LazyRangeSet::from(a.difference(b)),
+            (true, false) => LazyRangeSet::from(b.difference(a)),
+        }
+    }
+
+    /// Return the difference with the given `LazyRangeSet`.
+    pub fn difference(&self, that: &LazyRangeSet<T>) -> LazyRangeSet<T> {
+        let that = LazyRangeSet {
+            set: that.set.clone(),
+            complemented: !that.complemented,
+        };
+        self.intersection(&that)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations() -> Result<(), String> {
+        let sets = [
+            RangeSet::<u8>::empty(),
+            RangeSet::<u8>::total(),
+            RangeSet::<u8>(vec![0, 10, 20, 30]),
+            RangeSet::<u8>(vec![5, 25, 255, 255]),
+        ];
+        for a in &sets {
+            for b in &sets {
+                for (a_complemented, b_complemented) in
+                    [(false, false), (false, true), (true, false), (true, true)]
+                {
+                    let (mut lazy_a, mut lazy_b) =
+                        (LazyRangeSet::from(a.clone()), LazyRangeSet::from(b.clone()));
+                    let (mut a, mut b) = (a.clone(), b.clone());
+                    if a_complemented {
+                        lazy_a = !lazy_a;
+                        a = a.complement();
+                    }
+                    if b_complemented {
+                        lazy_b = !lazy_b;
+                        b = b.complement();
+                    }
+
+                    assert_eq!(a.union(&b), lazy_a.union(&lazy_b).into_range_set());
+                    assert_eq!(
+                        a.intersection(&b),
+                        lazy_a.intersection(&lazy_b).into_range_set()
+                    );
+                    assert_eq!(
+                        a.difference(&b),
+                        lazy_a.difference(&lazy_b).into_range_set()
+                    );
+                    assert_eq!(a.is_empty(), lazy_a.is_empty());
+                    assert_eq!(a.is_total(), lazy_a.is_total());
+                    assert_eq!(a == b, lazy_a == lazy_b);
+                    for value in [0, 5, 11, 25, 254, 255] {
+                        assert_eq!(a.contains(value), lazy_a.contains(value));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,13 +1,116 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(feature = "serde")]
 pub use serde::{Deserialize, Serialize};
 
-use std::ops::{Bound, RangeBounds};
+// Allows the code generated by `irange-derive` to refer to `::irange` in the tests of this crate.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as irange;
+
+use core::ops::{Bound, RangeBounds};
+#[cfg(feature = "alloc")]
+use core::{
+    cmp::Ordering,
+    ops::{ControlFlow, Range, RangeInclusive},
+};
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 
+#[cfg(feature = "alloc")]
+use display::{LimitedDisplay, MathDisplay};
+#[cfg(feature = "alloc")]
+use entry::Entry;
+#[cfg(feature = "alloc")]
+use integer::Measure;
 use integer::NumericInteger;
+#[cfg(feature = "alloc")]
 use range::AnyRange;
+#[cfg(feature = "alloc")]
+use stream::{Difference, Intersection, Union, Values};
 
+#[cfg(feature = "std")]
+pub mod ack;
+#[cfg(feature = "std")]
+pub mod alphabet;
+#[cfg(feature = "std")]
+pub mod btree;
+#[cfg(feature = "clap")]
+pub mod cli;
+#[cfg(feature = "std")]
+pub mod columnar;
+#[cfg(feature = "std")]
+pub mod delta;
+#[cfg(feature = "std")]
+pub mod discriminant;
+#[cfg(feature = "alloc")]
+pub mod display;
+#[cfg(feature = "std")]
+pub mod domain;
+#[cfg(feature = "alloc")]
+pub mod entry;
+#[cfg(feature = "std")]
+pub mod eytzinger;
+pub mod fixed;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod index;
 pub mod integer;
+#[cfg(feature = "std")]
+pub mod interval;
+#[cfg(feature = "std")]
+pub mod interval_tree;
+#[cfg(feature = "alloc")]
+mod kernel;
+#[cfg(feature = "std")]
+pub mod layout;
+#[cfg(feature = "std")]
+pub mod lazy;
+#[cfg(feature = "std")]
+pub mod multiset;
+#[cfg(feature = "std")]
+pub mod non_empty;
+#[cfg(feature = "std")]
+pub mod observer;
+#[cfg(feature = "std")]
+pub mod paged;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "sqlx-postgres")]
+mod postgres;
+#[cfg(feature = "alloc")]
 pub mod range;
+#[cfg(feature = "std")]
+pub mod range2d;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "std")]
+pub mod run_length;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+#[cfg(feature = "arc-swap")]
+pub mod snapshot;
+#[cfg(feature = "alloc")]
+pub mod sql;
+#[cfg(feature = "alloc")]
+pub mod stream;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub mod timestamp;
+#[cfg(feature = "unicode")]
+mod unicode;
+#[cfg(feature = "std")]
+pub mod view;
+#[cfg(feature = "std")]
+pub mod wrapping;
 
 fn range_to_bounds<T: NumericInteger, R: RangeBounds<T>>(range: &R) -> (T, T) {
     let min = match range.start_bound() {
@@ -24,16 +127,152 @@ fn range_to_bounds<T: NumericInteger, R: RangeBounds<T>>(range: &R) -> (T, T) {
     (min, max)
 }
 
+/// Return `true` if the bounds are sorted pairs of ranges, neither overlapping nor adjacent.
+#[cfg(feature = "std")]
+fn is_canonical<T: NumericInteger>(bounds: &[T]) -> bool {
+    bounds.len().is_multiple_of(2)
+        && bounds.chunks_exact(2).all(|range| range[0] <= range[1])
+        && bounds
+            .windows(2)
+            .skip(1)
+            .step_by(2)
+            .all(|gap| gap[0] < T::max_value() && gap[0] + T::one() < gap[1])
+}
+
+/// Panic with the first violation of the canonical form of the bounds, naming the operation that received or produced them.
+#[cfg(all(feature = "alloc", feature = "strict-invariants"))]
+#[track_caller]
+fn check_canonical<T: NumericInteger>(bounds: &[T], operation: &str, stage: &str) {
+    if !bounds.len().is_multiple_of(2) {
+        panic!(
+            "the {stage} of `{operation}` has an odd number of bounds: {}",
+            bounds.len()
+        );
+    }
+    for (i, range) in bounds.chunks_exact(2).enumerate() {
+        if range[0] > range[1] {
+            panic!(
+                "the {stage} of `{operation}` has the inverted range {}..={} at index {i}",
+                range[0], range[1]
+            );
+        }
+        if i > 0 {
+            let previous = bounds[2 * i - 1];
+            if previous >= range[0] || previous + T::one() == range[0] {
+                panic!(
+                    "the {stage} of `{operation}` has the range {}..={} at index {i} overlapping or adjacent to the previous one ending at {previous}",
+                    range[0], range[1]
+                );
+            }
+        }
+    }
+}
+
+/// Check the canonical form of the given sets when the `strict-invariants` feature is enabled, do nothing otherwise.
+///
+/// It is called on the sets received and returned by every public constructor, mutator and operation, directly or through the one it delegates to, including each of the sets returned by `partition`, `partition_around` and the chunk iterators.
+/// The exceptions are the constructors canonical by construction (`empty`, `total`, `singleton` and the ASCII classes) and the methods only reading the bounds, such as the iterators and the statistics.
+#[cfg(feature = "alloc")]
+macro_rules! check_invariants {
+    ($operation:expr, $stage:expr, $($set:expr),+) => {
+        #[cfg(feature = "strict-invariants")]
+        {
+            $(check_canonical(&$set.0, $operation, $stage);)+
+        }
+    };
+}
+
+/// The number of bounds up to which `contains` scans them linearly instead of searching them.
+#[cfg(feature = "alloc")]
+const LINEAR_SCAN_BOUNDS: usize = 16;
+
+/// Return `true` if `value` is contained, `position` being the number of bounds lower than it.
+#[cfg(feature = "alloc")]
+#[inline]
+fn is_contained_at<T: NumericInteger>(bounds: &[T], position: usize, value: T) -> bool {
+    position < bounds.len() && (!position.is_multiple_of(2) || bounds[position] == value)
+}
+
+/// Return `true` if a range starting at `min`, not before the last range of `bounds`, overlaps or is adjacent to it.
+#[cfg(feature = "alloc")]
+#[inline]
+fn touches_last<T: NumericInteger>(bounds: &[T], min: T) -> bool {
+    bounds
+        .last()
+        .is_some_and(|&last| last == T::max_value() || min <= last + T::one())
+}
+
+/// Append the range `min..=max`, starting not before the last range of `bounds`, merging it with the last range if they touch.
+#[cfg(feature = "alloc")]
+#[inline]
+fn push_merged<T: NumericInteger>(bounds: &mut Vec<T>, min: T, max: T) {
+    if touches_last(bounds, min) {
+        let last = bounds.last_mut().unwrap();
+        if max > *last {
+            *last = max;
+        }
+    } else {
+        bounds.extend_from_slice(&[min, max]);
+    }
+}
+
+/// Append the sorted ranges of `ranges`, starting not before the last range of `bounds`, merging the first ones with it if they touch.
+#[cfg(feature = "alloc")]
+#[inline]
+fn extend_merged<T: NumericInteger>(bounds: &mut Vec<T>, mut ranges: &[T]) {
+    // Only the first ranges can touch the last range, the others are copied at once.
+    while let [min, max, rest @ ..] = ranges {
+        if !touches_last(bounds, *min) {
+            break;
+        }
+        push_merged(bounds, *min, *max);
+        ranges = rest;
+    }
+    bounds.extend_from_slice(ranges);
+}
+
+/// Append the range of `ranges` at index `i` and the following ones ending before `pivot`, returning the index of the next range.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn push_merged_below<T: NumericInteger>(
+    bounds: &mut Vec<T>,
+    ranges: &[T],
+    i: usize,
+    pivot: T,
+) -> usize {
+    push_merged(bounds, ranges[i], ranges[i + 1]);
+    // The following ranges are usually not below the pivot, they are appended at once otherwise.
+    let end = kernel::skip_ranges_below(ranges, i + 2, pivot);
+    if end > i + 2 {
+        extend_merged(bounds, &ranges[i + 2..end]);
+    }
+    end
+}
+
+/// Return `a * b`, `None` if it does not fit in `i128`.
+#[cfg(feature = "alloc")]
+fn checked_mul_unsigned(a: u128, b: i128) -> Option<i128> {
+    match i128::try_from(a) {
+        Ok(a) => a.checked_mul(b),
+        Err(_) => match b {
+            0 => Some(0),
+            -1 if a == 1 << 127 => Some(i128::MIN),
+            _ => None,
+        },
+    }
+}
+
 /// A structure holding a collection of `u8`, `u16`, `u32`, `u64`, `u128`, `usize`, `i8`, `i16`, `i32`, `i64`, `i128` or `isize`.
-#[derive(PartialEq, Eq, Hash, Clone, Debug, PartialOrd, Ord)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
 pub struct RangeSet<T: NumericInteger>(
     /// In this collection all the elements with even index represent the lower bounds (inclusive) and all the odd index represent the upper bounds (inclusive).
     pub Vec<T>,
 );
 
-impl<T: NumericInteger> std::fmt::Display for RangeSet<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+#[cfg(feature = "alloc")]
+impl<T: NumericInteger> core::fmt::Display for RangeSet<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "[ ")?;
         for i in (0..self.0.len()).step_by(2) {
             let (min, max) = (self.0[i], self.0[i + 1]);
@@ -43,49 +282,459 @@ impl<T: NumericInteger> std::fmt::Display for RangeSet<T> {
     }
 }
 
+/// Display the bounds like a derived implementation, such as `RangeSet([2, 4, 7, 9])`.
+///
+/// The alternate form `{:#?}` displays the ranges as `min..=max` entries along with the number of ranges and the number of values contained, saturating at `u128::MAX`.
+#[cfg(feature = "alloc")]
+impl<T: NumericInteger + core::fmt::Debug> core::fmt::Debug for RangeSet<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if !f.alternate() {
+            return f.debug_tuple("RangeSet").field(&self.0).finish();
+        }
+
+        /// The ranges displayed as a list of `min..=max`.
+        struct Ranges<'a, T>(&'a [T]);
+
+        impl<T: core::fmt::Debug + Copy> core::fmt::Debug for Ranges<'_, T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.debug_list()
+                    .entries(self.0.chunks_exact(2).map(|range| range[0]..=range[1]))
+                    .finish()
+            }
+        }
+
+        f.debug_struct("RangeSet")
+            .field("ranges", &Ranges(&self.0))
+            .field("range_count", &(self.0.len() / 2))
+            .field(
+                "cardinality",
+                &self.covered_len::<u128>().unwrap_or(u128::MAX),
+            )
+            .finish()
+    }
+}
+
+/// Equal if it contains exactly the values of the range.
+#[cfg(feature = "alloc")]
+impl<T: NumericInteger> PartialEq<RangeInclusive<T>> for RangeSet<T> {
+    fn eq(&self, other: &RangeInclusive<T>) -> bool {
+        if other.is_empty() {
+            self.is_empty()
+        } else {
+            self.0 == [*other.start(), *other.end()]
+        }
+    }
+}
+
+/// Equal if it contains exactly the values of the range.
+#[cfg(feature = "alloc")]
+impl<T: NumericInteger> PartialEq<Range<T>> for RangeSet<T> {
+    fn eq(&self, other: &Range<T>) -> bool {
+        if other.is_empty() {
+            self.is_empty()
+        } else {
+            self.0 == [other.start, other.end - T::one()]
+        }
+    }
+}
+
+/// Equal if it contains exactly the values of the ranges, which can be in any order and overlap.
+#[cfg(feature = "alloc")]
+impl<T: NumericInteger> PartialEq<&[RangeInclusive<T>]> for RangeSet<T> {
+    fn eq(&self, other: &&[RangeInclusive<T>]) -> bool {
+        *self == RangeSet::new_from_iter(other.iter().cloned())
+    }
+}
+
+/// Parse the format of `Display`, such as `[ 2..=4 7..=9 ]`, see `RangeSet::from_str_radix`.
+#[cfg(feature = "alloc")]
+impl<T: NumericInteger> core::str::FromStr for RangeSet<T> {
+    type Err = ParseRangeSetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RangeSet::from_str_radix(s, 10)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "defmt"))]
+impl<T: NumericInteger + defmt::Format> defmt::Format for RangeSet<T> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "[ ");
+        for i in (0..self.0.len()).step_by(2) {
+            defmt::write!(f, "{}..={} ", self.0[i], self.0[i + 1]);
+        }
+        defmt::write!(f, "]");
+    }
+}
+
+#[cfg(feature = "icu")]
+impl From<&icu_collections::codepointinvlist::CodePointInversionList<'_>> for RangeSet<u32> {
+    fn from(value: &icu_collections::codepointinvlist::CodePointInversionList<'_>) -> Self {
+        RangeSet(
+            value
+                .iter_ranges()
+                .flat_map(|range| [*range.start(), *range.end()])
+                .collect(),
+        )
+    }
+}
+
+/// Convert a set of code points, failing if it contains a value greater than `0x10FFFF`.
+#[cfg(feature = "icu")]
+impl TryFrom<&RangeSet<u32>>
+    for icu_collections::codepointinvlist::CodePointInversionList<'static>
+{
+    type Error = icu_collections::codepointinvlist::InvalidSetError;
+
+    fn try_from(value: &RangeSet<u32>) -> Result<Self, Self::Error> {
+        let inversion_list: Vec<u32> = value
+            .0
+            .chunks_exact(2)
+            .flat_map(|range| [range[0], range[1].saturating_add(1)])
+            .collect();
+        Self::try_from_u32_inversion_list_slice(&inversion_list)
+    }
+}
+
+#[cfg(feature = "bitvec")]
+impl<T: bitvec::store::BitStore, O: bitvec::order::BitOrder> From<&bitvec::slice::BitSlice<T, O>>
+    for RangeSet<usize>
+{
+    fn from(value: &bitvec::slice::BitSlice<T, O>) -> Self {
+        let mut bounds = vec![];
+        let mut position = 0;
+        while let Some(start) = value[position..].first_one() {
+            let start = position + start;
+            let end = match value[start..].first_zero() {
+                Some(length) => start + length,
+                None => value.len(),
+            };
+            bounds.extend_from_slice(&[start, end - 1]);
+            position = end;
+        }
+        RangeSet(bounds)
+    }
+}
+
+/// Convert a set of indices to bits, the length being the largest index plus one.
+#[cfg(feature = "bitvec")]
+impl<T: bitvec::store::BitStore, O: bitvec::order::BitOrder> From<&RangeSet<usize>>
+    for bitvec::vec::BitVec<T, O>
+{
+    fn from(value: &RangeSet<usize>) -> Self {
+        let mut bits = bitvec::vec::BitVec::repeat(false, value.0.last().map_or(0, |&max| max + 1));
+        for range in value.0.chunks_exact(2) {
+            bits[range[0]..=range[1]].fill(true);
+        }
+        bits
+    }
+}
+
+#[cfg(feature = "fixedbitset")]
+impl From<&fixedbitset::FixedBitSet> for RangeSet<usize> {
+    fn from(value: &fixedbitset::FixedBitSet) -> Self {
+        const BITS: u32 = usize::BITS;
+        let mut bounds = vec![];
+        let mut start = None;
+        for (k, &block) in value.as_slice().iter().enumerate() {
+            let base = k * BITS as usize;
+            let mut position = 0;
+            while position < BITS {
+                let rest = block >> position;
+                match start {
+                    None => {
+                        if rest == 0 {
+                            break;
+                        }
+                        position += rest.trailing_zeros();
+                        start = Some(base + position as usize);
+                    }
+                    Some(min) => {
+                        position += rest.trailing_ones();
+                        if position == BITS {
+                            break;
+                        }
+                        bounds.extend_from_slice(&[min, base + position as usize - 1]);
+                        start = None;
+                    }
+                }
+            }
+        }
+        if let Some(min) = start {
+            bounds.extend_from_slice(&[min, value.as_slice().len() * BITS as usize - 1]);
+        }
+        RangeSet(bounds)
+    }
+}
+
+/// Convert a set of indices to bits, the length being the largest index plus one.
+#[cfg(feature = "fixedbitset")]
+impl From<&RangeSet<usize>> for fixedbitset::FixedBitSet {
+    fn from(value: &RangeSet<usize>) -> Self {
+        let mut bits =
+            fixedbitset::FixedBitSet::with_capacity(value.0.last().map_or(0, |&max| max + 1));
+        for range in value.0.chunks_exact(2) {
+            bits.insert_range(range[0]..range[1] + 1);
+        }
+        bits
+    }
+}
+
+/// # Panics
+///
+/// Panics if it contains `u128::MAX` or `i128::MAX`, which cannot be stored in a `RangeSetBlaze`.
+#[cfg(feature = "range-set-blaze")]
+impl<T: NumericInteger + range_set_blaze::Integer> From<&RangeSet<T>>
+    for range_set_blaze::RangeSetBlaze<T>
+{
+    fn from(value: &RangeSet<T>) -> Self {
+        value
+            .0
+            .chunks_exact(2)
+            .map(|range| range[0]..=range[1])
+            .collect()
+    }
+}
+
+#[cfg(feature = "range-set-blaze")]
+impl<T: NumericInteger + range_set_blaze::Integer> From<&range_set_blaze::RangeSetBlaze<T>>
+    for RangeSet<T>
+{
+    fn from(value: &range_set_blaze::RangeSetBlaze<T>) -> Self {
+        RangeSet(
+            value
+                .ranges()
+                .flat_map(|range| [*range.start(), *range.end()])
+                .collect(),
+        )
+    }
+}
+
+/// Serialized as an array of `[min, max]` pairs in human readable formats such as JSON, and as the flat array of bounds otherwise.
+///
+/// Human readable formats also accept the flat array of bounds when deserializing, as written before version 2.0.
+#[cfg(feature = "serde")]
+impl<T: NumericInteger + Serialize> Serialize for RangeSet<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serde_helpers::pairs::serialize(self, serializer)
+        } else {
+            serde_helpers::raw::serialize(self, serializer)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: NumericInteger + Deserialize<'de>> Deserialize<'de> for RangeSet<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            serde_helpers::deserialize_pairs_or_raw(deserializer)
+        } else {
+            serde_helpers::raw::deserialize(deserializer)
+        }
+    }
+}
+
 /// A structure to hold the iterator of a `RangeSet` instance.
+#[cfg(feature = "alloc")]
 pub struct RangeSetIter<'a, T: NumericInteger> {
-    range_set: &'a RangeSet<T>,
-    index: usize,
-    value: Option<T>,
+    /// The bounds of the ranges not started yet.
+    bounds: &'a [T],
+    /// The values of the current range not yielded yet.
+    current: Option<(T, T)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: NumericInteger> RangeSetIter<'a, T> {
+    /// Skip the values lower than the given value, so the next value returned is the smallest contained value greater than or equal to it.
+    ///
+    /// Does nothing if the iterator is already past the given value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![0, 3, 10, 12, 20, 25]);
+    /// let mut iter = range.iter();
+    ///
+    /// iter.advance_to(11);
+    /// assert_eq!(Some(11), iter.next());
+    /// iter.advance_to(13);
+    /// assert_eq!(Some(20), iter.next());
+    /// iter.advance_to(0);
+    /// assert_eq!(Some(21), iter.next());
+    /// ```
+    pub fn advance_to(&mut self, value: T) {
+        if let Some((min, max)) = self.current {
+            if value <= min {
+                return;
+            } else if value <= max {
+                self.current = Some((value, max));
+                return;
+            }
+            self.current = None;
+        }
+
+        let position = self.bounds.partition_point(|&x| x < value);
+        if position % 2 != 0 {
+            self.current = Some((value, self.bounds[position]));
+            self.bounds = &self.bounds[position + 1..];
+        } else {
+            self.bounds = &self.bounds[position..];
+        }
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T: NumericInteger> Iterator for RangeSetIter<'a, T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let (Some(min), Some(max)) = (
-            self.range_set.0.get(self.index),
-            self.range_set.0.get(self.index + 1),
-        ) {
-            if let Some(value) = self.value {
-                if value == *max {
-                    self.index += 2;
-                    if let (Some(min), Some(_)) = (
-                        self.range_set.0.get(self.index),
-                        self.range_set.0.get(self.index + 1),
-                    ) {
-                        self.value = Some(*min);
-                        Some(*min)
-                    } else {
-                        None
-                    }
-                } else {
-                    let next = value + T::one();
-                    self.value = Some(next);
-
-                    self.value
-                }
-            } else {
-                self.value = Some(*min);
-                self.value
+        let (min, max) = match self.current {
+            Some(current) => current,
+            None => {
+                let (&[min, max], bounds) = self.bounds.split_first_chunk()?;
+                self.bounds = bounds;
+                (min, max)
             }
+        };
+
+        self.current = if min < max {
+            Some((min + T::one(), max))
         } else {
             None
+        };
+        Some(min)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: NumericInteger> core::iter::FusedIterator for RangeSetIter<'a, T> {}
+
+/// How the set operations returning a new `RangeSet` size its allocation, see `RangeSet::union_with_policy`.
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum AllocationPolicy {
+    /// Shrink the allocation to the size of the result only when more than half of it is unused.
+    #[default]
+    Balanced,
+    /// Always shrink the allocation to the size of the result.
+    ShrinkToFit,
+    /// Keep the allocation reserved for the estimated size of the result, avoiding a reallocation.
+    KeepCapacity,
+}
+
+#[cfg(feature = "alloc")]
+impl AllocationPolicy {
+    /// Apply the policy to the bounds of a result.
+    fn apply<T>(self, bounds: &mut Vec<T>) {
+        match self {
+            AllocationPolicy::Balanced if bounds.capacity() > 2 * bounds.len() => {
+                bounds.shrink_to_fit()
+            }
+            AllocationPolicy::ShrinkToFit => bounds.shrink_to_fit(),
+            AllocationPolicy::Balanced | AllocationPolicy::KeepCapacity => {}
         }
     }
 }
 
+/// The policy to choose between two contained values at the same distance, see `RangeSet::closest_value`.
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum TieBreak {
+    /// Choose the smallest value.
+    Lower,
+    /// Choose the largest value.
+    Higher,
+}
+
+/// The handling of the results out of the domain, see `RangeSet::arith_add`.
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Overflow {
+    /// Replace the results by the closest bound of the domain.
+    Saturate,
+    /// Wrap the results around the domain, as two's complement arithmetic.
+    Wrap,
+    /// Drop the results.
+    Discard,
+}
+
+/// A bound of the result of an arithmetic operation, that can be out of the domain.
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+enum Extended<T> {
+    Below,
+    Value(T),
+    Above,
+}
+
+/// Summary statistics of a `RangeSet`, returned by `RangeSet::describe`.
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Clone, Debug)]
+pub struct RangeSetStats<T: NumericInteger> {
+    /// The number of maximal ranges.
+    pub ranges: usize,
+    /// The number of values contained, saturating at `u128::MAX`.
+    pub cardinality: u128,
+    /// The smallest value contained.
+    pub min: Option<T>,
+    /// The largest value contained.
+    pub max: Option<T>,
+    /// The lowest of the ranges containing the most values.
+    pub largest_range: Option<RangeInclusive<T>>,
+    /// The lowest of the largest ranges of values not contained between `min` and `max`.
+    pub largest_gap: Option<RangeInclusive<T>>,
+    /// The ratio of values contained between `min` and `max`, `0` if it is empty.
+    pub density: f64,
+}
+
+/// Error returned by `RangeSet::try_union_disjoint` when both operands contain common values.
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct OverlapError<T: NumericInteger> {
+    /// The smallest value of the first range of common values.
+    pub min: T,
+    /// The largest value of the first range of common values.
+    pub max: T,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: NumericInteger> core::fmt::Display for OverlapError<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "the values {}..={} are contained in both sets",
+            self.min, self.max
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: NumericInteger + core::fmt::Debug> core::error::Error for OverlapError<T> {}
+
+/// Error returned when parsing a `RangeSet` fails.
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct ParseRangeSetError {
+    /// The part of the input that could not be parsed.
+    pub token: String,
+    /// The reason of the failure.
+    pub reason: String,
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for ParseRangeSetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "invalid range `{}`: {}", self.token, self.reason)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for ParseRangeSetError {}
+
+#[cfg(feature = "alloc")]
 impl<T: NumericInteger> RangeSet<T> {
     /// Return an iterator to iterate in order over all the values contained.
     ///
@@ -100,14 +749,51 @@ impl<T: NumericInteger> RangeSet<T> {
     ///     print!("{value} "); // 2 3 4 5
     /// }
     /// ```
-    pub fn iter(&self) -> RangeSetIter<T> {
+    pub fn iter(&self) -> RangeSetIter<'_, T> {
         RangeSetIter {
-            range_set: self,
-            index: 0,
-            value: None,
+            bounds: &self.0,
+            current: None,
         }
     }
 
+    /// Return a helper to display it in mathematical interval notation.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u8>(vec![2, 4, 7, 9]);
+    ///
+    /// assert_eq!("[2,4] ∪ [7,9]", range.display_math().to_string());
+    /// assert_eq!("[2,5) ∪ [7,10)", range.display_math().half_open().to_string());
+    /// assert_eq!("∅", RangeSet::<u8>::empty().display_math().to_string());
+    /// ```
+    pub fn display_math(&self) -> MathDisplay<'_, T> {
+        MathDisplay::new(self)
+    }
+
+    /// Return a helper to display it in the format of `Display` with at most `max_ranges` ranges.
+    ///
+    /// When there are more ranges, only the first and last ones are displayed around an ellipsis, followed by the number of ranges.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u8>::new_from_iter((0..40).map(|i| i * 5..i * 5 + 2));
+    ///
+    /// assert_eq!(
+    ///     "[ 0..=1 5..=6 ... 195..=196 ] (40 ranges)",
+    ///     range.display_limited(3).to_string()
+    /// );
+    /// assert_eq!(range.to_string(), range.display_limited(40).to_string());
+    /// ```
+    pub fn display_limited(&self, max_ranges: usize) -> LimitedDisplay<'_, T> {
+        LimitedDisplay::new(self, max_ranges)
+    }
+
     /// Create a new instance from the given range.
     ///
     /// # Example:
@@ -126,11 +812,59 @@ impl<T: NumericInteger> RangeSet<T> {
     /// ```
     pub fn new_from_range<R: RangeBounds<T>>(range: R) -> RangeSet<T> {
         let (min, max) = range_to_bounds(&range);
-        if max >= min {
+        let new_range = if max >= min {
             RangeSet(vec![min, max])
         } else {
             RangeSet::empty()
+        };
+        check_invariants!("new_from_range", "output", new_range);
+        new_range
+    }
+
+    /// Create a new instance containing only the given value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// assert_eq!(RangeSet(vec![7, 7]), RangeSet::<u8>::singleton(7));
+    /// ```
+    #[inline]
+    pub fn singleton(value: T) -> RangeSet<T> {
+        RangeSet(vec![value, value])
+    }
+
+    /// Create a new instance from the given values, in any order and possibly repeated.
+    ///
+    /// The consecutive values are coalesced into ranges.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// // Contains 1, 2, 3, 8
+    /// let range = RangeSet::<u16>::new_from_values(&[8, 3, 1, 2, 3]);
+    /// assert_eq!(RangeSet(vec![1, 3, 8, 8]), range);
+    /// ```
+    pub fn new_from_values(values: &[T]) -> RangeSet<T> {
+        let mut values = values.to_vec();
+        values.sort_unstable();
+
+        let mut bounds: Vec<T> = Vec::new();
+        for value in values {
+            match bounds.last_mut() {
+                Some(current_max) if value <= *current_max => {}
+                Some(current_max) if value == *current_max + T::one() => *current_max = value,
+                _ => bounds.extend_from_slice(&[value, value]),
+            }
         }
+
+        bounds.shrink_to_fit();
+        let new_range = RangeSet(bounds);
+        check_invariants!("new_from_values", "output", new_range);
+        new_range
     }
 
     /// Create a new instance from the given ranges.
@@ -145,67 +879,225 @@ impl<T: NumericInteger> RangeSet<T> {
     /// RangeSet::<i64>::new_from_ranges(&[AnyRange::from(3..=4), AnyRange::from(7..9)]);
     /// ```
     pub fn new_from_ranges(ranges: &[AnyRange<T>]) -> RangeSet<T> {
+        RangeSet::new_from_iter(ranges.iter().copied())
+    }
+
+    /// Create a new instance from the given ranges, in any order and possibly overlapping.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// // Contains 0, 1, 2, 3, 4, 9, 10, 11
+    /// let range = RangeSet::<u16>::new_from_iter(vec![9..12, 0..5]);
+    /// assert_eq!(RangeSet(vec![0, 4, 9, 11]), range);
+    ///
+    /// // Contains 1, 3, 5, 7
+    /// let range = RangeSet::<u16>::new_from_iter((0..4).map(|i| i * 2 + 1..=i * 2 + 1));
+    /// assert_eq!(RangeSet(vec![1, 1, 3, 3, 5, 5, 7, 7]), range);
+    /// ```
+    pub fn new_from_iter<R: RangeBounds<T>>(ranges: impl IntoIterator<Item = R>) -> RangeSet<T> {
         let mut ranges: Vec<(T, T)> = ranges
-            .iter()
-            .map(|range| range.get_bounds())
+            .into_iter()
+            .map(|range| range_to_bounds(&range))
             .filter(|(min, max)| max >= min)
-            .copied()
             .collect();
-        ranges.sort_by(|r1, r2| r1.0.cmp(&r2.0));
+        ranges.sort_by_key(|r| r.0);
 
-        let mut bounds = Vec::with_capacity(ranges.len() * 2);
-        let mut current_max = T::min_value();
+        let mut bounds: Vec<T> = Vec::with_capacity(ranges.len() * 2);
         for (min, max) in ranges {
-            if bounds.is_empty() || min > current_max {
-                bounds.push(min);
-                bounds.push(max);
-                current_max = max;
-            } else if max > current_max {
-                *bounds.last_mut().unwrap() = max;
-                current_max = max;
+            match bounds.last_mut() {
+                Some(current_max) if *current_max == T::max_value() => break,
+                Some(current_max) if min <= *current_max + T::one() => {
+                    if max > *current_max {
+                        *current_max = max;
+                    }
+                }
+                _ => bounds.extend_from_slice(&[min, max]),
             }
         }
 
         bounds.shrink_to_fit();
-        RangeSet(bounds)
+        let new_range = RangeSet(bounds);
+        check_invariants!("new_from_iter", "output", new_range);
+        new_range
     }
 
-    /// Create a new instance that does not contain any value.
+    /// Parse the compact notation of `to_compact_string`, such as `1-5,9,20-30`, as used by `lscpu` or `taskset`.
+    ///
+    /// The values and ranges can be given in any order and can overlap, the spaces around them are ignored.
+    /// A negative minimum is written with its sign, as is a negative maximum after the dash.
     ///
     /// # Example:
     ///
     /// ```
     /// use irange::RangeSet;
-    ///  
-    /// // Contains nothing
-    /// RangeSet::<i32>::empty();
+    ///
+    /// assert_eq!(Ok(RangeSet(vec![0, 3, 8, 8])), RangeSet::<u16>::parse_compact("0-3, 8"));
+    /// assert_eq!(Ok(RangeSet(vec![-10, -5, 2, 2])), RangeSet::<i32>::parse_compact("2,-10--5"));
+    /// assert_eq!(Ok(RangeSet::empty()), RangeSet::<u8>::parse_compact(""));
+    /// assert!(RangeSet::<u8>::parse_compact("5-1").is_err());
     /// ```
-    #[inline]
-    pub fn empty() -> RangeSet<T> {
-        RangeSet(vec![])
+    pub fn parse_compact(s: &str) -> Result<RangeSet<T>, ParseRangeSetError> {
+        let ranges = s
+            .split(',')
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                let error = |reason: String| ParseRangeSetError {
+                    token: token.to_string(),
+                    reason,
+                };
+                // Skip the first character, which can be the sign of the minimum.
+                let (min, max) = match token.char_indices().skip(1).find(|&(_, c)| c == '-') {
+                    Some((separator, _)) => (&token[..separator], Some(&token[separator + 1..])),
+                    None => (token, None),
+                };
+                let min = T::from_str_radix(min.trim(), 10).map_err(|e| error(e.to_string()))?;
+                let max = match max {
+                    Some(max) => {
+                        T::from_str_radix(max.trim(), 10).map_err(|e| error(e.to_string()))?
+                    }
+                    None => min,
+                };
+                AnyRange::try_new(min, max).map_err(|e| error(e.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(RangeSet::new_from_ranges(&ranges))
     }
 
-    /// Create a new instance that contains all possible values.
+    /// Return the compact notation, such as `1-5,9,20-30`: the ranges separated by commas, a single value being written alone.
     ///
     /// # Example:
     ///
     /// ```
     /// use irange::RangeSet;
-    ///  
-    /// // Contains all values that can be stored into a u8
-    /// // -> 0..=255
-    /// RangeSet::<u8>::total();
     ///
-    /// // Contains all values that can be stored into a i16
-    /// // -> -32768..=32767
-    /// RangeSet::<i16>::total();
-    /// ```
-    #[inline]
-    pub fn total() -> RangeSet<T> {
-        RangeSet(vec![T::min_value(), T::max_value()])
-    }
-
-    /// Return `true` if it contains all the possible values.
+    /// let cpus = RangeSet::<u32>(vec![0, 3, 8, 8, 10, 11]);
+    /// assert_eq!("0-3,8,10-11", cpus.to_compact_string());
+    /// assert_eq!(Ok(cpus), RangeSet::parse_compact("0-3,8,10-11"));
+    ///
+    /// assert_eq!("-10--5,2", RangeSet::<i8>(vec![-10, -5, 2, 2]).to_compact_string());
+    /// ```
+    pub fn to_compact_string(&self) -> String {
+        let mut string = String::new();
+        for (i, range) in self.0.chunks_exact(2).enumerate() {
+            if i > 0 {
+                string.push(',');
+            }
+            if range[0] == range[1] {
+                string.push_str(&range[0].to_string());
+            } else {
+                string.push_str(&format!("{}-{}", range[0], range[1]));
+            }
+        }
+        string
+    }
+
+    /// Parse the format of `Display` with the bounds written in the given radix, such as `[ 0x1000..=0x1fff ]`.
+    ///
+    /// The brackets are optional, a single value can be written without `..=`, and the ranges can be given in any order and can overlap.
+    /// The bounds can start with a sign, and with the prefix `0x`, `0o` or `0b` matching the radix.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>::from_str_radix("[ 0x1000..=0x1fff 0x3000..=0x3fff ]", 16).unwrap();
+    /// assert_eq!(RangeSet(vec![0x1000, 0x1fff, 0x3000, 0x3fff]), range);
+    /// assert_eq!("[ 0x1000..=0x1fff 0x3000..=0x3fff ]", format!("{range:#x}"));
+    ///
+    /// assert_eq!(Ok(RangeSet(vec![-5, -3, 5, 5])), RangeSet::<i8>::from_str_radix("101 -101..=-11", 2));
+    /// assert!(RangeSet::<u8>::from_str_radix("[ 0x10..=0x1 ]", 16).is_err());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range from 2 to 36.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<RangeSet<T>, ParseRangeSetError> {
+        let parse_bound = |bound: &str, token: &str| {
+            let (sign, digits) = match bound.strip_prefix('-') {
+                Some(digits) => ("-", digits),
+                None => ("", bound.strip_prefix('+').unwrap_or(bound)),
+            };
+            let prefix = match radix {
+                2 => Some("0b"),
+                8 => Some("0o"),
+                16 => Some("0x"),
+                _ => None,
+            };
+            let digits = prefix
+                .and_then(|prefix| {
+                    digits
+                        .strip_prefix(prefix)
+                        .or_else(|| digits.strip_prefix(&prefix.to_uppercase()))
+                })
+                .unwrap_or(digits);
+            T::from_str_radix(&format!("{sign}{digits}"), radix).map_err(|error| {
+                ParseRangeSetError {
+                    token: token.to_string(),
+                    reason: error.to_string(),
+                }
+            })
+        };
+
+        let s = s.trim();
+        let s = s.strip_prefix('[').unwrap_or(s);
+        let s = s.strip_suffix(']').unwrap_or(s);
+        let ranges = s
+            .split_whitespace()
+            .map(|token| {
+                let (min, max) = token.split_once("..=").unwrap_or((token, token));
+                let (min, max) = (parse_bound(min, token)?, parse_bound(max, token)?);
+                AnyRange::try_new(min, max).map_err(|error| ParseRangeSetError {
+                    token: token.to_string(),
+                    reason: error.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(RangeSet::new_from_ranges(&ranges))
+    }
+
+    /// Create a new instance that does not contain any value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///  
+    /// // Contains nothing
+    /// RangeSet::<i32>::empty();
+    /// ```
+    #[inline]
+    pub fn empty() -> RangeSet<T> {
+        RangeSet(vec![])
+    }
+
+    /// Create a new instance that contains all possible values.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///  
+    /// // Contains all values that can be stored into a u8
+    /// // -> 0..=255
+    /// RangeSet::<u8>::total();
+    ///
+    /// // Contains all values that can be stored into a i16
+    /// // -> -32768..=32767
+    /// RangeSet::<i16>::total();
+    /// ```
+    #[inline]
+    pub fn total() -> RangeSet<T> {
+        RangeSet(vec![T::min_value(), T::max_value()])
+    }
+
+    /// Return `true` if it contains all the possible values.
     ///
     /// # Example:
     ///
@@ -253,10 +1145,15 @@ impl<T: NumericInteger> RangeSet<T> {
     /// assert!(range.contains(3));
     /// assert!(!range.contains(4));
     /// ```
+    #[inline]
     pub fn contains(&self, value: T) -> bool {
-        if self.is_empty() {
-            return false;
+        check_invariants!("contains", "input", self);
+        if self.0.len() <= LINEAR_SCAN_BOUNDS {
+            // On a few ranges, count the lower bounds with a linear scan instead of a binary search.
+            let position = self.0.iter().filter(|&&x| x < value).count();
+            return is_contained_at(&self.0, position, value);
         }
+
         let first = *self.0.first().unwrap();
         let last = *self.0.last().unwrap();
         if value < first || value > last {
@@ -270,6 +1167,190 @@ impl<T: NumericInteger> RangeSet<T> {
         self.0[position] == value || position % 2 != 0
     }
 
+    /// Return the index and the bounds of the range containing the given value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<i64>(vec![2, 4, 10, 20]);
+    /// assert_eq!(Some((1, 10..=20)), range.containing_range(15));
+    /// assert_eq!(None, range.containing_range(5));
+    /// ```
+    #[inline]
+    pub fn containing_range(&self, value: T) -> Option<(usize, RangeInclusive<T>)> {
+        let position = self.0.partition_point(|&x| x < value);
+        if is_contained_at(&self.0, position, value) {
+            let index = position / 2;
+            Some((index, self.0[index * 2]..=self.0[index * 2 + 1]))
+        } else {
+            None
+        }
+    }
+
+    /// Return the index of the range containing the given value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<i64>(vec![2, 4, 10, 20]);
+    /// assert_eq!(Some(1), range.range_index_containing(15));
+    /// assert_eq!(None, range.range_index_containing(5));
+    /// ```
+    #[inline]
+    pub fn range_index_containing(&self, value: T) -> Option<usize> {
+        self.containing_range(value).map(|(index, _)| index)
+    }
+
+    /// Return the range at the given index, the ranges being sorted in increasing order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<i64>(vec![2, 4, 10, 20]);
+    /// assert_eq!(Some(10..=20), range.get_range(1));
+    /// assert_eq!(None, range.get_range(2));
+    /// ```
+    #[inline]
+    pub fn get_range(&self, index: usize) -> Option<RangeInclusive<T>> {
+        let i = index.checked_mul(2)?;
+        Some(*self.0.get(i)?..=*self.0.get(i + 1)?)
+    }
+
+    /// Return the distance between the given value and the closest value contained, `0` if it is contained and `None` if the set is empty.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<i8>(vec![-128, -100, 10, 20]);
+    /// assert_eq!(Some(0), range.distance_to(15));
+    /// assert_eq!(Some(3), range.distance_to(7));
+    /// assert_eq!(Some(107), range.distance_to(127));
+    /// assert_eq!(None, RangeSet::<i8>::empty().distance_to(0));
+    /// ```
+    pub fn distance_to(&self, value: T) -> Option<T::DistanceType> {
+        let position = self.0.partition_point(|&x| x < value);
+        if is_contained_at(&self.0, position, value) {
+            return Some(T::distance_from_u128(0));
+        }
+
+        let below = (position > 0).then(|| T::distance(self.0[position - 1], value));
+        let above = (position < self.0.len()).then(|| T::distance(value, self.0[position]));
+        match (below, above) {
+            (Some(below), Some(above)) => Some(core::cmp::min(below, above)),
+            (below, above) => below.or(above),
+        }
+    }
+
+    /// Return the contained value the closest to the given value, `None` if the set is empty.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::{RangeSet, TieBreak};
+    ///
+    /// let range = RangeSet::<u8>(vec![2, 4, 10, 20]);
+    /// assert_eq!(Some(3), range.closest_value(3, TieBreak::Lower));
+    /// assert_eq!(Some(4), range.closest_value(5, TieBreak::Lower));
+    /// assert_eq!(Some(4), range.closest_value(7, TieBreak::Lower));
+    /// assert_eq!(Some(10), range.closest_value(7, TieBreak::Higher));
+    /// assert_eq!(Some(20), range.closest_value(255, TieBreak::Lower));
+    /// ```
+    pub fn closest_value(&self, value: T, tie: TieBreak) -> Option<T> {
+        let position = self.0.partition_point(|&x| x < value);
+        if is_contained_at(&self.0, position, value) {
+            return Some(value);
+        }
+
+        let below = (position > 0).then(|| self.0[position - 1]);
+        let above = (position < self.0.len()).then(|| self.0[position]);
+        match (below, above) {
+            (Some(below), Some(above)) => {
+                match T::distance(below, value).cmp(&T::distance(value, above)) {
+                    Ordering::Less => Some(below),
+                    Ordering::Greater => Some(above),
+                    Ordering::Equal => match tie {
+                        TieBreak::Lower => Some(below),
+                        TieBreak::Higher => Some(above),
+                    },
+                }
+            }
+            (below, above) => below.or(above),
+        }
+    }
+
+    /// Return `true` if it contains at least one of the given values, stopping at the first one found.
+    ///
+    /// While the values are increasing, each search only looks at the ranges after the previous value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let allowed = RangeSet::<u32>(vec![100, 199, 500, 599]);
+    ///
+    /// assert!(allowed.contains_any([42, 550, 1000]));
+    /// assert!(!allowed.contains_any([42, 300, 1000]));
+    /// ```
+    pub fn contains_any(&self, values: impl IntoIterator<Item = T>) -> bool {
+        check_invariants!("contains_any", "input", self);
+        let mut start = 0;
+        let mut previous = None;
+        for value in values {
+            if previous.is_some_and(|previous| value < previous) {
+                start = 0;
+            }
+            let position = start + self.0[start..].partition_point(|&x| x < value);
+            if is_contained_at(&self.0, position, value) {
+                return true;
+            }
+            start = position;
+            previous = Some(value);
+        }
+        false
+    }
+
+    /// Return `true` if it contains at least one of the given values, which must be sorted in increasing order.
+    ///
+    /// The ranges and the values are skipped alternately with binary searches, so a batch of values mostly falling in the gaps is checked in `O(k log n)`, `k` being the number of gaps reached.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let allowed = RangeSet::<u32>(vec![100, 199, 500, 599]);
+    /// let candidates: Vec<u32> = (0..1000).step_by(7).filter(|v| !(100..200).contains(v)).collect();
+    ///
+    /// assert!(allowed.contains_any_sorted(&candidates));
+    /// assert!(!allowed.contains_any_sorted(&candidates[..20]));
+    /// ```
+    pub fn contains_any_sorted(&self, values: &[T]) -> bool {
+        check_invariants!("contains_any_sorted", "input", self);
+        let mut i = 0;
+        let mut j = 0;
+        while j < values.len() {
+            i += self.0[i..].partition_point(|&x| x < values[j]);
+            if i >= self.0.len() {
+                return false;
+            } else if i % 2 != 0 || self.0[i] == values[j] {
+                return true;
+            }
+            // The value is in the gap before the range starting at `self.0[i]`.
+            let next = self.0[i];
+            j += values[j..].partition_point(|&value| value < next);
+        }
+        false
+    }
+
     /// Return `true` if it contains the given `RangeSet`.
     ///
     /// # Example:
@@ -284,6 +1365,7 @@ impl<T: NumericInteger> RangeSet<T> {
     /// assert!(!range2.contains_all(&range1));
     /// ```
     pub fn contains_all(&self, that: &RangeSet<T>) -> bool {
+        check_invariants!("contains_all", "input", self, that);
         if self.is_total() || that.is_empty() {
             return true;
         }
@@ -316,385 +1398,4318 @@ impl<T: NumericInteger> RangeSet<T> {
         true
     }
 
-    /// Return the union with the given `RangeSet`.
+    /// Return the indices of the fewest candidate ranges whose union contains it, sorted by the start of the ranges, `None` if all the candidates together do not contain it.
+    ///
+    /// The candidates are chosen greedily: among the ones starting before the smallest value not covered yet, the one ending the furthest.
     ///
     /// # Example:
     ///
     /// ```
     /// use irange::RangeSet;
     ///
-    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
-    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
+    /// let requested = RangeSet::<u32>(vec![10, 29, 50, 54]);
+    /// let reservations = [0..=15, 12..=20, 14..=40, 45..=60, 100..=200];
     ///
-    /// // Contains 2..=5
-    /// let union = range1.union(&range2);
+    /// assert_eq!(Some(vec![0, 2, 3]), requested.minimal_cover(&reservations));
+    /// assert_eq!(None, requested.minimal_cover(&reservations[..3]));
     /// ```
-    pub fn union(&self, that: &RangeSet<T>) -> RangeSet<T> {
-        if self.is_empty() || that.is_total() {
-            return that.clone();
-        } else if that.is_empty() || self.is_total() {
-            return self.clone();
-        }
-
-        let mut new_range = Vec::with_capacity(self.0.len() + that.0.len());
-
-        let mut self_i = 0;
-        let mut that_i = 0;
-
-        let mut current_min = T::min_value();
-        let mut current_max = T::min_value();
-        let mut current_i = None;
-
-        while self_i < self.0.len() || that_i < that.0.len() {
-            if that_i < that.0.len() && (self_i >= self.0.len() || self.0[self_i] > that.0[that_i])
-            {
-                let (that_min, that_max) = (that.0[that_i], that.0[that_i + 1]);
+    pub fn minimal_cover(&self, candidates: &[RangeInclusive<T>]) -> Option<Vec<usize>> {
+        check_invariants!("minimal_cover", "input", self);
+        let mut order: Vec<usize> = (0..candidates.len())
+            .filter(|&i| !candidates[i].is_empty())
+            .collect();
+        order.sort_by_key(|&i| *candidates[i].start());
 
-                if let Some(ci) = current_i {
-                    if that_min <= current_max + T::one() && that_max >= current_max {
-                        new_range[ci + 1] = that_max;
-                    } else if that_min < current_min || that_max > current_max {
-                        new_range.extend_from_slice(&[that_min, that_max]);
-                    }
-                } else {
-                    new_range.extend_from_slice(&[that_min, that_max]);
+        let mut cover = vec![];
+        let mut next = 0;
+        let mut uncovered = self.0.first().copied();
+        while let Some(value) = uncovered {
+            // The candidates not chosen end before the ones chosen, so they are never needed again.
+            let mut best: Option<usize> = None;
+            while let Some(&i) = order.get(next) {
+                if *candidates[i].start() > value {
+                    break;
                 }
+                if *candidates[i].end() >= value
+                    && best.is_none_or(|best| candidates[i].end() > candidates[best].end())
+                {
+                    best = Some(i);
+                }
+                next += 1;
+            }
+            let best = best?;
+            cover.push(best);
 
-                that_i += 2;
+            let end = *candidates[best].end();
+            let position = self.0.partition_point(|&x| x <= end);
+            uncovered = if position == self.0.len() {
+                None
+            } else if position % 2 != 0 {
+                Some(end + T::one())
             } else {
-                let (self_min, self_max) = (self.0[self_i], self.0[self_i + 1]);
-
-                if let Some(ci) = current_i {
-                    if self_min <= current_max + T::one() && self_max >= current_max {
-                        new_range[ci + 1] = self_max;
-                    } else if self_min < current_min || self_max > current_max {
-                        new_range.extend_from_slice(&[self_min, self_max]);
-                    }
-                } else {
-                    new_range.extend_from_slice(&[self_min, self_max]);
-                }
+                Some(self.0[position])
+            };
+        }
+        Some(cover)
+    }
 
-                self_i += 2;
-            }
-            current_min = new_range[new_range.len() - 2];
-            current_max = new_range[new_range.len() - 1];
-            if current_max == T::max_value() {
-                break;
-            }
-            current_i = Some(new_range.len() - 2);
+    /// Compare with the given `RangeSet` by set inclusion.
+    ///
+    /// Return `Some(Ordering::Less)` if it is strictly contained in the given `RangeSet`, `Some(Ordering::Greater)` if it strictly contains it, `Some(Ordering::Equal)` if both are equal and `None` if neither contains the other.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use core::cmp::Ordering;
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<i64>::new_from_range(11..90);
+    /// let range2 = RangeSet::<i64>::new_from_range(19..23);
+    /// let range3 = RangeSet::<i64>::new_from_range(80..100);
+    ///
+    /// assert_eq!(Some(Ordering::Greater), range1.inclusion_cmp(&range2));
+    /// assert_eq!(Some(Ordering::Less), range2.inclusion_cmp(&range1));
+    /// assert_eq!(Some(Ordering::Equal), range1.inclusion_cmp(&range1));
+    /// assert_eq!(None, range1.inclusion_cmp(&range3));
+    /// ```
+    pub fn inclusion_cmp(&self, that: &RangeSet<T>) -> Option<Ordering> {
+        check_invariants!("inclusion_cmp", "input", self, that);
+        if self == that {
+            Some(Ordering::Equal)
+        } else if that.contains_all(self) {
+            Some(Ordering::Less)
+        } else if self.contains_all(that) {
+            Some(Ordering::Greater)
+        } else {
+            None
         }
+    }
 
-        new_range.shrink_to_fit();
-        RangeSet(new_range)
+    /// Return the entry of the given value, to check and insert it with a single search.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::entry::Entry;
+    ///
+    /// let mut range = RangeSet::<u32>::new_from_range(2..=4);
+    ///
+    /// match range.entry(5) {
+    ///     Entry::Occupied(_) => unreachable!(),
+    ///     Entry::Vacant(entry) => entry.insert(),
+    /// }
+    /// assert_eq!(RangeSet::new_from_range(2..=5), range);
+    ///
+    /// assert!(range.entry(3).is_occupied());
+    /// ```
+    pub fn entry(&mut self, value: T) -> Entry<'_, T> {
+        check_invariants!("entry", "input", self);
+        Entry::new(self, value, value)
     }
 
-    /// Return `true` if there is common value with the given `RangeSet`.
+    /// Return the entry of the given range, which is occupied if at least one of its values is contained.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
     ///
     /// # Example:
     ///
     /// ```
     /// use irange::RangeSet;
     ///
-    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
-    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
-    /// let range3 = RangeSet::<i64>::new_from_range(5..13);
+    /// let mut range = RangeSet::<u32>::new_from_range(2..=4);
     ///
-    /// assert!(range1.has_intersection(&range2));
-    /// assert!(!range1.has_intersection(&range3));
-    /// assert!(range2.has_intersection(&range3));
+    /// range.entry_range(10..20).or_insert();
+    /// assert_eq!(RangeSet(vec![2, 4, 10, 19]), range);
+    ///
+    /// // Already partially occupied: nothing is inserted
+    /// range.entry_range(15..30).or_insert();
+    /// assert_eq!(RangeSet(vec![2, 4, 10, 19]), range);
     /// ```
-    pub fn has_intersection(&self, that: &RangeSet<T>) -> bool {
-        let mut i = 0;
-        let mut j = 0;
+    pub fn entry_range<R: RangeBounds<T>>(&mut self, range: R) -> Entry<'_, T> {
+        check_invariants!("entry_range", "input", self);
+        let (min, max) = range_to_bounds(&range);
+        assert!(min <= max, "the range of an entry must not be empty");
+        Entry::new(self, min, max)
+    }
 
-        while i < self.0.len() && j < that.0.len() {
-            let self_min = self.0[i];
-            let self_max = self.0[i + 1];
-            let that_min = that.0[j];
-            let that_max = that.0[j + 1];
+    /// Return the `n` smallest values contained, or all of them if it contains less than `n` values.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u8>(vec![2, 4, 10, 20]);
+    ///
+    /// assert_eq!(RangeSet(vec![2, 4, 10, 11]), range.take_first(5));
+    /// assert_eq!(range, range.take_first(1000));
+    /// ```
+    pub fn take_first(&self, n: u128) -> RangeSet<T> {
+        check_invariants!("take_first", "input", self);
+        let mut new_range = Vec::new();
+        let mut remaining = n;
 
-            if self_max < that_min {
-                i += 2;
-            } else if that_max < self_min {
-                j += 2;
-            } else {
-                return true;
+        for range in self.0.chunks_exact(2) {
+            if remaining == 0 {
+                break;
+            }
+            let (min, max) = (range[0], range[1]);
+            let extent = T::distance_to_u128(T::distance(min, max));
+            if remaining - 1 <= extent {
+                new_range.push(min);
+                new_range.push(min.add_distance(T::distance_from_u128(remaining - 1)));
+                break;
             }
+            new_range.push(min);
+            new_range.push(max);
+            remaining -= extent + 1;
         }
 
-        false
+        let new_range = RangeSet(new_range);
+        check_invariants!("take_first", "output", new_range);
+        new_range
     }
 
-    /// Return the intersection with the given `RangeSet`.
+    /// Return the `n` largest values contained, or all of them if it contains less than `n` values.
     ///
     /// # Example:
     ///
     /// ```
     /// use irange::RangeSet;
     ///
-    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
-    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
+    /// let range = RangeSet::<u8>(vec![2, 4, 10, 20]);
     ///
-    /// // Contains 2..=3
-    /// let intersection = range1.intersection(&range2);
+    /// assert_eq!(RangeSet(vec![4, 4, 10, 20]), range.take_last(12));
+    /// assert!(range.take_last(0).is_empty());
     /// ```
-    pub fn intersection(&self, that: &RangeSet<T>) -> RangeSet<T> {
-        if self.is_empty() || that.is_empty() {
-            return RangeSet::empty();
-        } else if self.is_total() {
-            return that.clone();
-        } else if that.is_total() {
-            return self.clone();
+    pub fn take_last(&self, n: u128) -> RangeSet<T> {
+        check_invariants!("take_last", "input", self);
+        let mut new_range = Vec::new();
+        let mut remaining = n;
+
+        for range in self.0.chunks_exact(2).rev() {
+            if remaining == 0 {
+                break;
+            }
+            let (min, max) = (range[0], range[1]);
+            let extent = T::distance_to_u128(T::distance(min, max));
+            if remaining - 1 <= extent {
+                new_range.push(max);
+                new_range.push(max.sub_distance(T::distance_from_u128(remaining - 1)));
+                break;
+            }
+            new_range.push(max);
+            new_range.push(min);
+            remaining -= extent + 1;
         }
 
-        let mut new_range = Vec::with_capacity(self.0.len() + that.0.len());
+        new_range.reverse();
+        let new_range = RangeSet(new_range);
+        check_invariants!("take_last", "output", new_range);
+        new_range
+    }
 
-        let mut i = 0;
-        let mut j = 0;
+    /// Remove and return the `n` smallest values contained, or all of them if it contains less than `n` values.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let mut free_ids = RangeSet::<u32>(vec![2, 4, 10, 2000]);
+    ///
+    /// let batch = free_ids.pop_first_n(1024);
+    /// assert_eq!(RangeSet(vec![2, 4, 10, 1030]), batch);
+    /// assert_eq!(RangeSet(vec![1031, 2000]), free_ids);
+    /// ```
+    pub fn pop_first_n(&mut self, n: u128) -> RangeSet<T> {
+        check_invariants!("pop_first_n", "input", self);
+        let taken = self.take_first(n);
+        if let Some(&last) = taken.0.last() {
+            let index = taken.0.len() - 2;
+            if self.0[index + 1] == last {
+                self.0.drain(..index + 2);
+            } else {
+                self.0[index] = last + T::one();
+                self.0.drain(..index);
+            }
+        }
+        taken
+    }
 
-        while i < self.0.len() && j < that.0.len() {
-            let self_min = self.0[i];
-            let self_max = self.0[i + 1];
-            let that_min = that.0[j];
-            let that_max = that.0[j + 1];
+    /// Remove the values lower than `value`, trimming the range containing it.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let mut range = RangeSet::<u64>(vec![0, 9, 20, 29, 40, 49]);
+    ///
+    /// range.remove_below(25);
+    /// assert_eq!(RangeSet(vec![25, 29, 40, 49]), range);
+    /// range.remove_below(30);
+    /// assert_eq!(RangeSet(vec![40, 49]), range);
+    /// ```
+    pub fn remove_below(&mut self, value: T) {
+        check_invariants!("remove_below", "input", self);
+        let position = self.0.partition_point(|&x| x < value);
+        if position % 2 != 0 {
+            self.0[position - 1] = value;
+            self.0.drain(..position - 1);
+        } else {
+            self.0.drain(..position);
+        }
+    }
 
-            if self_max < that_min {
-                i += 2;
-            } else if that_max < self_min {
-                j += 2;
-            } else {
-                new_range.push(std::cmp::max(self_min, that_min));
-                new_range.push(std::cmp::min(self_max, that_max));
+    /// Remove the values greater than `value`, trimming the range containing it.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let mut range = RangeSet::<u64>(vec![0, 9, 20, 29, 40, 49]);
+    ///
+    /// range.remove_above(25);
+    /// assert_eq!(RangeSet(vec![0, 9, 20, 25]), range);
+    /// range.remove_above(19);
+    /// assert_eq!(RangeSet(vec![0, 9]), range);
+    /// ```
+    pub fn remove_above(&mut self, value: T) {
+        check_invariants!("remove_above", "input", self);
+        let position = self.0.partition_point(|&x| x <= value);
+        if position % 2 != 0 {
+            self.0[position] = value;
+            self.0.truncate(position + 1);
+        } else {
+            self.0.truncate(position);
+        }
+    }
 
-                if self_max < that_max {
-                    i += 2;
-                } else {
-                    j += 2;
-                }
+    /// Remove all the values, keeping the allocated capacity.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let mut range = RangeSet::<u64>(vec![0, 9, 20, 29]);
+    ///
+    /// range.clear();
+    /// assert!(range.is_empty());
+    /// assert!(range.0.capacity() >= 4);
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Keep only the `n` first ranges, keeping the allocated capacity.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let mut range = RangeSet::<u64>(vec![0, 9, 20, 29, 40, 49]);
+    ///
+    /// range.truncate_ranges(2);
+    /// assert_eq!(RangeSet(vec![0, 9, 20, 29]), range);
+    /// range.truncate_ranges(5);
+    /// assert_eq!(RangeSet(vec![0, 9, 20, 29]), range);
+    /// ```
+    #[inline]
+    pub fn truncate_ranges(&mut self, n: usize) {
+        self.0.truncate(n.saturating_mul(2));
+        check_invariants!("truncate_ranges", "output", self);
+    }
+
+    /// Split the ranges into the ones accepted by the given predicate and the ones rejected, in a single pass.
+    ///
+    /// The predicate is called with the inclusive bounds `(min, max)` of each range.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let holes = RangeSet::<u32>(vec![0, 1, 10, 100, 200, 203]);
+    ///
+    /// let (short, long) = holes.partition(|min, max| max - min < 10);
+    /// assert_eq!(RangeSet(vec![0, 1, 200, 203]), short);
+    /// assert_eq!(RangeSet(vec![10, 100]), long);
+    /// ```
+    pub fn partition<F: Fn(T, T) -> bool>(&self, f: F) -> (RangeSet<T>, RangeSet<T>) {
+        check_invariants!("partition", "input", self);
+        let mut accepted = Vec::new();
+        let mut rejected = Vec::new();
+
+        for range in self.0.chunks_exact(2) {
+            if f(range[0], range[1]) {
+                accepted.extend_from_slice(range);
+            } else {
+                rejected.extend_from_slice(range);
             }
         }
 
-        new_range.shrink_to_fit();
-        RangeSet(new_range)
+        let (accepted, rejected) = (RangeSet(accepted), RangeSet(rejected));
+        check_invariants!("partition", "output", accepted, rejected);
+        (accepted, rejected)
     }
 
-    /// Return the complement.
+    /// Split in a single pass into the values lower than the given window, the values within it and the values greater than it.
     ///
     /// # Example:
     ///
     /// ```
     /// use irange::RangeSet;
     ///
-    /// let range = RangeSet::<u8>::new_from_range(2..4);
+    /// let free = RangeSet::<u32>(vec![0, 99, 200, 299]);
     ///
-    /// // Contains 0..=1 + 4..=255
-    /// range.complement();
+    /// let (below, reserved, above) = free.partition_around(50..250);
+    /// assert_eq!(RangeSet(vec![0, 49]), below);
+    /// assert_eq!(RangeSet(vec![50, 99, 200, 249]), reserved);
+    /// assert_eq!(RangeSet(vec![250, 299]), above);
     /// ```
-    pub fn complement(&self) -> RangeSet<T> {
-        if self.is_empty() {
-            return Self::total();
-        } else if self.is_total() {
-            return Self::empty();
+    pub fn partition_around<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> (RangeSet<T>, RangeSet<T>, RangeSet<T>) {
+        check_invariants!("partition_around", "input", self);
+        let (min, max) = range_to_bounds(&range);
+        let first = self.0.partition_point(|&x| x < min);
+        let last = if min > max {
+            first
+        } else {
+            self.0.partition_point(|&x| x <= max)
+        };
+
+        let mut below = self.0[..first].to_vec();
+        let mut inside = vec![];
+        let mut above = vec![];
+        if first % 2 != 0 {
+            below.push(min - T::one());
+            if min > max {
+                above.push(min);
+            } else {
+                inside.push(min);
+            }
+        }
+        if min <= max {
+            inside.extend_from_slice(&self.0[first..last]);
+            if last % 2 != 0 {
+                inside.push(max);
+                above.push(max + T::one());
+            }
         }
+        above.extend_from_slice(&self.0[last..]);
 
-        let mut new_range = Vec::with_capacity(self.0.len() + 2);
+        let (below, inside, above) = (RangeSet(below), RangeSet(inside), RangeSet(above));
+        check_invariants!("partition_around", "output", below, inside, above);
+        (below, inside, above)
+    }
 
-        for i in (0..self.0.len()).step_by(2) {
-            let (min, max) = (self.0[i], self.0[i + 1]);
+    /// Return an iterator over consecutive `RangeSet` holding at most `n` ranges each, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is `0`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![0, 1, 5, 6, 10, 12]);
+    ///
+    /// let chunks: Vec<_> = range.chunks_of_ranges(2).collect();
+    /// assert_eq!(vec![RangeSet(vec![0, 1, 5, 6]), RangeSet(vec![10, 12])], chunks);
+    /// ```
+    pub fn chunks_of_ranges(&self, n: usize) -> impl Iterator<Item = RangeSet<T>> + '_ {
+        assert!(n != 0, "chunk size must be non-zero");
+        check_invariants!("chunks_of_ranges", "input", self);
+        self.0.chunks(2 * n).map(|bounds| {
+            let chunk = RangeSet(bounds.to_vec());
+            check_invariants!("chunks_of_ranges", "output", chunk);
+            chunk
+        })
+    }
 
-            if new_range.is_empty() && min != T::min_value() {
-                new_range.push(T::min_value());
-                new_range.push(min - T::one());
-            }
+    /// Return an iterator over consecutive `RangeSet` holding at most `n` values each, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is `0`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![0, 1, 5, 6, 10, 12]);
+    ///
+    /// let chunks: Vec<_> = range.chunks_of_values(3).collect();
+    /// assert_eq!(
+    ///     vec![
+    ///         RangeSet(vec![0, 1, 5, 5]),
+    ///         RangeSet(vec![6, 6, 10, 11]),
+    ///         RangeSet(vec![12, 12]),
+    ///     ],
+    ///     chunks
+    /// );
+    /// ```
+    pub fn chunks_of_values(&self, n: u128) -> impl Iterator<Item = RangeSet<T>> + '_ {
+        assert!(n != 0, "chunk size must be non-zero");
+        check_invariants!("chunks_of_values", "input", self);
 
-            if new_range.len() % 2 == 1 {
-                new_range.push(min - T::one());
+        let mut index = 0;
+        let mut start = None;
+        core::iter::from_fn(move || {
+            if index >= self.0.len() {
+                return None;
             }
-            if max < T::max_value() {
-                new_range.push(max + T::one());
+
+            let mut bounds = Vec::new();
+            let mut remaining = n;
+            while remaining > 0 && index < self.0.len() {
+                let min = start.unwrap_or(self.0[index]);
+                let max = self.0[index + 1];
+                let extent = T::distance_to_u128(T::distance(min, max));
+                if remaining - 1 < extent {
+                    let end = min.add_distance(T::distance_from_u128(remaining - 1));
+                    bounds.extend_from_slice(&[min, end]);
+                    start = Some(end + T::one());
+                    remaining = 0;
+                } else {
+                    bounds.extend_from_slice(&[min, max]);
+                    start = None;
+                    index += 2;
+                    remaining -= extent + 1;
+                }
             }
+            let chunk = RangeSet(bounds);
+            check_invariants!("chunks_of_values", "output", chunk);
+            Some(chunk)
+        })
+    }
+
+    /// Return an iterator over batches of at most `n` consecutive values each, in order.
+    ///
+    /// The batches are delimited by arithmetic over the lengths of the ranges, as with `chunks_of_values`, and only the values of the current batch are collected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is `0`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let ids = RangeSet::<u64>(vec![1, 4, 100, 101]);
+    ///
+    /// let batches: Vec<Vec<u64>> = ids.iter_chunks(4).collect();
+    /// assert_eq!(vec![vec![1, 2, 3, 4], vec![100, 101]], batches);
+    /// ```
+    pub fn iter_chunks(&self, n: usize) -> impl Iterator<Item = Vec<T>> + '_ {
+        self.chunks_of_values(n as u128)
+            .map(|chunk| chunk.iter().collect())
+    }
+
+    /// Return an iterator over the ranges having at least one value in common with the given window, in order.
+    ///
+    /// The ranges are returned whole, not restricted to the window.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![0, 1, 5, 6, 10, 12, 20, 25]);
+    ///
+    /// let overlapping: Vec<_> = range.ranges_overlapping(6..=11).collect();
+    /// assert_eq!(vec![5..=6, 10..=12], overlapping);
+    /// ```
+    pub fn ranges_overlapping<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        let (min, max) = range_to_bounds(&range);
+        let start = if min > max {
+            self.0.len()
+        } else {
+            let position = self.0.partition_point(|&x| x < min);
+            position - position % 2
+        };
+
+        self.0[start..]
+            .chunks_exact(2)
+            .take_while(move |bounds| bounds[0] <= max)
+            .map(|bounds| bounds[0]..=bounds[1])
+    }
+
+    /// Return an iterator over the smallest value of each range, in increasing order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![2, 4, 10, 20]);
+    /// assert_eq!(vec![2, 10], range.starts().collect::<Vec<_>>());
+    /// ```
+    pub fn starts(&self) -> impl ExactSizeIterator<Item = T> + '_ {
+        self.0.chunks_exact(2).map(|range| range[0])
+    }
+
+    /// Return an iterator over the largest value of each range, in increasing order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![2, 4, 10, 20]);
+    /// assert_eq!(vec![4, 20], range.ends().collect::<Vec<_>>());
+    /// ```
+    pub fn ends(&self) -> impl ExactSizeIterator<Item = T> + '_ {
+        self.0.chunks_exact(2).map(|range| range[1])
+    }
+
+    /// Call the given closure with the bounds `(min, max)` of each range in order, until it returns `ControlFlow::Break`.
+    ///
+    /// Return the value of the break, `None` if the closure continued until the end.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use core::ops::ControlFlow;
+    /// use irange::RangeSet;
+    ///
+    /// let free = RangeSet::<u32>(vec![0, 4, 10, 19, 50, 59]);
+    ///
+    /// // The start of the first block of at least 8 values.
+    /// let start = free.try_for_each_range(|min, max| {
+    ///     if max - min + 1 >= 8 {
+    ///         ControlFlow::Break(min)
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// });
+    /// assert_eq!(Some(10), start);
+    /// ```
+    pub fn try_for_each_range<B>(&self, mut f: impl FnMut(T, T) -> ControlFlow<B>) -> Option<B> {
+        match self
+            .0
+            .chunks_exact(2)
+            .try_for_each(|range| f(range[0], range[1]))
+        {
+            ControlFlow::Break(value) => Some(value),
+            ControlFlow::Continue(()) => None,
         }
-        if new_range.len() % 2 == 1 {
-            new_range.push(T::max_value());
+    }
+
+    /// Return the number of values contained measured with the given type, `None` if it does not fit.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u64>(vec![0, 9, 20, 29]);
+    /// assert_eq!(Some(20u32), range.covered_len());
+    ///
+    /// assert_eq!(Some(1u128 << 64), RangeSet::<u64>::total().covered_len());
+    /// assert_eq!(None::<u64>, RangeSet::<u64>::total().covered_len());
+    /// ```
+    pub fn covered_len<M: Measure<T>>(&self) -> Option<M> {
+        self.0.chunks_exact(2).try_fold(M::zero(), |len, range| {
+            len.checked_sum(M::measure(range[0], range[1])?)
+        })
+    }
+
+    /// Return the summary statistics in a single pass.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let stats = RangeSet::<u32>(vec![0, 4, 10, 19, 50, 54]).describe();
+    ///
+    /// assert_eq!(3, stats.ranges);
+    /// assert_eq!(20, stats.cardinality);
+    /// assert_eq!(Some(0), stats.min);
+    /// assert_eq!(Some(54), stats.max);
+    /// assert_eq!(Some(10..=19), stats.largest_range);
+    /// assert_eq!(Some(20..=49), stats.largest_gap);
+    /// assert_eq!(20.0 / 55.0, stats.density);
+    /// ```
+    pub fn describe(&self) -> RangeSetStats<T> {
+        let mut cardinality = 0u128;
+        let mut largest_range: Option<(T, T)> = None;
+        let mut largest_gap: Option<(T, T)> = None;
+        for (i, range) in self.0.chunks_exact(2).enumerate() {
+            let (min, max) = (range[0], range[1]);
+            let extent = T::distance(min, max);
+            cardinality = cardinality
+                .saturating_add(T::distance_to_u128(extent))
+                .saturating_add(1);
+            if largest_range.is_none_or(|(lmin, lmax)| extent > T::distance(lmin, lmax)) {
+                largest_range = Some((min, max));
+            }
+            if i > 0 {
+                let (gap_min, gap_max) = (self.0[2 * i - 1] + T::one(), min - T::one());
+                if largest_gap.is_none_or(|(gmin, gmax)| {
+                    T::distance(gap_min, gap_max) > T::distance(gmin, gmax)
+                }) {
+                    largest_gap = Some((gap_min, gap_max));
+                }
+            }
         }
 
-        new_range.shrink_to_fit();
-        RangeSet(new_range)
+        let (min, max) = (self.0.first().copied(), self.0.last().copied());
+        let density = match (min, max) {
+            (Some(min), Some(max)) => {
+                cardinality as f64 / (T::distance_to_u128(T::distance(min, max)) as f64 + 1.0)
+            }
+            _ => 0.0,
+        };
+
+        RangeSetStats {
+            ranges: self.0.len() / 2,
+            cardinality,
+            min,
+            max,
+            largest_range: largest_range.map(|(min, max)| min..=max),
+            largest_gap: largest_gap.map(|(min, max)| min..=max),
+            density,
+        }
     }
 
-    /// Return the difference with the given `RangeSet`.
+    /// Return the index and the bounds of the range containing the most values, the lowest one if there are several.
     ///
     /// # Example:
     ///
     /// ```
     /// use irange::RangeSet;
     ///
-    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
-    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
+    /// let free = RangeSet::<u32>(vec![0, 4, 10, 19, 50, 59]);
+    /// assert_eq!(Some((1, 10..=19)), free.largest_range());
+    /// assert_eq!(None, RangeSet::<u32>::empty().largest_range());
+    /// ```
+    pub fn largest_range(&self) -> Option<(usize, RangeInclusive<T>)> {
+        self.extreme_range(Ordering::Greater)
+    }
+
+    /// Return the index and the bounds of the range containing the fewest values, the lowest one if there are several.
+    ///
+    /// # Example:
     ///
-    /// // Contains 2
-    /// let difference = range1.difference(&range2);
     /// ```
-    #[inline]
-    pub fn difference(&self, that: &RangeSet<T>) -> RangeSet<T> {
-        self.intersection(&that.complement())
+    /// use irange::RangeSet;
+    ///
+    /// let free = RangeSet::<u32>(vec![0, 4, 10, 19, 50, 54]);
+    /// assert_eq!(Some((0, 0..=4)), free.smallest_range());
+    /// ```
+    pub fn smallest_range(&self) -> Option<(usize, RangeInclusive<T>)> {
+        self.extreme_range(Ordering::Less)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::collections::HashSet;
+    /// Return the first range whose number of values compares to the ones of all the other ranges with `ordering` or `Ordering::Equal`.
+    fn extreme_range(&self, ordering: Ordering) -> Option<(usize, RangeInclusive<T>)> {
+        let mut extreme: Option<(usize, T::DistanceType)> = None;
+        for (i, range) in self.0.chunks_exact(2).enumerate() {
+            let extent = T::distance(range[0], range[1]);
+            if extreme.is_none_or(|(_, current)| extent.cmp(&current) == ordering) {
+                extreme = Some((i, extent));
+            }
+        }
+        extreme.map(|(i, _)| (i, self.0[2 * i]..=self.0[2 * i + 1]))
+    }
+
+    /// Return the number of ranges per bucket of lengths, the length of a range being its number of values measured with the given type.
+    ///
+    /// The upper limits of the buckets must be sorted in increasing order: the element `i` counts the ranges longer than `buckets[i - 1]` and shorter than or equal to `buckets[i]`.
+    /// The last element counts the ranges longer than all the limits, or whose length does not fit.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let free = RangeSet::<u64>(vec![0, 0, 10, 13, 20, 20, 30, 99, 200, 1199]);
+    ///
+    /// // 1 | 2..=16 | 17..=256 | more
+    /// assert_eq!(vec![2, 1, 1, 1], free.range_length_histogram(&[1u32, 16, 256]));
+    /// ```
+    pub fn range_length_histogram<M: Measure<T> + Ord>(&self, buckets: &[M]) -> Vec<usize> {
+        let mut histogram = vec![0; buckets.len() + 1];
+        for range in self.0.chunks_exact(2) {
+            let bucket = match M::measure(range[0], range[1]) {
+                Some(length) => buckets.partition_point(|limit| *limit < length),
+                None => buckets.len(),
+            };
+            histogram[bucket] += 1;
+        }
+        histogram
+    }
+
+    /// Render as a SQL predicate on the given column, such as `(id BETWEEN 1 AND 100 OR id IN (200, 201))`.
+    ///
+    /// The ranges of at most 2 values are listed in a single `IN`, see `irange::sql::SqlPredicate` to change it.
+    /// The column is written without quoting nor escaping, so it must be a trusted identifier.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let ids = RangeSet::<u32>(vec![1, 100, 200, 201]);
+    ///
+    /// assert_eq!("(id BETWEEN 1 AND 100 OR id IN (200, 201))", ids.to_sql_predicate("id"));
+    /// ```
+    pub fn to_sql_predicate(&self, column: &str) -> String {
+        sql::SqlPredicate::new(column).render(self)
+    }
+
+    /// Render as a SQL predicate on the given column with `?` placeholders, returning it with the values to bind in order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let ids = RangeSet::<u32>(vec![1, 100, 200, 201]);
+    ///
+    /// assert_eq!(
+    ///     ("(id BETWEEN ? AND ? OR id IN (?, ?))".to_string(), vec![1, 100, 200, 201]),
+    ///     ids.to_sql_predicate_parameterized("id")
+    /// );
+    /// ```
+    pub fn to_sql_predicate_parameterized(&self, column: &str) -> (String, Vec<T>) {
+        sql::SqlPredicate::new(column).render_parameterized(self)
+    }
+
+    /// Return the number of values contained within the given window, saturating at `u128::MAX`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![0, 1, 5, 6, 10, 12, 20, 25]);
+    ///
+    /// assert_eq!(3, range.count_in(6..=11));
+    /// assert_eq!(13, range.count_in(..));
+    /// ```
+    pub fn count_in<R: RangeBounds<T>>(&self, range: R) -> u128 {
+        let (min, max) = range_to_bounds(&range);
+        self.ranges_overlapping(min..=max)
+            .map(|range| {
+                let (range_min, range_max) = range.into_inner();
+                let extent = T::distance(
+                    core::cmp::max(range_min, min),
+                    core::cmp::min(range_max, max),
+                );
+                T::distance_to_u128(extent)
+            })
+            .fold(0u128, |count, extent| {
+                count.saturating_add(extent).saturating_add(1)
+            })
+    }
+
+    /// Return the sum of the values contained within the given window, `None` if it does not fit in `i128`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![0, 1, 5, 6, 10, 12, 20, 25]);
+    ///
+    /// assert_eq!(Some(6 + 10 + 11), range.sum_in(6..=11));
+    /// assert_eq!(None, RangeSet::<u128>::total().sum_in(..));
+    /// ```
+    pub fn sum_in<R: RangeBounds<T>>(&self, range: R) -> Option<i128> {
+        let (min, max) = range_to_bounds(&range);
+        self.ranges_overlapping(min..=max)
+            .try_fold(0i128, |sum, range| {
+                let (range_min, range_max) = range.into_inner();
+                let (first, last) = (
+                    core::cmp::max(range_min, min),
+                    core::cmp::min(range_max, max),
+                );
+                let extent = T::distance_to_u128(T::distance(first, last));
+                let (first, last) = (integer::to_i128(first)?, integer::to_i128(last)?);
+                let range_sum = if extent % 2 == 0 {
+                    let middle = first.checked_add_unsigned(extent / 2)?;
+                    checked_mul_unsigned(extent + 1, middle)?
+                } else {
+                    checked_mul_unsigned(extent / 2 + 1, first.checked_add(last)?)?
+                };
+                sum.checked_add(range_sum)
+            })
+    }
+
+    /// Return an iterator over the ranges of values not contained within the given window, in order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![0, 1, 5, 6, 10, 12, 20, 25]);
+    ///
+    /// let gaps: Vec<_> = range.gaps_within(4..=15).collect();
+    /// assert_eq!(vec![4..=4, 7..=9, 13..=15], gaps);
+    /// ```
+    pub fn gaps_within<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        let (min, max) = range_to_bounds(&range);
+        let mut ranges = self.ranges_overlapping(min..=max);
+        let mut cursor = (min <= max).then_some(min);
+
+        core::iter::from_fn(move || {
+            let mut start = cursor?;
+            for range in ranges.by_ref() {
+                let (range_min, range_max) = range.into_inner();
+                cursor = (range_max < max).then(|| range_max + T::one());
+                if range_min > start {
+                    return Some(start..=range_min - T::one());
+                }
+                start = cursor?;
+            }
+            cursor = None;
+            Some(start..=max)
+        })
+    }
+
+    /// Return for each of the given blocks of values `(min, max)` if it has at least one value in common with the set.
+    ///
+    /// When the blocks are sorted by their minimum, a single cursor moves forward over the ranges instead of searching for every block.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![10, 19, 45, 45]);
+    /// let blocks = [(0, 9), (10, 29), (30, 39), (40, 49)];
+    ///
+    /// assert_eq!(vec![false, true, false, true], range.filter_blocks(blocks.into_iter()));
+    /// ```
+    pub fn filter_blocks(&self, blocks: impl Iterator<Item = (T, T)>) -> Vec<bool> {
+        let mut cursor = 0;
+        let mut previous_min = T::min_value();
+        blocks
+            .map(|(min, max)| {
+                if min < previous_min {
+                    let position = self.0.partition_point(|&x| x < min);
+                    cursor = position - position % 2;
+                } else {
+                    while cursor < self.0.len() && self.0[cursor + 1] < min {
+                        cursor += 2;
+                    }
+                }
+                previous_min = min;
+
+                min <= max && cursor < self.0.len() && self.0[cursor] <= max
+            })
+            .collect()
+    }
+
+    /// Return the smallest superset made of at most `max_ranges` ranges, obtained by filling the smallest gaps first.
+    ///
+    /// Gaps of the same size are filled from the lowest one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_ranges` is `0` and the set is not empty.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![0, 1, 3, 4, 10, 12, 100, 100]);
+    ///
+    /// assert_eq!(RangeSet(vec![0, 12, 100, 100]), range.simplified(2));
+    /// assert_eq!(RangeSet(vec![0, 100]), range.simplified(1));
+    /// ```
+    pub fn simplified(&self, max_ranges: usize) -> RangeSet<T> {
+        check_invariants!("simplified", "input", self);
+        let ranges = self.0.len() / 2;
+        if ranges <= max_ranges {
+            return self.clone();
+        }
+        assert!(max_ranges > 0, "max_ranges must be greater than 0");
+
+        let mut gaps: Vec<(T::DistanceType, usize)> = (1..ranges)
+            .map(|i| (T::distance(self.0[i * 2 - 1], self.0[i * 2]), i))
+            .collect();
+        gaps.sort();
+        let mut filled = vec![false; ranges];
+        for &(_, i) in &gaps[..ranges - max_ranges] {
+            filled[i] = true;
+        }
+
+        let mut new_range = Vec::with_capacity(max_ranges * 2);
+        for i in 0..ranges {
+            if !filled[i] {
+                new_range.push(self.0[i * 2]);
+            }
+            if i + 1 == ranges || !filled[i + 1] {
+                new_range.push(self.0[i * 2 + 1]);
+            }
+        }
+
+        let new_range = RangeSet(new_range);
+        check_invariants!("simplified", "output", new_range);
+        new_range
+    }
+
+    /// Return an iterator over the minimal decomposition into naturally aligned blocks of power-of-two sizes, as `(start, k)` where the block holds `2^k` values and `start` is a multiple of `2^k`.
+    ///
+    /// This is the generalization of CIDR aggregation and of the blocks of a buddy allocator.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>(vec![3, 12]);
+    ///
+    /// // 3, 4..=7, 8..=11, 12
+    /// let blocks: Vec<_> = range.aligned_blocks().collect();
+    /// assert_eq!(vec![(3, 0), (4, 2), (8, 2), (12, 0)], blocks);
+    /// ```
+    pub fn aligned_blocks(&self) -> impl Iterator<Item = (T, u32)> + '_ {
+        let offset = |value: T| T::distance_to_u128(T::distance(T::min_value(), value));
+        let value = |offset: u128| T::min_value().add_distance(T::distance_from_u128(offset));
+
+        self.0.chunks_exact(2).flat_map(move |range| {
+            let (mut min, max) = (Some(offset(range[0])), offset(range[1]));
+            core::iter::from_fn(move || {
+                let start = min?;
+                let k = match (max - start).checked_add(1) {
+                    Some(count) => core::cmp::min(start.trailing_zeros(), count.ilog2()),
+                    None => 128,
+                };
+                let end = start + 1u128.checked_shl(k).map_or(u128::MAX, |size| size - 1);
+                min = if end < max { Some(end + 1) } else { None };
+                Some((value(start), k))
+            })
+        })
+    }
+
+    /// Return the smallest superset whose ranges start at a multiple of `k` and end right before a multiple of `k`, clamped to the domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is not positive.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let bytes = RangeSet::<u64>(vec![100, 5000, 9000, 9000]);
+    ///
+    /// assert_eq!(RangeSet(vec![0, 12287]), bytes.align_outward(4096));
+    /// ```
+    pub fn align_outward(&self, k: T) -> RangeSet<T> {
+        check_invariants!("align_outward", "input", self);
+        let zero = T::zero();
+        assert!(k > zero, "k must be positive");
+
+        RangeSet::new_from_iter(self.0.chunks_exact(2).map(|range| {
+            let (min, max) = (range[0], range[1]);
+            let start = min.checked_sub(min.rem_euclid(k)).unwrap_or(T::min_value());
+            let end = max
+                .checked_add(k - T::one() - max.rem_euclid(k))
+                .unwrap_or(T::max_value());
+            start..=end
+        }))
+    }
+
+    /// Return the largest subset whose ranges start at a multiple of `k` and end right before a multiple of `k`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is not positive.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let bytes = RangeSet::<u64>(vec![100, 12287, 20000, 20100]);
+    ///
+    /// assert_eq!(RangeSet(vec![4096, 12287]), bytes.align_inward(4096));
+    /// ```
+    pub fn align_inward(&self, k: T) -> RangeSet<T> {
+        check_invariants!("align_inward", "input", self);
+        let zero = T::zero();
+        assert!(k > zero, "k must be positive");
+
+        let mut new_range = Vec::with_capacity(self.0.len());
+        for range in self.0.chunks_exact(2) {
+            let (min, max) = (range[0], range[1]);
+            let start = min.checked_add((k - min.rem_euclid(k)).rem_euclid(k));
+            let end = max.checked_sub((max.rem_euclid(k) + T::one()).rem_euclid(k));
+            if let (Some(start), Some(end)) = (start, end) {
+                if start <= end {
+                    new_range.extend_from_slice(&[start, end]);
+                }
+            }
+        }
+
+        let new_range = RangeSet(new_range);
+        check_invariants!("align_inward", "output", new_range);
+        new_range
+    }
+
+    /// Return the values contained that are congruent to `r` modulo `m`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is not positive.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let ids = RangeSet::<i32>(vec![-3, 3, 10, 12]);
+    ///
+    /// assert_eq!(RangeSet(vec![-2, -2, 1, 1, 10, 10]), ids.filter_residue(3, 1));
+    /// ```
+    pub fn filter_residue(&self, m: T, r: T) -> RangeSet<T> {
+        check_invariants!("filter_residue", "input", self);
+        let zero = T::zero();
+        assert!(m > zero, "m must be positive");
+        if m == T::one() {
+            return self.clone();
+        }
+
+        let r = r.rem_euclid(m);
+        let mut new_range = Vec::new();
+        for range in self.0.chunks_exact(2) {
+            let (min, max) = (range[0], range[1]);
+            let residue = min.rem_euclid(m);
+            let delta = if r >= residue {
+                r - residue
+            } else {
+                m - residue + r
+            };
+
+            let mut value = min.checked_add(delta);
+            while let Some(current) = value.filter(|&current| current <= max) {
+                new_range.extend_from_slice(&[current, current]);
+                value = current.checked_add(m);
+            }
+        }
+
+        let new_range = RangeSet(new_range);
+        check_invariants!("filter_residue", "output", new_range);
+        new_range
+    }
+
+    /// Return the indices of the buckets of `bucket` values having at least one value contained, the bucket `i` holding the values from `i * bucket` to `(i + 1) * bucket - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bucket` is not positive.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let offsets = RangeSet::<u64>(vec![100, 5000, 9000, 9000]);
+    ///
+    /// assert_eq!(RangeSet(vec![0, 2]), offsets.quantize(4096));
+    /// ```
+    pub fn quantize(&self, bucket: T) -> RangeSet<T> {
+        check_invariants!("quantize", "input", self);
+        let zero = T::zero();
+        assert!(bucket > zero, "bucket must be positive");
+
+        RangeSet::new_from_iter(
+            self.0
+                .chunks_exact(2)
+                .map(|range| range[0].div_euclid(bucket)..=range[1].div_euclid(bucket)),
+        )
+    }
+
+    /// Return the values of the buckets of `bucket` values whose indices are contained, clamped to the domain.
+    ///
+    /// This is the inverse of `quantize`, up to the values of the buckets that were partially contained.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bucket` is not positive.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let pages = RangeSet::<u64>(vec![0, 2]);
+    ///
+    /// assert_eq!(RangeSet(vec![0, 12287]), pages.dequantize(4096));
+    /// ```
+    pub fn dequantize(&self, bucket: T) -> RangeSet<T> {
+        check_invariants!("dequantize", "input", self);
+        let zero = T::zero();
+        assert!(bucket > zero, "bucket must be positive");
+
+        let mut new_range = Vec::with_capacity(self.0.len());
+        for range in self.0.chunks_exact(2) {
+            let (min, max) = (range[0], range[1]);
+            let start = match min.checked_mul(bucket) {
+                Some(start) => start,
+                None if min < zero => T::min_value(),
+                None => break,
+            };
+            let end = if max < zero {
+                match (max + T::one())
+                    .checked_mul(bucket)
+                    .and_then(|end| end.checked_sub(T::one()))
+                {
+                    Some(end) => end,
+                    None => continue,
+                }
+            } else {
+                max.checked_mul(bucket)
+                    .and_then(|end| end.checked_add(bucket - T::one()))
+                    .unwrap_or(T::max_value())
+            };
+            new_range.extend_from_slice(&[start, end]);
+        }
+
+        RangeSet::new_from_iter(new_range.chunks_exact(2).map(|range| range[0]..=range[1]))
+    }
+
+    /// Return the values mapped to the unsigned type of the same width in an order-preserving way, the smallest value being mapped to `0`.
+    ///
+    /// For a signed type, this is the biased encoding `x ^ MIN`. For an unsigned type, the values are unchanged.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<i8>(vec![-128, -100, -1, 1]);
+    /// assert_eq!(RangeSet::<u8>(vec![0, 28, 127, 129]), range.to_unsigned_lossless());
+    /// ```
+    pub fn to_unsigned_lossless(&self) -> RangeSet<T::DistanceType> {
+        check_invariants!("to_unsigned_lossless", "input", self);
+        let new_range = RangeSet(
+            self.0
+                .iter()
+                .map(|&bound| T::distance(T::min_value(), bound))
+                .collect(),
+        );
+        check_invariants!("to_unsigned_lossless", "output", new_range);
+        new_range
+    }
+
+    /// Return the values of the unsigned type of the same width as `S` mapped to `S` in an order-preserving way, `0` being mapped to the smallest value of `S`.
+    ///
+    /// This is the inverse of `to_unsigned_lossless`, decoding the biased encoding `x ^ MIN` when `S` is signed.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let register = RangeSet::<u16>(vec![0x7ff0, 0x8010]);
+    /// assert_eq!(RangeSet::<i16>(vec![-16, 16]), register.to_signed_lossless());
+    /// ```
+    pub fn to_signed_lossless<S: NumericInteger<DistanceType = T>>(&self) -> RangeSet<S> {
+        check_invariants!("to_signed_lossless", "input", self);
+        let new_range = RangeSet(
+            self.0
+                .iter()
+                .map(|&bound| S::min_value().add_distance(bound))
+                .collect(),
+        );
+        check_invariants!("to_signed_lossless", "output", new_range);
+        new_range
+    }
+
+    /// Return the residues modulo `m` of the values contained, as a subset of `[0, m)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is not positive.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let minutes = RangeSet::<u32>(vec![50, 70, 130, 130]);
+    ///
+    /// assert_eq!(RangeSet(vec![0, 10, 50, 59]), minutes.project_mod(60));
+    /// ```
+    pub fn project_mod(&self, m: T) -> RangeSet<T> {
+        check_invariants!("project_mod", "input", self);
+        let zero = T::zero();
+        assert!(m > zero, "m must be positive");
+
+        let period = T::distance_to_u128(T::distance(zero, m));
+        let mut ranges = Vec::with_capacity(self.0.len() + 2);
+        for range in self.0.chunks_exact(2) {
+            let (min, max) = (range[0], range[1]);
+            if T::distance_to_u128(T::distance(min, max)) >= period - 1 {
+                return RangeSet(vec![zero, m - T::one()]);
+            }
+
+            let (first, last) = (min.rem_euclid(m), max.rem_euclid(m));
+            if first <= last {
+                ranges.push(first..=last);
+            } else {
+                ranges.push(first..=m - T::one());
+                ranges.push(zero..=last);
+            }
+        }
+
+        RangeSet::new_from_iter(ranges)
+    }
+
+    /// Return the sums `a + b` of every value `a` contained and every value `b` of the given `RangeSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::{Overflow, RangeSet};
+    ///
+    /// let x = RangeSet::<u8>(vec![0, 10, 250, 255]);
+    /// let y = RangeSet::<u8>(vec![1, 2]);
+    ///
+    /// assert_eq!(RangeSet(vec![1, 12, 251, 255]), x.arith_add(&y, Overflow::Saturate));
+    /// assert_eq!(RangeSet(vec![0, 12, 251, 255]), x.arith_add(&y, Overflow::Wrap));
+    /// assert_eq!(RangeSet(vec![1, 12, 251, 255]), x.arith_add(&y, Overflow::Discard));
+    /// ```
+    pub fn arith_add(&self, that: &RangeSet<T>, overflow: Overflow) -> RangeSet<T> {
+        check_invariants!("arith_add", "input", self, that);
+        let zero = T::zero();
+        let add = |a: T, b: T| match a.checked_add(b) {
+            Some(sum) => Extended::Value(sum),
+            None if b > zero => Extended::Above,
+            None => Extended::Below,
+        };
+        self.arith(that, overflow, |(a_min, a_max), (b_min, b_max)| {
+            (
+                add(a_min, b_min),
+                add(a_max, b_max),
+                Some(a_min.add_distance(T::distance(zero, b_min))),
+            )
+        })
+    }
+
+    /// Return the differences `a - b` of every value `a` contained and every value `b` of the given `RangeSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::{Overflow, RangeSet};
+    ///
+    /// let x = RangeSet::<i8>(vec![-128, -120, 0, 10]);
+    /// let y = RangeSet::<i8>(vec![0, 5]);
+    ///
+    /// assert_eq!(RangeSet(vec![-128, -120, -5, 10]), x.arith_sub(&y, Overflow::Saturate));
+    /// assert_eq!(RangeSet(vec![-128, -120, -5, 10, 123, 127]), x.arith_sub(&y, Overflow::Wrap));
+    /// ```
+    pub fn arith_sub(&self, that: &RangeSet<T>, overflow: Overflow) -> RangeSet<T> {
+        check_invariants!("arith_sub", "input", self, that);
+        let zero = T::zero();
+        let sub = |a: T, b: T| match a.checked_sub(b) {
+            Some(difference) => Extended::Value(difference),
+            None if b < zero => Extended::Above,
+            None => Extended::Below,
+        };
+        self.arith(that, overflow, |(a_min, a_max), (b_min, b_max)| {
+            (
+                sub(a_min, b_max),
+                sub(a_max, b_min),
+                Some(a_min.sub_distance(T::distance(zero, b_max))),
+            )
+        })
+    }
+
+    /// Return the smallest range containing the products `a * b` of every value `a` of each range and every value `b` of each range of the given `RangeSet`.
+    ///
+    /// As in interval arithmetic the result is an over-approximation: the products of two ranges are replaced by the range between the smallest and the largest of them.
+    /// With `Overflow::Wrap`, the products of two ranges that overflow are approximated by the whole domain.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::{Overflow, RangeSet};
+    ///
+    /// let x = RangeSet::<i8>(vec![-3, 2]);
+    /// let y = RangeSet::<i8>(vec![10, 50]);
+    ///
+    /// assert_eq!(RangeSet(vec![-128, 100]), x.arith_mul(&y, Overflow::Saturate));
+    /// assert_eq!(RangeSet::total(), x.arith_mul(&y, Overflow::Wrap));
+    /// ```
+    pub fn arith_mul(&self, that: &RangeSet<T>, overflow: Overflow) -> RangeSet<T> {
+        check_invariants!("arith_mul", "input", self, that);
+        let zero = T::zero();
+        let mul = |a: T, b: T| match a.checked_mul(b) {
+            Some(product) => Extended::Value(product),
+            None if (a < zero) == (b < zero) => Extended::Above,
+            None => Extended::Below,
+        };
+        self.arith(that, overflow, |(a_min, a_max), (b_min, b_max)| {
+            let products = [
+                mul(a_min, b_min),
+                mul(a_min, b_max),
+                mul(a_max, b_min),
+                mul(a_max, b_max),
+            ];
+            (
+                *products.iter().min().unwrap(),
+                *products.iter().max().unwrap(),
+                None,
+            )
+        })
+    }
+
+    /// Combine every pair of ranges with `f`, which returns the bounds of the result, possibly out of the domain, and the wrapped value of its minimum when the result is a range of `extent_a + extent_b + 1` values.
+    fn arith<F>(&self, that: &RangeSet<T>, overflow: Overflow, f: F) -> RangeSet<T>
+    where
+        F: Fn((T, T), (T, T)) -> (Extended<T>, Extended<T>, Option<T>),
+    {
+        let domain = T::distance(T::min_value(), T::max_value());
+        let mut ranges = Vec::new();
+        for a in self.0.chunks_exact(2) {
+            for b in that.0.chunks_exact(2) {
+                let (a, b) = ((a[0], a[1]), (b[0], b[1]));
+                let (min, max, wrapped_min) = f(a, b);
+                let clamp = |bound| match bound {
+                    Extended::Below => T::min_value(),
+                    Extended::Value(value) => value,
+                    Extended::Above => T::max_value(),
+                };
+
+                match (overflow, min, max) {
+                    (_, Extended::Value(min), Extended::Value(max)) => ranges.push(min..=max),
+                    (Overflow::Saturate, min, max) => ranges.push(clamp(min)..=clamp(max)),
+                    (Overflow::Discard, Extended::Above, _)
+                    | (Overflow::Discard, _, Extended::Below) => {}
+                    (Overflow::Discard, min, max) => ranges.push(clamp(min)..=clamp(max)),
+                    (Overflow::Wrap, _, _) => {
+                        let extent = T::distance_to_u128(T::distance(a.0, a.1))
+                            .checked_add(T::distance_to_u128(T::distance(b.0, b.1)));
+                        match (wrapped_min, extent) {
+                            (Some(min), Some(extent)) if extent < T::distance_to_u128(domain) => {
+                                let extent = T::distance_from_u128(extent);
+                                if T::distance(min, T::max_value()) >= extent {
+                                    ranges.push(min..=min.add_distance(extent));
+                                } else {
+                                    ranges.push(min..=T::max_value());
+                                    ranges.push(T::min_value()..=min.add_distance(extent));
+                                }
+                            }
+                            _ => return RangeSet::total(),
+                        }
+                    }
+                }
+            }
+        }
+
+        RangeSet::new_from_iter(ranges)
+    }
+
+    /// Return the union with the given `RangeSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
+    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
+    ///
+    /// // Contains 2..=5
+    /// let union = range1.union(&range2);
+    /// ```
+    pub fn union(&self, that: &RangeSet<T>) -> RangeSet<T> {
+        self.union_with_policy(that, AllocationPolicy::default())
+    }
+
+    /// Return the union with the given `RangeSet`, sizing its allocation with the given policy.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::{AllocationPolicy, RangeSet};
+    ///
+    /// let range1 = RangeSet::<i64>(vec![0, 9, 20, 29]);
+    /// let range2 = RangeSet::<i64>(vec![5, 24]);
+    ///
+    /// let union = range1.union_with_policy(&range2, AllocationPolicy::ShrinkToFit);
+    /// assert_eq!(union.0.len(), union.0.capacity());
+    /// ```
+    pub fn union_with_policy(&self, that: &RangeSet<T>, policy: AllocationPolicy) -> RangeSet<T> {
+        let mut union = RangeSet::empty();
+        self.union_into(that, &mut union);
+        policy.apply(&mut union.0);
+        check_invariants!("union_with_policy", "output", union);
+        union
+    }
+
+    /// Replace the content of `out` with the union with the given `RangeSet`, reusing its capacity.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
+    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
+    /// let mut out = RangeSet::empty();
+    ///
+    /// range1.union_into(&range2, &mut out);
+    /// assert_eq!(RangeSet::new_from_range(2..=5), out);
+    /// ```
+    pub fn union_into(&self, that: &RangeSet<T>, out: &mut RangeSet<T>) {
+        check_invariants!("union_into", "input", self, that);
+        let new_range = &mut out.0;
+        new_range.clear();
+        if self.is_empty() || that.is_total() {
+            new_range.extend_from_slice(&that.0);
+            return;
+        } else if that.is_empty() || self.is_total() {
+            new_range.extend_from_slice(&self.0);
+            return;
+        }
+
+        new_range.reserve(self.0.len() + that.0.len());
+
+        let mut self_i = 0;
+        let mut that_i = 0;
+        while self_i < self.0.len() && that_i < that.0.len() {
+            if self.0[self_i] <= that.0[that_i] {
+                self_i = push_merged_below(new_range, &self.0, self_i, that.0[that_i]);
+            } else {
+                that_i = push_merged_below(new_range, &that.0, that_i, self.0[self_i]);
+            }
+        }
+
+        let tail = if self_i < self.0.len() {
+            &self.0[self_i..]
+        } else {
+            &that.0[that_i..]
+        };
+        extend_merged(new_range, tail);
+        check_invariants!("union_into", "output", out);
+    }
+
+    /// Return the union with the given range.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<i64>(vec![2, 4, 10, 20]);
+    ///
+    /// assert_eq!(RangeSet(vec![2, 20]), range.union_range(5..10));
+    /// assert_eq!(RangeSet(vec![2, 4, 7, 7, 10, 20]), range.union_range(7..=7));
+    /// ```
+    pub fn union_range<R: RangeBounds<T>>(&self, range: R) -> RangeSet<T> {
+        check_invariants!("union_range", "input", self);
+        let (min, max) = range_to_bounds(&range);
+        if min > max {
+            return self.clone();
+        }
+
+        let start = self.0.partition_point(|&x| x < min);
+        let mut end = self.0.partition_point(|&x| x <= max);
+
+        let mut new_range = Vec::with_capacity(self.0.len() + 2);
+        new_range.extend_from_slice(&self.0[..start]);
+        if start % 2 == 0 {
+            if start > 0 && self.0[start - 1] + T::one() == min {
+                new_range.pop();
+            } else {
+                new_range.push(min);
+            }
+        }
+        if end % 2 == 0 {
+            if end < self.0.len() && max < T::max_value() && self.0[end] == max + T::one() {
+                end += 1;
+            } else {
+                new_range.push(max);
+            }
+        }
+        new_range.extend_from_slice(&self.0[end..]);
+
+        let new_range = RangeSet(new_range);
+        check_invariants!("union_range", "output", new_range);
+        new_range
+    }
+
+    /// Return the union with the given `RangeSet`, or the first range of common values if they are not disjoint.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::{OverlapError, RangeSet};
+    ///
+    /// let free = RangeSet::<u64>(vec![0, 9, 20, 29]);
+    ///
+    /// assert_eq!(Ok(RangeSet(vec![0, 29])), free.try_union_disjoint(&RangeSet(vec![10, 19])));
+    /// assert_eq!(
+    ///     Err(OverlapError { min: 8, max: 9 }),
+    ///     free.try_union_disjoint(&RangeSet(vec![8, 19]))
+    /// );
+    /// ```
+    pub fn try_union_disjoint(&self, that: &RangeSet<T>) -> Result<RangeSet<T>, OverlapError<T>> {
+        check_invariants!("try_union_disjoint", "input", self, that);
+        let mut i = 0;
+        let mut j = 0;
+
+        while i < self.0.len() && j < that.0.len() {
+            let self_min = self.0[i];
+            let self_max = self.0[i + 1];
+            let that_min = that.0[j];
+            let that_max = that.0[j + 1];
+
+            if self_max < that_min {
+                i += 2;
+            } else if that_max < self_min {
+                j += 2;
+            } else {
+                return Err(OverlapError {
+                    min: core::cmp::max(self_min, that_min),
+                    max: core::cmp::min(self_max, that_max),
+                });
+            }
+        }
+
+        Ok(self.union(that))
+    }
+
+    /// Replace with the union with the given `RangeSet`, return `true` if it changed.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let mut range = RangeSet::<i64>::new_from_range(2..10);
+    ///
+    /// assert!(range.union_changed(&RangeSet::new_from_range(8..12)));
+    /// assert!(!range.union_changed(&RangeSet::new_from_range(3..5)));
+    /// assert_eq!(RangeSet::new_from_range(2..12), range);
+    /// ```
+    pub fn union_changed(&mut self, that: &RangeSet<T>) -> bool {
+        if self.contains_all(that) {
+            false
+        } else {
+            *self = self.union(that);
+            true
+        }
+    }
+
+    /// Return `true` if there is common value with the given `RangeSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
+    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
+    /// let range3 = RangeSet::<i64>::new_from_range(5..13);
+    ///
+    /// assert!(range1.has_intersection(&range2));
+    /// assert!(!range1.has_intersection(&range3));
+    /// assert!(range2.has_intersection(&range3));
+    /// ```
+    pub fn has_intersection(&self, that: &RangeSet<T>) -> bool {
+        check_invariants!("has_intersection", "input", self, that);
+        let mut i = 0;
+        let mut j = 0;
+
+        while i < self.0.len() && j < that.0.len() {
+            let self_min = self.0[i];
+            let self_max = self.0[i + 1];
+            let that_min = that.0[j];
+            let that_max = that.0[j + 1];
+
+            if self_max < that_min {
+                i = kernel::skip_ranges_below(&self.0, i + 2, that_min);
+            } else if that_max < self_min {
+                j = kernel::skip_ranges_below(&that.0, j + 2, self_min);
+            } else {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Return the intersection with the given `RangeSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
+    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
+    ///
+    /// // Contains 2..=3
+    /// let intersection = range1.intersection(&range2);
+    /// ```
+    pub fn intersection(&self, that: &RangeSet<T>) -> RangeSet<T> {
+        self.intersection_with_policy(that, AllocationPolicy::default())
+    }
+
+    /// Return the intersection with the given `RangeSet`, sizing its allocation with the given policy.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::{AllocationPolicy, RangeSet};
+    ///
+    /// let range1 = RangeSet::<i64>(vec![0, 9, 20, 29]);
+    /// let range2 = RangeSet::<i64>(vec![5, 24]);
+    ///
+    /// let intersection = range1.intersection_with_policy(&range2, AllocationPolicy::ShrinkToFit);
+    /// assert_eq!(intersection.0.len(), intersection.0.capacity());
+    /// ```
+    pub fn intersection_with_policy(
+        &self,
+        that: &RangeSet<T>,
+        policy: AllocationPolicy,
+    ) -> RangeSet<T> {
+        let mut intersection = RangeSet::empty();
+        self.intersection_into(that, &mut intersection);
+        policy.apply(&mut intersection.0);
+        check_invariants!("intersection_with_policy", "output", intersection);
+        intersection
+    }
+
+    /// Replace the content of `out` with the intersection with the given `RangeSet`, reusing its capacity.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
+    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
+    /// let mut out = RangeSet::empty();
+    ///
+    /// range1.intersection_into(&range2, &mut out);
+    /// assert_eq!(RangeSet::new_from_range(3..4), out);
+    /// ```
+    pub fn intersection_into(&self, that: &RangeSet<T>, out: &mut RangeSet<T>) {
+        check_invariants!("intersection_into", "input", self, that);
+        let new_range = &mut out.0;
+        new_range.clear();
+        if self.is_empty() || that.is_empty() {
+            return;
+        } else if self.is_total() {
+            new_range.extend_from_slice(&that.0);
+            return;
+        } else if that.is_total() {
+            new_range.extend_from_slice(&self.0);
+            return;
+        }
+
+        // An estimate, the intersection can have up to one range less than both sets combined.
+        new_range.reserve(core::cmp::max(self.0.len(), that.0.len()));
+
+        let mut i = 0;
+        let mut j = 0;
+
+        while i < self.0.len() && j < that.0.len() {
+            let self_min = self.0[i];
+            let self_max = self.0[i + 1];
+            let that_min = that.0[j];
+            let that_max = that.0[j + 1];
+
+            if self_max < that_min {
+                i = kernel::skip_ranges_below(&self.0, i + 2, that_min);
+            } else if that_max < self_min {
+                j = kernel::skip_ranges_below(&that.0, j + 2, self_min);
+            } else {
+                new_range.push(core::cmp::max(self_min, that_min));
+                new_range.push(core::cmp::min(self_max, that_max));
+
+                if self_max < that_max {
+                    i += 2;
+                } else {
+                    j += 2;
+                }
+            }
+        }
+        check_invariants!("intersection_into", "output", out);
+    }
+
+    /// Return the intersection with the given range.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<i64>(vec![2, 4, 10, 20]);
+    ///
+    /// assert_eq!(RangeSet(vec![3, 4, 10, 11]), range.intersection_range(3..12));
+    /// ```
+    pub fn intersection_range<R: RangeBounds<T>>(&self, range: R) -> RangeSet<T> {
+        check_invariants!("intersection_range", "input", self);
+        let (min, max) = range_to_bounds(&range);
+        if min > max {
+            return RangeSet::empty();
+        }
+
+        let start = self.0.partition_point(|&x| x < min);
+        let end = self.0.partition_point(|&x| x <= max);
+
+        let mut new_range = Vec::with_capacity(end - start + 2);
+        if start % 2 != 0 {
+            new_range.push(min);
+        }
+        new_range.extend_from_slice(&self.0[start..end]);
+        if end % 2 != 0 {
+            new_range.push(max);
+        }
+
+        let new_range = RangeSet(new_range);
+        check_invariants!("intersection_range", "output", new_range);
+        new_range
+    }
+
+    /// Replace with the intersection with the given `RangeSet`, return `true` if it changed.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let mut range = RangeSet::<i64>::new_from_range(2..10);
+    ///
+    /// assert!(range.intersect_changed(&RangeSet::new_from_range(0..5)));
+    /// assert!(!range.intersect_changed(&RangeSet::new_from_range(..)));
+    /// assert_eq!(RangeSet::new_from_range(2..5), range);
+    /// ```
+    pub fn intersect_changed(&mut self, that: &RangeSet<T>) -> bool {
+        if that.contains_all(self) {
+            false
+        } else {
+            *self = self.intersection(that);
+            true
+        }
+    }
+
+    /// Return the complement.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u8>::new_from_range(2..4);
+    ///
+    /// // Contains 0..=1 + 4..=255
+    /// range.complement();
+    /// ```
+    pub fn complement(&self) -> RangeSet<T> {
+        self.complement_with_policy(AllocationPolicy::default())
+    }
+
+    /// Return the complement, sizing its allocation with the given policy.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::{AllocationPolicy, RangeSet};
+    ///
+    /// let range1 = RangeSet::<i64>(vec![0, 9, 20, 29]);
+    ///
+    /// let complement = range1.complement_with_policy(AllocationPolicy::ShrinkToFit);
+    /// assert_eq!(complement.0.len(), complement.0.capacity());
+    /// ```
+    pub fn complement_with_policy(&self, policy: AllocationPolicy) -> RangeSet<T> {
+        let mut complement = RangeSet::empty();
+        self.complement_into(&mut complement);
+        policy.apply(&mut complement.0);
+        check_invariants!("complement_with_policy", "output", complement);
+        complement
+    }
+
+    /// Replace the content of `out` with the complement, reusing its capacity.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u8>::new_from_range(2..4);
+    /// let mut out = RangeSet::empty();
+    ///
+    /// range.complement_into(&mut out);
+    /// assert_eq!(RangeSet(vec![0, 1, 4, 255]), out);
+    /// ```
+    pub fn complement_into(&self, out: &mut RangeSet<T>) {
+        check_invariants!("complement_into", "input", self);
+        let new_range = &mut out.0;
+        new_range.clear();
+        if self.is_empty() {
+            new_range.extend_from_slice(&[T::min_value(), T::max_value()]);
+            return;
+        } else if self.is_total() {
+            return;
+        }
+
+        new_range.reserve(self.0.len() + 2);
+
+        for i in (0..self.0.len()).step_by(2) {
+            let (min, max) = (self.0[i], self.0[i + 1]);
+
+            if new_range.is_empty() && min != T::min_value() {
+                new_range.push(T::min_value());
+                new_range.push(min - T::one());
+            }
+
+            if new_range.len() % 2 == 1 {
+                new_range.push(min - T::one());
+            }
+            if max < T::max_value() {
+                new_range.push(max + T::one());
+            }
+        }
+        if new_range.len() % 2 == 1 {
+            new_range.push(T::max_value());
+        }
+        check_invariants!("complement_into", "output", out);
+    }
+
+    /// Replace the content with its complement, rewriting the bounds in place.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let mut range = RangeSet::<u8>(vec![0, 9, 20, 29]);
+    ///
+    /// range.invert();
+    /// assert_eq!(RangeSet(vec![10, 19, 30, 255]), range);
+    /// range.invert();
+    /// assert_eq!(RangeSet(vec![0, 9, 20, 29]), range);
+    /// ```
+    pub fn invert(&mut self) {
+        check_invariants!("invert", "input", self);
+        let bounds = &mut self.0;
+        if bounds.is_empty() {
+            bounds.extend_from_slice(&[T::min_value(), T::max_value()]);
+            return;
+        }
+
+        let starts_at_min = bounds[0] == T::min_value();
+        let ends_at_max = bounds[bounds.len() - 1] == T::max_value();
+        // Only the first bound can be the minimum and only the last one the maximum.
+        for range in bounds.chunks_exact_mut(2) {
+            if range[0] > T::min_value() {
+                range[0] = range[0] - T::one();
+            }
+            if range[1] < T::max_value() {
+                range[1] += T::one();
+            }
+        }
+        if ends_at_max {
+            bounds.pop();
+        } else {
+            bounds.push(T::max_value());
+        }
+        if starts_at_min {
+            bounds.remove(0);
+        } else {
+            bounds.insert(0, T::min_value());
+        }
+        check_invariants!("invert", "output", self);
+    }
+
+    /// Return the difference with the given `RangeSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
+    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
+    ///
+    /// // Contains 2
+    /// let difference = range1.difference(&range2);
+    /// ```
+    pub fn difference(&self, that: &RangeSet<T>) -> RangeSet<T> {
+        self.difference_with_policy(that, AllocationPolicy::default())
+    }
+
+    /// Return the difference with the given `RangeSet`, sizing its allocation with the given policy.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::{AllocationPolicy, RangeSet};
+    ///
+    /// let range1 = RangeSet::<i64>(vec![0, 9, 20, 29]);
+    /// let range2 = RangeSet::<i64>(vec![5, 24]);
+    ///
+    /// let difference = range1.difference_with_policy(&range2, AllocationPolicy::ShrinkToFit);
+    /// assert_eq!(difference.0.len(), difference.0.capacity());
+    /// ```
+    pub fn difference_with_policy(
+        &self,
+        that: &RangeSet<T>,
+        policy: AllocationPolicy,
+    ) -> RangeSet<T> {
+        let mut difference = RangeSet::empty();
+        self.difference_into(that, &mut difference);
+        policy.apply(&mut difference.0);
+        check_invariants!("difference_with_policy", "output", difference);
+        difference
+    }
+
+    /// Replace the content of `out` with the difference with the given `RangeSet`, reusing its capacity.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<i64>::new_from_range(2..4);
+    /// let range2 = RangeSet::<i64>::new_from_range(3..=5);
+    /// let mut out = RangeSet::empty();
+    ///
+    /// range1.difference_into(&range2, &mut out);
+    /// assert_eq!(RangeSet::new_from_range(2..3), out);
+    /// ```
+    pub fn difference_into(&self, that: &RangeSet<T>, out: &mut RangeSet<T>) {
+        check_invariants!("difference_into", "input", self, that);
+        let new_range = &mut out.0;
+        new_range.clear();
+        new_range.reserve(self.0.len() + that.0.len());
+
+        let mut j = 0;
+        for i in (0..self.0.len()).step_by(2) {
+            let (mut min, max) = (self.0[i], self.0[i + 1]);
+
+            j = kernel::skip_ranges_below(&that.0, j, min);
+
+            let mut k = j;
+            let mut remaining = true;
+            while k < that.0.len() && that.0[k] <= max {
+                let (that_min, that_max) = (that.0[k], that.0[k + 1]);
+                if that_min > min {
+                    new_range.extend_from_slice(&[min, that_min - T::one()]);
+                }
+                if that_max >= max {
+                    remaining = false;
+                    break;
+                }
+                min = that_max + T::one();
+                k += 2;
+            }
+            if remaining {
+                new_range.extend_from_slice(&[min, max]);
+            }
+        }
+        check_invariants!("difference_into", "output", out);
+    }
+
+    /// Return an iterator over the ranges contained, as `min..=max`.
+    fn range_iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.0.chunks_exact(2).map(|range| range[0]..=range[1])
+    }
+
+    /// Return an iterator over the ranges of the union with the given `RangeSet`, computed lazily without building it.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<u32>(vec![0, 9, 20, 29]);
+    /// let range2 = RangeSet::<u32>(vec![10, 14, 40, 49]);
+    ///
+    /// assert_eq!(
+    ///     vec![0..=14, 20..=29, 40..=49],
+    ///     range1.union_ranges_iter(&range2).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn union_ranges_iter<'a>(
+        &'a self,
+        that: &'a RangeSet<T>,
+    ) -> Union<
+        T,
+        impl Iterator<Item = RangeInclusive<T>> + 'a,
+        impl Iterator<Item = RangeInclusive<T>> + 'a,
+    > {
+        Union::new(self.range_iter(), that.range_iter())
+    }
+
+    /// Return an iterator over the ranges of the intersection with the given `RangeSet`, computed lazily without building it.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<u32>(vec![0, 9, 20, 29]);
+    /// let range2 = RangeSet::<u32>(vec![5, 24]);
+    ///
+    /// assert_eq!(
+    ///     vec![5..=9, 20..=24],
+    ///     range1.intersection_ranges_iter(&range2).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn intersection_ranges_iter<'a>(
+        &'a self,
+        that: &'a RangeSet<T>,
+    ) -> Intersection<
+        T,
+        impl Iterator<Item = RangeInclusive<T>> + 'a,
+        impl Iterator<Item = RangeInclusive<T>> + 'a,
+    > {
+        Intersection::new(self.range_iter(), that.range_iter())
+    }
+
+    /// Return an iterator over the ranges of the difference with the given `RangeSet`, computed lazily without building it.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<u32>(vec![0, 9, 20, 29]);
+    /// let range2 = RangeSet::<u32>(vec![5, 24]);
+    ///
+    /// assert_eq!(
+    ///     vec![0..=4, 25..=29],
+    ///     range1.difference_ranges_iter(&range2).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn difference_ranges_iter<'a>(
+        &'a self,
+        that: &'a RangeSet<T>,
+    ) -> Difference<
+        T,
+        impl Iterator<Item = RangeInclusive<T>> + 'a,
+        impl Iterator<Item = RangeInclusive<T>> + 'a,
+    > {
+        Difference::new(self.range_iter(), that.range_iter())
+    }
+
+    /// Return an iterator over the values of the union with the given `RangeSet`, computed lazily without building it.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<u32>(vec![0, 2, 20, 20]);
+    /// let range2 = RangeSet::<u32>(vec![3, 4]);
+    ///
+    /// assert_eq!(vec![0, 1, 2, 3, 4, 20], range1.union_iter(&range2).collect::<Vec<_>>());
+    /// ```
+    pub fn union_iter<'a>(
+        &'a self,
+        that: &'a RangeSet<T>,
+    ) -> Values<T, impl Iterator<Item = RangeInclusive<T>> + 'a> {
+        Values::new(self.union_ranges_iter(that))
+    }
+
+    /// Return an iterator over the values of the intersection with the given `RangeSet`, computed lazily without building it.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<u32>(vec![0, 9, 20, 29]);
+    /// let range2 = RangeSet::<u32>(vec![8, 21]);
+    ///
+    /// assert_eq!(vec![8, 9, 20, 21], range1.intersection_iter(&range2).collect::<Vec<_>>());
+    /// ```
+    pub fn intersection_iter<'a>(
+        &'a self,
+        that: &'a RangeSet<T>,
+    ) -> Values<T, impl Iterator<Item = RangeInclusive<T>> + 'a> {
+        Values::new(self.intersection_ranges_iter(that))
+    }
+
+    /// Return an iterator over the values of the difference with the given `RangeSet`, computed lazily without building it.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range1 = RangeSet::<u32>(vec![0, 9]);
+    /// let range2 = RangeSet::<u32>(vec![2, 8]);
+    ///
+    /// assert_eq!(vec![0, 1, 9], range1.difference_iter(&range2).collect::<Vec<_>>());
+    /// ```
+    pub fn difference_iter<'a>(
+        &'a self,
+        that: &'a RangeSet<T>,
+    ) -> Values<T, impl Iterator<Item = RangeInclusive<T>> + 'a> {
+        Values::new(self.difference_ranges_iter(that))
+    }
+
+    /// Return the difference with the given range.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<i64>(vec![2, 4, 10, 20]);
+    ///
+    /// assert_eq!(RangeSet(vec![2, 2, 12, 20]), range.difference_range(3..12));
+    /// ```
+    pub fn difference_range<R: RangeBounds<T>>(&self, range: R) -> RangeSet<T> {
+        check_invariants!("difference_range", "input", self);
+        let (min, max) = range_to_bounds(&range);
+        if min > max {
+            return self.clone();
+        }
+
+        let start = self.0.partition_point(|&x| x < min);
+        let end = self.0.partition_point(|&x| x <= max);
+
+        let mut new_range = Vec::with_capacity(self.0.len() + 2);
+        new_range.extend_from_slice(&self.0[..start]);
+        if start % 2 != 0 {
+            new_range.push(min - T::one());
+        }
+        if end % 2 != 0 {
+            new_range.push(max + T::one());
+        }
+        new_range.extend_from_slice(&self.0[end..]);
+
+        let new_range = RangeSet(new_range);
+        check_invariants!("difference_range", "output", new_range);
+        new_range
+    }
+
+    /// Return the widening with the `RangeSet` computed at the previous iteration of a fixpoint computation.
+    ///
+    /// Every bound of the union that is not already a bound of `previous` is considered unstable and is pushed outward: up to the domain limits for the outermost ranges, or until it merges with the neighboring range otherwise.
+    /// Each call that does not reach a fixpoint removes at least one gap, so an ascending sequence of widenings always terminates.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let previous = RangeSet::<i32>(vec![0, 10, 20, 30]);
+    /// let current = RangeSet::<i32>(vec![0, 11, 20, 30]);
+    ///
+    /// // Contains 0..=30
+    /// let widened = current.widen(&previous);
+    /// assert_eq!(RangeSet(vec![0, 30]), widened);
+    ///
+    /// // Contains 0..=i32::MAX
+    /// let widened = RangeSet(vec![0, 31]).widen(&widened);
+    /// assert_eq!(RangeSet(vec![0, i32::MAX]), widened);
+    /// ```
+    pub fn widen(&self, previous: &RangeSet<T>) -> RangeSet<T> {
+        check_invariants!("widen", "input", self, previous);
+        let joined = previous.union(self);
+
+        let mut new_range: Vec<T> = Vec::with_capacity(joined.0.len());
+        let mut merge_with_next = false;
+
+        for i in (0..joined.0.len()).step_by(2) {
+            let (min, max) = (joined.0[i], joined.0[i + 1]);
+
+            let position = previous.0.partition_point(|&x| x < min);
+            let stable_min = position % 2 == 0 && previous.0.get(position) == Some(&min);
+            let position = previous.0.partition_point(|&x| x <= max);
+            let stable_max = position % 2 == 0 && position > 0 && previous.0[position - 1] == max;
+
+            if let (Some(last), true) = (new_range.last_mut(), merge_with_next || !stable_min) {
+                *last = max;
+            } else if stable_min {
+                new_range.extend_from_slice(&[min, max]);
+            } else {
+                new_range.extend_from_slice(&[T::min_value(), max]);
+            }
+            merge_with_next = !stable_max;
+        }
+        if let (Some(last), true) = (new_range.last_mut(), merge_with_next) {
+            *last = T::max_value();
+        }
+
+        new_range.shrink_to_fit();
+        let new_range = RangeSet(new_range);
+        check_invariants!("widen", "output", new_range);
+        new_range
+    }
+
+    /// Return the widening with the `RangeSet` computed at the previous iteration, made of at most `max_ranges` ranges.
+    ///
+    /// The result of `widen` is simplified by filling its smallest gaps, so the sets of a fixpoint computation never hold more than `max_ranges` ranges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_ranges` is `0` and the widening is not empty.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let previous = RangeSet::<i32>(vec![0, 10, 20, 30, 100, 110]);
+    /// let current = RangeSet::<i32>(vec![0, 10, 20, 30, 105, 110]);
+    ///
+    /// assert_eq!(previous, current.widen(&previous));
+    /// assert_eq!(
+    ///     RangeSet(vec![0, 30, 100, 110]),
+    ///     current.widen_with_max_ranges(&previous, 2)
+    /// );
+    /// ```
+    pub fn widen_with_max_ranges(&self, previous: &RangeSet<T>, max_ranges: usize) -> RangeSet<T> {
+        self.widen(previous).simplified(max_ranges)
+    }
+
+    /// Return the narrowing with the `RangeSet` computed at the next iteration of a fixpoint computation.
+    ///
+    /// Only the bounds that were widened to the domain limits are refined to the ones of `next`, so a descending sequence of narrowings always terminates.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let widened = RangeSet::<i32>(vec![0, 10, 20, i32::MAX]);
+    /// let next = RangeSet::<i32>(vec![2, 8, 20, 100]);
+    ///
+    /// // Contains 0..=10 + 20..=100
+    /// let narrowed = widened.narrow(&next);
+    /// assert_eq!(RangeSet(vec![0, 10, 20, 100]), narrowed);
+    /// ```
+    pub fn narrow(&self, next: &RangeSet<T>) -> RangeSet<T> {
+        check_invariants!("narrow", "input", self, next);
+        if next.is_empty() {
+            return next.clone();
+        }
+
+        let min = if self.0.first() == Some(&T::min_value()) {
+            next.0[0]
+        } else {
+            T::min_value()
+        };
+        let max = if self.0.last() == Some(&T::max_value()) {
+            next.0[next.0.len() - 1]
+        } else {
+            T::max_value()
+        };
+
+        self.intersection(&RangeSet::new_from_range(min..=max))
+    }
+}
+
+/// Operations on sets of bytes, the ASCII character classes having the same semantic as the `is_ascii_*` methods of `u8`.
+#[cfg(feature = "alloc")]
+impl RangeSet<u8> {
+    /// Return the ASCII decimal digits `0-9`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let digits = RangeSet::<u8>::ascii_digit();
+    /// assert!(digits.contains(b'7'));
+    /// assert!(!digits.contains(b'a'));
+    /// ```
+    pub fn ascii_digit() -> RangeSet<u8> {
+        RangeSet(vec![b'0', b'9'])
+    }
+
+    /// Return the ASCII hexadecimal digits `0-9`, `A-F` and `a-f`.
+    pub fn ascii_hexdigit() -> RangeSet<u8> {
+        RangeSet(vec![b'0', b'9', b'A', b'F', b'a', b'f'])
+    }
+
+    /// Return the ASCII uppercase letters `A-Z`.
+    pub fn ascii_uppercase() -> RangeSet<u8> {
+        RangeSet(vec![b'A', b'Z'])
+    }
+
+    /// Return the ASCII lowercase letters `a-z`.
+    pub fn ascii_lowercase() -> RangeSet<u8> {
+        RangeSet(vec![b'a', b'z'])
+    }
+
+    /// Return the ASCII letters `A-Z` and `a-z`.
+    pub fn ascii_alphabetic() -> RangeSet<u8> {
+        RangeSet(vec![b'A', b'Z', b'a', b'z'])
+    }
+
+    /// Return the ASCII letters and digits `0-9`, `A-Z` and `a-z`.
+    pub fn ascii_alphanumeric() -> RangeSet<u8> {
+        RangeSet(vec![b'0', b'9', b'A', b'Z', b'a', b'z'])
+    }
+
+    /// Return the ASCII whitespaces as defined by the WHATWG: space, `\t`, `\n`, form feed and `\r`.
+    pub fn ascii_whitespace() -> RangeSet<u8> {
+        RangeSet(vec![b'\t', b'\n', 0x0C, b'\r', b' ', b' '])
+    }
+
+    /// Return the ASCII punctuation characters, the graphic characters that are neither letters nor digits.
+    pub fn ascii_punctuation() -> RangeSet<u8> {
+        RangeSet(vec![b'!', b'/', b':', b'@', b'[', b'`', b'{', b'~'])
+    }
+
+    /// Return the ASCII graphic characters `!` to `~`.
+    pub fn ascii_graphic() -> RangeSet<u8> {
+        RangeSet(vec![b'!', b'~'])
+    }
+
+    /// Return the ASCII control characters `0x00-0x1F` and `0x7F`.
+    pub fn ascii_control() -> RangeSet<u8> {
+        RangeSet(vec![0x00, 0x1F, 0x7F, 0x7F])
+    }
+
+    /// Return all the ASCII characters `0x00-0x7F`.
+    pub fn ascii() -> RangeSet<u8> {
+        RangeSet(vec![0x00, 0x7F])
+    }
+
+    /// Return the bytes as a regex character class such as `[0-9A-F\x80-\xFF]`, for byte-oriented regexes (`(?-u)` in the `regex` crate).
+    ///
+    /// The class is negated when it is shorter, and the characters with a special meaning in a class are escaped.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// assert_eq!("[0-9A-Fa-f]", RangeSet::<u8>::ascii_hexdigit().to_regex_class());
+    /// assert_eq!("[^\\x0A]", RangeSet::<u8>(vec![0, 9, 11, 255]).to_regex_class());
+    /// assert_eq!("[\\-\\]\\x80-\\xFF]", RangeSet::<u8>(vec![b'-', b'-', b']', b']', 0x80, 0xFF]).to_regex_class());
+    /// ```
+    pub fn to_regex_class(&self) -> String {
+        let bounds = self.0.iter().map(|&bound| u32::from(bound)).collect();
+        regex_class(&RangeSet(bounds), &[0, 0xFF], |byte, class| {
+            class.push_str(&format!("\\x{byte:02X}"))
+        })
+    }
+}
+
+/// Operations on sets of code points.
+#[cfg(feature = "alloc")]
+impl RangeSet<u32> {
+    /// Return the code points as a regex character class such as `[0-9a-f\u{1F300}-\u{1F5FF}]`.
+    ///
+    /// The class is negated when it is shorter, the characters with a special meaning in a class are escaped, and the code points that are not Unicode scalar values are ignored.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>::new_from_iter(['a' as u32..='f' as u32, '0' as u32..='9' as u32, 0x1F300..=0x1F5FF]);
+    /// assert_eq!("[0-9a-f\\u{1F300}-\\u{1F5FF}]", range.to_regex_class());
+    ///
+    /// let range = RangeSet::<u32>::new_from_range('^' as u32..='^' as u32).complement();
+    /// assert_eq!("[^\\^]", range.to_regex_class());
+    /// ```
+    pub fn to_regex_class(&self) -> String {
+        regex_class(self, &[0, 0xD7FF, 0xE000, 0x10FFFF], |code_point, class| {
+            class.push_str(&format!("\\u{{{code_point:X}}}"))
+        })
+    }
+}
+
+/// Render the values of the given domain contained in the set as a regex character class, calling `escape` for the values that are not printable ASCII.
+#[cfg(feature = "alloc")]
+fn regex_class(set: &RangeSet<u32>, domain: &[u32], escape: fn(u32, &mut String)) -> String {
+    let render = |value: u32, class: &mut String| match char::from_u32(value) {
+        Some(c) if c.is_ascii_graphic() => {
+            if matches!(c, '\\' | '[' | ']' | '^' | '-' | '&' | '~') {
+                class.push('\\');
+            }
+            class.push(c);
+        }
+        _ => escape(value, class),
+    };
+
+    let domain = RangeSet(domain.to_vec());
+    let set = set.intersection(&domain);
+    let complement = domain.difference(&set);
+
+    let mut class = String::from("[");
+    let ranges = if set.is_empty() {
+        class.push('^');
+        &domain
+    } else if !complement.is_empty() && complement.0.len() < set.0.len() {
+        class.push('^');
+        &complement
+    } else {
+        &set
+    };
+    for range in ranges.0.chunks_exact(2) {
+        render(range[0], &mut class);
+        if range[1] > range[0] {
+            if range[1] > range[0] + 1 {
+                class.push('-');
+            }
+            render(range[1], &mut class);
+        }
+    }
+    class.push(']');
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    macro_rules! test_empty_and_total_for_types {
+        ($($t:ty),*) => {
+            $(
+                let empty = RangeSet::<$t>::empty();
+                assert!(empty.is_empty());
+                assert!(!empty.is_total());
+
+                let total = RangeSet::<$t>::total();
+                assert!(!total.is_empty());
+                assert!(total.is_total());
+            )*
+        };
+    }
+
+    #[test]
+    fn test_empty_and_total() -> Result<(), String> {
+        test_empty_and_total_for_types!(
+            u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_from_range() -> Result<(), String> {
+        assert_eq!(
+            RangeSet::<u8>(vec![3, 4]),
+            RangeSet::<u8>::new_from_range(3..5)
+        );
+
+        assert_eq!(
+            RangeSet::<u8>(vec![3, 5]),
+            RangeSet::<u8>::new_from_range(3..=5)
+        );
+
+        assert_eq!(
+            RangeSet::<u8>(vec![0, 5]),
+            RangeSet::<u8>::new_from_range(..=5)
+        );
+
+        assert_eq!(
+            RangeSet::<u8>(vec![2, 255]),
+            RangeSet::<u8>::new_from_range(2..)
+        );
+
+        assert_eq!(
+            RangeSet::<u8>(vec![0, 255]),
+            RangeSet::<u8>::new_from_range(..)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_new_from_ranges() -> Result<(), String> {
+        assert_eq!(
+            RangeSet(vec![3, 5, 9, 14]),
+            RangeSet::new_from_ranges(&[AnyRange::from(3..=5), AnyRange::from(9..15)])
+        );
+
+        assert_eq!(
+            RangeSet(vec![3, 5, 9, 14]),
+            RangeSet::new_from_ranges(&[AnyRange::from(9..15), AnyRange::from(3..=5)])
+        );
+
+        assert_eq!(
+            RangeSet(vec![3, 5, 9, 14]),
+            RangeSet::new_from_ranges(&[
+                AnyRange::from(9..15),
+                AnyRange::from(23..18),
+                AnyRange::from(3..=5),
+                AnyRange::from(4..=4)
+            ])
+        );
+
+        assert_eq!(
+            RangeSet(vec![3, 5, 9, 14]),
+            RangeSet::new_from_ranges(&[
+                AnyRange::from(9..15),
+                AnyRange::from(23..18),
+                AnyRange::from(3..=5)
+            ])
+        );
+
+        assert_eq!(
+            RangeSet(vec![3, 255]),
+            RangeSet::<u8>::new_from_ranges(&[AnyRange::from(4..), AnyRange::from(3..=5)])
+        );
+
+        assert_eq!(
+            RangeSet(vec![0, 5]),
+            RangeSet::<u8>::new_from_ranges(&[AnyRange::from(..=4), AnyRange::from(3..=5)])
+        );
+
+        assert_eq!(
+            RangeSet::total(),
+            RangeSet::new_from_ranges(&[AnyRange::from(..), AnyRange::from(3..=5)])
+        );
+
+        assert_eq!(
+            RangeSet::empty(),
+            RangeSet::new_from_ranges(&[AnyRange::from(3..1), AnyRange::from(10..=5)])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains_small_and_large() -> Result<(), String> {
+        let mut range = RangeSet::<i8>::empty();
+        for min in (-120..120).step_by(12) {
+            for value in i8::MIN..=i8::MAX {
+                let expected = range
+                    .0
+                    .chunks_exact(2)
+                    .any(|r| r[0] <= value && value <= r[1]);
+                assert_eq!(expected, range.contains(value));
+            }
+            range = range.union_range(min..=min + 5);
+        }
+        assert!(range.0.len() > LINEAR_SCAN_BOUNDS);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains_any() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-100, -90, 0, 0, 10, 20, 127, 127]);
+        assert!(!RangeSet::<i8>::empty().contains_any(i8::MIN..=i8::MAX));
+        assert!(!range.contains_any([]));
+        assert!(!range.contains_any_sorted(&[]));
+
+        let values: Vec<i8> = (i8::MIN..=i8::MAX).collect();
+        for window in values.windows(3) {
+            let expected = window.iter().any(|&value| range.contains(value));
+            assert_eq!(expected, range.contains_any(window.iter().copied()));
+            assert_eq!(expected, range.contains_any(window.iter().rev().copied()));
+            assert_eq!(expected, range.contains_any_sorted(window));
+        }
+        assert!(range.contains_any([126, -128, 127]));
+        assert!(range.contains_any([5, -95]));
+        assert!(range.contains_any_sorted(&[-128, -50, 1, 9, 126, 127]));
+        assert!(!range.contains_any_sorted(&[-128, -50, 1, 9, 126]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_minimal_cover() -> Result<(), String> {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        assert_eq!(Some(vec![]), RangeSet::<u8>::empty().minimal_cover(&[]));
+        assert_eq!(None, RangeSet::<u8>::total().minimal_cover(&[]));
+        assert_eq!(
+            Some(vec![1]),
+            RangeSet::<u8>::total().minimal_cover(&[0..=100, 0..=255, 100..=255])
+        );
+
+        let mut rng = StdRng::seed_from_u64(215);
+        for _ in 0..300 {
+            let range = RangeSet::<u8>::new_from_iter((0..rng.gen_range(0..4)).map(|_| {
+                let min: u8 = rng.gen();
+                min..=min.saturating_add(rng.gen_range(0..30))
+            }));
+            let candidates: Vec<RangeInclusive<u8>> = (0..rng.gen_range(0..8))
+                .map(|_| {
+                    let min: u8 = rng.gen();
+                    min..=min.saturating_add(rng.gen_range(0..60))
+                })
+                .collect();
+
+            // The size of the smallest subset of candidates containing the set.
+            let smallest = (0..1u32 << candidates.len())
+                .filter(|subset| {
+                    let union = RangeSet::new_from_iter(
+                        (0..candidates.len())
+                            .filter(|i| subset & (1 << i) != 0)
+                            .map(|i| candidates[i].clone()),
+                    );
+                    union.contains_all(&range)
+                })
+                .map(u32::count_ones)
+                .min();
+
+            let cover = range.minimal_cover(&candidates);
+            assert_eq!(smallest, cover.as_ref().map(|cover| cover.len() as u32));
+            if let Some(cover) = cover {
+                let union = RangeSet::new_from_iter(cover.iter().map(|&i| candidates[i].clone()));
+                assert!(union.contains_all(&range));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains_all() -> Result<(), String> {
+        assert!(RangeSet::<u8>::empty().contains_all(&RangeSet::empty()));
+        assert!(RangeSet::<u8>::total().contains_all(&RangeSet::empty()));
+        assert!(!RangeSet::<u8>::empty().contains_all(&RangeSet::total()));
+
+        assert!(!RangeSet(vec![2, 44]).contains_all(&RangeSet(vec![56, 60])));
+        assert!(!RangeSet(vec![56, 60]).contains_all(&RangeSet(vec![2, 44])));
+        assert!(RangeSet(vec![56, 60]).contains_all(&RangeSet(vec![56, 60])));
+        assert!(RangeSet(vec![56, 61]).contains_all(&RangeSet(vec![56, 61])));
+        assert!(RangeSet(vec![55, 61]).contains_all(&RangeSet(vec![56, 60])));
+
+        assert!(RangeSet(vec![56, 60]).contains_all(&RangeSet(vec![57, 59])));
+        assert!(RangeSet(vec![56, 60]).contains_all(&RangeSet(vec![57, 60])));
+        assert!(RangeSet(vec![56, 60]).contains_all(&RangeSet(vec![56, 59])));
+
+        assert!(RangeSet(vec![19, 33]).contains_all(&RangeSet(vec![20, 21, 30, 32])));
+        assert!(
+            RangeSet(vec![19, 33, 53, 70]).contains_all(&RangeSet(vec![20, 21, 30, 32, 66, 69]))
+        );
+        assert!(
+            !RangeSet(vec![19, 33, 53, 70]).contains_all(&RangeSet(vec![20, 21, 30, 32, 66, 71]))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inclusion_cmp() -> Result<(), String> {
+        let empty = RangeSet::<u8>::empty();
+        let total = RangeSet::<u8>::total();
+        assert_eq!(Some(Ordering::Equal), empty.inclusion_cmp(&empty));
+        assert_eq!(Some(Ordering::Less), empty.inclusion_cmp(&total));
+        assert_eq!(Some(Ordering::Greater), total.inclusion_cmp(&empty));
+
+        let range1 = RangeSet(vec![19, 33, 53, 70]);
+        let range2 = RangeSet(vec![20, 21, 30, 32, 66, 69]);
+        let range3 = RangeSet(vec![20, 21, 30, 32, 66, 71]);
+        assert_eq!(Some(Ordering::Greater), range1.inclusion_cmp(&range2));
+        assert_eq!(Some(Ordering::Less), range2.inclusion_cmp(&range1));
+        assert_eq!(Some(Ordering::Less), range2.inclusion_cmp(&range3));
+        assert_eq!(None, range1.inclusion_cmp(&range3));
+        assert_eq!(None, range3.inclusion_cmp(&range1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_and_contains() -> Result<(), String> {
+        let empty = RangeSet::<u8>::empty();
+        assert_eq!(0, empty.iter().count());
+        assert!(!empty.contains(0));
+
+        let total = RangeSet::<u8>::total();
+        assert_eq!(256, total.iter().collect::<HashSet<_>>().len());
+        assert!(total.iter().all(|v| total.contains(v)));
+
+        let range = RangeSet(vec![19, 33, 53, 70]);
+        assert_eq!(33, range.iter().collect::<HashSet<_>>().len()); // (33 - 19 + 1) + (77 - 53 + 1) = 33
+        assert!(range.iter().all(|v| range.contains(v)));
+        assert!(!range.contains(34));
+
+        let range = RangeSet(vec![i8::MIN, -127, 125, i8::MAX]);
+        assert_eq!(
+            vec![-128, -127, 125, 126, 127],
+            range.iter().collect::<Vec<_>>()
+        );
+        let mut iter = range.iter().skip(5);
+        assert_eq!(None, iter.next());
+        assert_eq!(None, iter.next());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_entry() -> Result<(), String> {
+        let mut range = RangeSet::<u8>::empty();
+        range.entry(255).or_insert();
+        range.entry(0).or_insert();
+        assert_eq!(RangeSet(vec![0, 0, 255, 255]), range);
+
+        range.entry_range(10..=20).or_insert();
+        range.entry_range(1..10).or_insert();
+        range.entry_range(22..255).or_insert();
+        assert_eq!(RangeSet(vec![0, 20, 22, 255]), range);
+        range.entry(21).or_insert();
+        assert!(range.is_total());
+
+        let mut range = RangeSet::<i8>(vec![-10, -5, 5, 10]);
+        assert!(range.entry_range(-4..=4).is_vacant());
+        assert!(range.entry_range(-5..=4).is_occupied());
+        assert!(range.entry_range(-4..=5).is_occupied());
+        assert!(range.entry_range(-20..=20).is_occupied());
+        assert!(range.entry_range(11..).is_vacant());
+        assert!(range.entry(-11).is_vacant());
+        assert!(range.entry(-10).is_occupied());
+
+        match range.entry_range(0..=20) {
+            Entry::Occupied(entry) => {
+                assert_eq!(5..=10, entry.range());
+                entry.remove();
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+        assert_eq!(RangeSet(vec![-10, -5]), range);
+
+        match range.entry(-7) {
+            Entry::Occupied(entry) => {
+                assert_eq!(-10..=-5, entry.range());
+                entry.remove();
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+        assert_eq!(RangeSet(vec![-10, -8, -6, -5]), range);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_take_first_and_last() -> Result<(), String> {
+        let empty = RangeSet::<i8>::empty();
+        assert!(empty.take_first(3).is_empty());
+        assert!(empty.take_last(3).is_empty());
+
+        let total = RangeSet::<i8>::total();
+        assert_eq!(RangeSet(vec![-128, -128]), total.take_first(1));
+        assert_eq!(RangeSet(vec![127, 127]), total.take_last(1));
+        assert_eq!(total, total.take_first(256));
+        assert_eq!(total, total.take_last(u128::MAX));
+        assert_eq!(RangeSet(vec![-128, 126]), total.take_first(255));
+
+        let range = RangeSet::<u128>(vec![0, 0, 5, u128::MAX]);
+        assert_eq!(RangeSet(vec![0, 0]), range.take_first(1));
+        assert_eq!(RangeSet(vec![0, 0, 5, 6]), range.take_first(3));
+        assert_eq!(range, range.take_first(u128::MAX));
+        assert_eq!(RangeSet(vec![5, u128::MAX]), range.take_last(u128::MAX - 4));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_first_n() -> Result<(), String> {
+        let mut range = RangeSet::<i8>(vec![-128, -127, 0, 10, 127, 127]);
+        assert!(range.pop_first_n(0).is_empty());
+        assert_eq!(RangeSet(vec![-128, -127]), range.pop_first_n(2));
+        assert_eq!(RangeSet(vec![0, 10, 127, 127]), range);
+        assert_eq!(RangeSet(vec![0, 4]), range.pop_first_n(5));
+        assert_eq!(RangeSet(vec![5, 10, 127, 127]), range);
+        assert_eq!(RangeSet(vec![5, 10, 127, 127]), range.pop_first_n(100));
+        assert!(range.is_empty());
+        assert!(range.pop_first_n(100).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_partition_around() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-128, -100, -5, 5, 10, 10, 120, 127]);
+        for min in i8::MIN..=i8::MAX {
+            for max in (min..=i8::MAX).step_by(7) {
+                let (below, inside, above) = range.partition_around(min..=max);
+                assert_eq!(range.difference_range(min..), below);
+                assert_eq!(range.intersection_range(min..=max), inside);
+                assert_eq!(
+                    range.intersection_range(max..).difference_range(max..=max),
+                    above
+                );
+            }
+        }
+
+        let (below, inside, above) = range.partition_around(0..0);
+        assert_eq!(RangeSet(vec![-128, -100, -5, -1]), below);
+        assert!(inside.is_empty());
+        assert_eq!(RangeSet(vec![0, 5, 10, 10, 120, 127]), above);
+        assert_eq!(
+            (RangeSet::empty(), range.clone(), RangeSet::empty()),
+            range.partition_around(..)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_below_and_above() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-128, -100, -5, 5, 10, 10, 120, 127]);
+        for value in i8::MIN..=i8::MAX {
+            let mut below = range.clone();
+            below.remove_below(value);
+            assert_eq!(range.intersection_range(value..), below);
+
+            let mut above = range.clone();
+            above.remove_above(value);
+            assert_eq!(range.intersection_range(..=value), above);
+        }
+
+        let mut range = RangeSet::<u8>::empty();
+        range.remove_below(5);
+        range.remove_above(5);
+        assert!(range.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_clear_and_truncate_ranges() -> Result<(), String> {
+        let mut range = RangeSet::<i8>(vec![-128, -100, -5, 5, 10, 10, 120, 127]);
+        range.truncate_ranges(usize::MAX);
+        assert_eq!(8, range.0.len());
+        range.truncate_ranges(3);
+        assert_eq!(RangeSet(vec![-128, -100, -5, 5, 10, 10]), range);
+        range.truncate_ranges(0);
+        assert!(range.is_empty());
+        assert_eq!(8, range.0.capacity());
+
+        let mut range = RangeSet::<u8>::total();
+        range.clear();
+        assert!(range.is_empty());
+        assert_eq!(2, range.0.capacity());
+        range.union_changed(&RangeSet(vec![3, 4]));
+        assert_eq!(RangeSet(vec![3, 4]), range);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_partition() -> Result<(), String> {
+        let (accepted, rejected) = RangeSet::<u8>::empty().partition(|_, _| true);
+        assert!(accepted.is_empty() && rejected.is_empty());
+
+        let range = RangeSet::<i16>(vec![-10, -5, 0, 0, 3, 9]);
+        let (negative, positive) = range.partition(|min, _| min < 0);
+        assert_eq!(RangeSet(vec![-10, -5]), negative);
+        assert_eq!(RangeSet(vec![0, 0, 3, 9]), positive);
+        assert_eq!(range, negative.union(&positive));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_chunks() -> Result<(), String> {
+        assert_eq!(0, RangeSet::<u8>::empty().chunks_of_ranges(1).count());
+        assert_eq!(0, RangeSet::<u8>::empty().chunks_of_values(1).count());
+
+        let total = RangeSet::<u8>::total();
+        assert_eq!(
+            vec![total.clone()],
+            total.chunks_of_ranges(1).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                RangeSet(vec![0, 99]),
+                RangeSet(vec![100, 199]),
+                RangeSet(vec![200, 255])
+            ],
+            total.chunks_of_values(100).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![total.clone()],
+            total.chunks_of_values(256).collect::<Vec<_>>()
+        );
+        assert_eq!(256, total.chunks_of_values(1).count());
+
+        let range = RangeSet::<i32>(vec![-10, -8, 0, 0, 3, 4, 20, 30]);
+        let chunks: Vec<_> = range.chunks_of_ranges(3).collect();
+        assert_eq!(
+            vec![RangeSet(vec![-10, -8, 0, 0, 3, 4]), RangeSet(vec![20, 30])],
+            chunks
+        );
+        let chunks: Vec<_> = range.chunks_of_values(4).collect();
+        assert_eq!(
+            vec![
+                RangeSet(vec![-10, -8, 0, 0]),
+                RangeSet(vec![3, 4, 20, 21]),
+                RangeSet(vec![22, 25]),
+                RangeSet(vec![26, 29]),
+                RangeSet(vec![30, 30]),
+            ],
+            chunks
+        );
+        let batches: Vec<Vec<i32>> = range.iter_chunks(5).collect();
+        assert_eq!(range.iter().collect::<Vec<_>>(), batches.concat());
+        assert!(batches
+            .iter()
+            .all(|batch| batch.len() == 5 || batch == &[29, 30]));
+        assert_eq!(0, RangeSet::<u8>::empty().iter_chunks(1).count());
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_ranges_overlapping() -> Result<(), String> {
+        assert_eq!(0, RangeSet::<u8>::empty().ranges_overlapping(..).count());
+
+        let range = RangeSet::<u8>(vec![0, 1, 5, 6, 10, 12, 20, 255]);
+        assert_eq!(4, range.ranges_overlapping(..).count());
+        assert_eq!(0, range.ranges_overlapping(7..=9).count());
+        assert_eq!(0, range.ranges_overlapping(9..7).count());
+        assert_eq!(
+            vec![0..=1],
+            range.ranges_overlapping(..=1).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![5..=6],
+            range.ranges_overlapping(2..=5).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![5..=6],
+            range.ranges_overlapping(6..=9).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![10..=12, 20..=255],
+            range.ranges_overlapping(12..=20).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![20..=255],
+            range.ranges_overlapping(255..).collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_containing_range() -> Result<(), String> {
+        assert_eq!(None, RangeSet::<u8>::empty().containing_range(0));
+        assert_eq!(
+            Some((0, 0..=255)),
+            RangeSet::<u8>::total().containing_range(255)
+        );
+
+        let range = RangeSet::<i8>(vec![-128, -100, 0, 0, 5, 10]);
+        assert_eq!(Some((0, -128..=-100)), range.containing_range(-128));
+        assert_eq!(Some((0, -128..=-100)), range.containing_range(-100));
+        assert_eq!(None, range.containing_range(-99));
+        assert_eq!(Some((1, 0..=0)), range.containing_range(0));
+        assert_eq!(Some((2, 5..=10)), range.containing_range(5));
+        assert_eq!(Some((2, 5..=10)), range.containing_range(7));
+        assert_eq!(None, range.containing_range(11));
+        assert_eq!(None, range.containing_range(127));
+
+        for value in i8::MIN..=i8::MAX {
+            let index = range.range_index_containing(value);
+            assert_eq!(range.containing_range(value).map(|(index, _)| index), index);
+            if let Some(index) = index {
+                assert!(range.get_range(index).unwrap().contains(&value));
+            }
+        }
+        assert_eq!(Some(-128..=-100), range.get_range(0));
+        assert_eq!(Some(5..=10), range.get_range(2));
+        assert_eq!(None, range.get_range(3));
+        assert_eq!(None, range.get_range(usize::MAX));
+        assert_eq!(None, RangeSet::<u8>::empty().get_range(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_distance_to() -> Result<(), String> {
+        assert_eq!(None, RangeSet::<u8>::empty().distance_to(0));
+        assert_eq!(Some(0), RangeSet::<u8>::total().distance_to(0));
+
+        let range = RangeSet::<i8>(vec![-128, -128, 127, 127]);
+        assert_eq!(Some(0), range.distance_to(-128));
+        assert_eq!(Some(127), range.distance_to(0));
+        assert_eq!(Some(127), range.distance_to(-1));
+        assert_eq!(Some(1), range.distance_to(126));
+
+        let range = RangeSet::<u64>(vec![10, 20]);
+        assert_eq!(Some(10), range.distance_to(0));
+        assert_eq!(Some(u64::MAX - 20), range.distance_to(u64::MAX));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_closest_value() -> Result<(), String> {
+        assert_eq!(
+            None,
+            RangeSet::<u8>::empty().closest_value(0, TieBreak::Lower)
+        );
+
+        let range = RangeSet::<i8>(vec![-128, -128, 127, 127]);
+        assert_eq!(Some(-128), range.closest_value(-1, TieBreak::Higher));
+        assert_eq!(Some(127), range.closest_value(0, TieBreak::Lower));
+        assert_eq!(Some(-128), range.closest_value(-128, TieBreak::Higher));
+
+        let range = RangeSet::<i64>(vec![0, 0, 4, 10]);
+        assert_eq!(Some(0), range.closest_value(2, TieBreak::Lower));
+        assert_eq!(Some(4), range.closest_value(2, TieBreak::Higher));
+        assert_eq!(Some(4), range.closest_value(3, TieBreak::Lower));
+        assert_eq!(Some(10), range.closest_value(i64::MAX, TieBreak::Lower));
+        assert_eq!(Some(0), range.closest_value(i64::MIN, TieBreak::Higher));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "strict-invariants")]
+    #[should_panic(
+        expected = "the input of `union_into` has the range 4..=8 at index 1 overlapping or adjacent to the previous one ending at 5"
+    )]
+    fn test_strict_invariants_overlapping() {
+        RangeSet::<u8>(vec![1, 5, 4, 8]).union(&RangeSet::empty());
+    }
+
+    #[test]
+    #[cfg(feature = "strict-invariants")]
+    #[should_panic(expected = "the input of `contains` has the inverted range 9..=3 at index 0")]
+    fn test_strict_invariants_inverted() {
+        RangeSet::<u8>(vec![9, 3]).contains(5);
+    }
+
+    #[test]
+    #[cfg(feature = "strict-invariants")]
+    #[should_panic(expected = "the input of `difference_into` has an odd number of bounds: 3")]
+    fn test_strict_invariants_odd() {
+        RangeSet::<u8>::total().difference(&RangeSet(vec![1, 2, 3]));
+    }
+
+    #[test]
+    #[cfg(feature = "strict-invariants")]
+    #[should_panic(expected = "the input of `to_signed_lossless` has an odd number of bounds: 1")]
+    fn test_strict_invariants_conversion() {
+        RangeSet::<u8>(vec![1]).to_signed_lossless::<i8>();
+    }
+
+    #[test]
+    #[cfg(feature = "icu")]
+    fn test_icu() -> Result<(), String> {
+        use icu_collections::codepointinvlist::CodePointInversionList;
+
+        let letters =
+            RangeSet::<u32>::new_from_iter(['A' as u32..='Z' as u32, 'a' as u32..='z' as u32]);
+        let list = CodePointInversionList::try_from(&letters).map_err(|e| format!("{e:?}"))?;
+        assert!(list.contains('q'));
+        assert!(!list.contains('['));
+        assert_eq!(52, list.size());
+        assert_eq!(letters, RangeSet::from(&list));
+
+        let all = RangeSet::<u32>::new_from_range(0..=0x10FFFF);
+        let list = CodePointInversionList::try_from(&all).map_err(|e| format!("{e:?}"))?;
+        assert_eq!(0x110000, list.size());
+        assert_eq!(all, RangeSet::from(&list));
+
+        assert!(CodePointInversionList::try_from(&RangeSet::<u32>::empty()).is_ok());
+        assert!(
+            CodePointInversionList::try_from(&RangeSet::<u32>(vec![0x110000, 0x110000])).is_err()
+        );
+        assert!(CodePointInversionList::try_from(&RangeSet::<u32>::total()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "bitvec", feature = "fixedbitset"))]
+    fn test_bitsets() -> Result<(), String> {
+        use bitvec::vec::BitVec;
+        use fixedbitset::FixedBitSet;
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(176);
+        for len in [0, 1, 63, 64, 65, 200, 1000] {
+            for density in [0.0, 0.1, 0.5, 0.9, 1.0] {
+                let values: Vec<usize> = (0..len).filter(|_| rng.gen_bool(density)).collect();
+                let range = RangeSet::new_from_iter(values.iter().map(|&value| value..=value));
+
+                let bits = BitVec::<u8>::from(&range);
+                assert_eq!(values, bits.iter_ones().collect::<Vec<_>>());
+                assert_eq!(range, RangeSet::from(bits.as_bitslice()));
+                let bits = BitVec::<usize>::from(&range);
+                assert_eq!(range, RangeSet::from(bits.as_bitslice()));
+
+                let bits = FixedBitSet::from(&range);
+                assert_eq!(values, bits.ones().collect::<Vec<_>>());
+                assert_eq!(range, RangeSet::from(&bits));
+            }
+        }
+
+        let mut bits = FixedBitSet::with_capacity(200);
+        bits.insert_range(..);
+        assert_eq!(RangeSet(vec![0, 199]), RangeSet::from(&bits));
+        let mut bits = FixedBitSet::with_capacity(128);
+        bits.insert_range(..);
+        assert_eq!(RangeSet(vec![0, 127]), RangeSet::from(&bits));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "range-set-blaze")]
+    fn test_range_set_blaze() -> Result<(), String> {
+        use range_set_blaze::RangeSetBlaze;
+
+        for range in [
+            RangeSet::<i8>::empty(),
+            RangeSet::<i8>::total(),
+            RangeSet::<i8>(vec![-128, -100, 0, 0, 10, 127]),
+        ] {
+            let blaze = RangeSetBlaze::from(&range);
+            assert_eq!(range.iter().count(), blaze.len());
+            assert_eq!(range, RangeSet::from(&blaze));
+        }
+
+        let blaze = RangeSetBlaze::from_iter([1u64..=5, 6..=9, 20..=30]);
+        assert_eq!(RangeSet(vec![1, 9, 20, 30]), RangeSet::from(&blaze));
+        let range = RangeSet::<u128>(vec![0, u128::MAX - 1]);
+        assert_eq!(range, RangeSet::from(&RangeSetBlaze::from(&range)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_radix() -> Result<(), String> {
+        let range = RangeSet::<u16>(vec![0, 9, 0x1000, 0x1fff, 0xffff, 0xffff]);
+        assert_eq!("[ 0..=9 1000..=1fff ffff..=ffff ]", format!("{range:x}"));
+        assert_eq!(
+            "[ 0x0..=0x9 0x1000..=0x1FFF 0xFFFF..=0xFFFF ]",
+            format!("{range:#X}")
+        );
+        assert_eq!(
+            "[ 0o0..=0o11 0o10000..=0o17777 0o177777..=0o177777 ]",
+            format!("{range:#o}")
+        );
+        assert_eq!(
+            "[ 0b0001..=0b1001 ]",
+            format!("{:#06b}", RangeSet::<u8>(vec![1, 9]))
+        );
+        assert_eq!("[ ]", format!("{:x}", RangeSet::<u8>::empty()));
+
+        for radix in [2, 8, 10, 16] {
+            for range in [
+                RangeSet::<i32>::empty(),
+                RangeSet::<i32>::total(),
+                RangeSet::<i32>(vec![-100, -50, 0, 0, 7, 1000]),
+            ] {
+                let formatted = match radix {
+                    2 => format!("{range:#b}"),
+                    8 => format!("{range:#o}"),
+                    16 => format!("{range:#x}"),
+                    _ => range.to_string(),
+                };
+                if radix == 10 || range.0.iter().all(|&bound| bound >= 0) {
+                    assert_eq!(
+                        Ok(range.clone()),
+                        RangeSet::from_str_radix(&formatted, radix)
+                    );
+                }
+            }
+        }
+
+        // Negative bounds written with a sign and their magnitude.
+        for (radix, prefix) in [(2, "0b"), (8, "0o"), (16, "0x")] {
+            for range in [
+                RangeSet::<i32>::total(),
+                RangeSet::<i32>(vec![-100, -50, 0, 0, 7, 1000]),
+                RangeSet::<i32>(vec![i32::MIN, i32::MIN, -1, 1]),
+            ] {
+                let write = |bound: i32| {
+                    let sign = if bound < 0 { "-" } else { "" };
+                    let magnitude = bound.unsigned_abs();
+                    match radix {
+                        2 => format!("{sign}{prefix}{magnitude:b}"),
+                        8 => format!("{sign}{prefix}{magnitude:o}"),
+                        _ => format!("{sign}{prefix}{magnitude:x}"),
+                    }
+                };
+                let formatted = range
+                    .ranges_overlapping(..)
+                    .map(|range| format!("{}..={}", write(*range.start()), write(*range.end())))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                assert_eq!(
+                    Ok(range.clone()),
+                    RangeSet::from_str_radix(&format!("[ {formatted} ]"), radix)
+                );
+            }
+        }
+
+        assert_eq!(
+            Ok(RangeSet(vec![-10, 5, 7, 7])),
+            "[ 7 -10..=3 0..=+5 ]".parse::<RangeSet<i64>>()
+        );
+        assert_eq!(Ok(RangeSet::<u8>::empty()), "[]".parse());
+        assert_eq!(
+            Ok(RangeSet::<u8>(vec![0xab, 0xcd])),
+            RangeSet::from_str_radix("0XAB..=cd", 16)
+        );
+        assert_eq!(
+            Err(ParseRangeSetError {
+                token: "3..=256".to_string(),
+                reason: "number too large to fit in target type".to_string()
+            }),
+            "[ 1..=2 3..=256 ]".parse::<RangeSet<u8>>()
+        );
+        assert_eq!(
+            "invalid range `5..=3`: the minimum 5 is greater than the maximum 3",
+            "5..=3".parse::<RangeSet<u8>>().unwrap_err().to_string()
+        );
+        assert!("1..=".parse::<RangeSet<u8>>().is_err());
+        assert!("1..2".parse::<RangeSet<u8>>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ascii_classes() -> Result<(), String> {
+        type Predicate = fn(&u8) -> bool;
+        let classes: [(RangeSet<u8>, Predicate); 11] = [
+            (RangeSet::ascii_digit(), u8::is_ascii_digit),
+            (RangeSet::ascii_hexdigit(), u8::is_ascii_hexdigit),
+            (RangeSet::ascii_uppercase(), u8::is_ascii_uppercase),
+            (RangeSet::ascii_lowercase(), u8::is_ascii_lowercase),
+            (RangeSet::ascii_alphabetic(), u8::is_ascii_alphabetic),
+            (RangeSet::ascii_alphanumeric(), u8::is_ascii_alphanumeric),
+            (RangeSet::ascii_whitespace(), u8::is_ascii_whitespace),
+            (RangeSet::ascii_punctuation(), u8::is_ascii_punctuation),
+            (RangeSet::ascii_graphic(), u8::is_ascii_graphic),
+            (RangeSet::ascii_control(), u8::is_ascii_control),
+            (RangeSet::ascii(), u8::is_ascii),
+        ];
+        for (class, predicate) in classes {
+            assert!(is_canonical(&class.0));
+            for value in 0..=255u8 {
+                assert_eq!(predicate(&value), class.contains(value), "{class} {value}");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_regex_class() -> Result<(), String> {
+        assert_eq!("[^\\x00-\\xFF]", RangeSet::<u8>::empty().to_regex_class());
+        assert_eq!("[\\x00-\\xFF]", RangeSet::<u8>::total().to_regex_class());
+        assert_eq!("[ab]", RangeSet::<u8>(vec![b'a', b'b']).to_regex_class());
+        assert_eq!(
+            "[\\x00-\\x1F\\x7F]",
+            RangeSet::<u8>::ascii_control().to_regex_class()
+        );
+        assert_eq!(
+            "[^\\u{0}-\\u{D7FF}\\u{E000}-\\u{10FFFF}]",
+            RangeSet::<u32>::empty().to_regex_class()
+        );
+        assert_eq!(
+            "[\\u{0}-\\u{D7FF}\\u{E000}-\\u{10FFFF}]",
+            RangeSet::<u32>::total().to_regex_class()
+        );
+        assert_eq!(
+            "[^\\u{0}-\\u{D7FF}\\u{E000}-\\u{10FFFF}]",
+            RangeSet::<u32>(vec![0xD800, 0xDFFF]).to_regex_class()
+        );
+        assert_eq!(
+            "[\\&\\~é]",
+            RangeSet::<u32>(vec![
+                '&' as u32,
+                '&' as u32,
+                '~' as u32,
+                '~' as u32,
+                'é' as u32,
+                'é' as u32
+            ])
+            .to_regex_class()
+            .replace("\\u{E9}", "é")
+        );
+
+        #[cfg(feature = "unicode")]
+        {
+            use rand::{rngs::StdRng, Rng, SeedableRng};
+            use regex_syntax::hir::{Class, HirKind};
+
+            let mut rng = StdRng::seed_from_u64(183);
+            for len in 1..20 {
+                let mut values: Vec<u8> = (0..len * 2).map(|_| rng.gen_range(0..=255)).collect();
+                values.sort();
+                let range = RangeSet::new_from_iter(values.chunks_exact(2).map(|r| r[0]..=r[1]));
+                for range in [range.clone(), range.complement()] {
+                    let hir = regex_syntax::ParserBuilder::new()
+                        .unicode(false)
+                        .utf8(false)
+                        .build()
+                        .parse(&range.to_regex_class())
+                        .map_err(|e| e.to_string())?;
+                    let HirKind::Class(Class::Bytes(class)) = hir.kind() else {
+                        return Err(format!("not a class: {hir:?}"));
+                    };
+                    let parsed =
+                        RangeSet::new_from_iter(class.ranges().iter().map(|r| r.start()..=r.end()));
+                    assert_eq!(range, parsed);
+                }
+
+                let mut values: Vec<u32> =
+                    (0..len * 2).map(|_| rng.gen_range(0..=0x10FFFF)).collect();
+                values.sort();
+                let range = RangeSet::new_from_iter(values.chunks_exact(2).map(|r| r[0]..=r[1]))
+                    .intersection(&RangeSet(vec![0, 0xD7FF, 0xE000, 0x10FFFF]));
+                let hir =
+                    regex_syntax::parse(&range.to_regex_class()).map_err(|e| e.to_string())?;
+                let HirKind::Class(Class::Unicode(class)) = hir.kind() else {
+                    return Err(format!("not a class: {hir:?}"));
+                };
+                let parsed = RangeSet::new_from_iter(
+                    class
+                        .ranges()
+                        .iter()
+                        .map(|r| u32::from(r.start())..=u32::from(r.end())),
+                );
+                assert_eq!(range, parsed);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_string() -> Result<(), String> {
+        for range in [
+            RangeSet::<i16>::empty(),
+            RangeSet::<i16>::total(),
+            RangeSet::<i16>(vec![-300, -300, -5, 5, 7, 8]),
+        ] {
+            assert_eq!(
+                Ok(range.clone()),
+                RangeSet::parse_compact(&range.to_compact_string())
+            );
+        }
+        assert_eq!("-32768-32767", RangeSet::<i16>::total().to_compact_string());
+        assert_eq!(
+            Ok(RangeSet(vec![1, 9])),
+            RangeSet::<u8>::parse_compact(" 5 - 9 ,1-4,,3 ")
+        );
+
+        let error = RangeSet::<u8>::parse_compact("1-5,9-x").unwrap_err();
+        assert_eq!("9-x", error.token);
+        assert!(RangeSet::<u8>::parse_compact("-1").is_err());
+        assert!(RangeSet::<u8>::parse_compact("1-256").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_largest_and_smallest_range() -> Result<(), String> {
+        assert_eq!(None, RangeSet::<i8>::empty().smallest_range());
+        assert_eq!(
+            Some((0, -128..=127)),
+            RangeSet::<i8>::total().largest_range()
+        );
+
+        let range = RangeSet::<i8>(vec![-128, -100, -5, 5, 10, 10, 20, 48, 100, 100]);
+        assert_eq!(Some((0, -128..=-100)), range.largest_range());
+        assert_eq!(Some((2, 10..=10)), range.smallest_range());
+        assert_eq!(
+            range.describe().largest_range,
+            range.largest_range().map(|(_, range)| range)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_length_histogram() -> Result<(), String> {
+        assert_eq!(
+            vec![0, 0],
+            RangeSet::<u8>::empty().range_length_histogram(&[10u8])
+        );
+        assert_eq!(
+            vec![1],
+            RangeSet::<u8>::total().range_length_histogram::<u8>(&[])
+        );
+        assert_eq!(
+            vec![0, 1],
+            RangeSet::<u8>::total().range_length_histogram(&[255u8])
+        );
+        assert_eq!(
+            vec![1, 0],
+            RangeSet::<u8>::total().range_length_histogram(&[256u16])
+        );
+
+        let range = RangeSet::<i8>(vec![-128, -100, -5, 5, 10, 10, 20, 30, 100, 100]);
+        assert_eq!(
+            vec![2, 0, 2, 1],
+            range.range_length_histogram(&[1u32, 10, 11])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_for_each_range() -> Result<(), String> {
+        assert_eq!(
+            None::<()>,
+            RangeSet::<u8>::empty().try_for_each_range(|_, _| ControlFlow::Break(()))
+        );
+
+        let range = RangeSet::<i8>(vec![-128, -100, -5, 5, 10, 10]);
+        let mut visited = vec![];
+        let result = range.try_for_each_range(|min, max| {
+            visited.push((min, max));
+            if min > 0 {
+                ControlFlow::Break(max)
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(Some(10), result);
+        assert_eq!(vec![(-128, -100), (-5, 5), (10, 10)], visited);
+
+        let mut count = 0;
+        let result = range.try_for_each_range(|min, _| {
+            count += 1;
+            if min == -128 {
+                ControlFlow::Break("first")
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(Some("first"), result);
+        assert_eq!(1, count);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_describe() -> Result<(), String> {
+        let stats = RangeSet::<i8>::empty().describe();
+        assert_eq!(0, stats.ranges);
+        assert_eq!(0, stats.cardinality);
+        assert_eq!(None, stats.min);
+        assert_eq!(None, stats.largest_range);
+        assert_eq!(None, stats.largest_gap);
+        assert_eq!(0.0, stats.density);
+
+        let stats = RangeSet::<i8>::total().describe();
+        assert_eq!(1, stats.ranges);
+        assert_eq!(256, stats.cardinality);
+        assert_eq!(Some(-128..=127), stats.largest_range);
+        assert_eq!(None, stats.largest_gap);
+        assert_eq!(1.0, stats.density);
+
+        let stats = RangeSet::<i8>(vec![-128, -100, 0, 0, 10, 38, 127, 127]).describe();
+        assert_eq!(4, stats.ranges);
+        assert_eq!(29 + 1 + 29 + 1, stats.cardinality);
+        assert_eq!(Some(-128), stats.min);
+        assert_eq!(Some(127), stats.max);
+        assert_eq!(Some(-128..=-100), stats.largest_range);
+        assert_eq!(Some(-99..=-1), stats.largest_gap);
+        assert_eq!(60.0 / 256.0, stats.density);
+
+        assert_eq!(u128::MAX, RangeSet::<u128>::total().describe().cardinality);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_covered_len() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-128, -100, 0, 0, 10, 127]);
+        assert_eq!(Some(29u8 + 1 + 118), range.covered_len());
+        assert_eq!(Some(0u8), RangeSet::<i8>::empty().covered_len());
+        assert_eq!(None::<u8>, RangeSet::<i8>::total().covered_len());
+        assert_eq!(Some(256u16), RangeSet::<i8>::total().covered_len());
+        assert_eq!(None::<u128>, RangeSet::<u128>::total().covered_len());
+        assert_eq!(
+            Some(u128::MAX),
+            RangeSet::<u128>(vec![1, u128::MAX]).covered_len()
+        );
+
+        /// Seconds covered by a set of timestamps in milliseconds.
+        #[derive(PartialEq, Debug)]
+        struct Seconds(f64);
+
+        impl Measure<i64> for Seconds {
+            fn zero() -> Self {
+                Seconds(0.0)
+            }
+
+            fn measure(min: i64, max: i64) -> Option<Self> {
+                Some(Seconds((max - min + 1) as f64 / 1000.0))
+            }
+
+            fn checked_sum(self, other: Self) -> Option<Self> {
+                Some(Seconds(self.0 + other.0))
+            }
+        }
+
+        let range = RangeSet::<i64>(vec![0, 499, 1000, 1999]);
+        assert_eq!(Some(Seconds(1.5)), range.covered_len());
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_count_in() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-128, -100, 0, 0, 10, 127]);
+        assert_eq!(29 + 1 + 118, range.count_in(..));
+        assert_eq!(1, range.count_in(-99..=5));
+        assert_eq!(2, range.count_in(-100..=9));
+        assert_eq!(0, range.count_in(1..10));
+        assert_eq!(0, range.count_in(20..10));
+        assert_eq!(0, RangeSet::<i8>::empty().count_in(..));
+
+        assert_eq!(256, RangeSet::<u8>::total().count_in(..));
+        assert_eq!(1 << 64, RangeSet::<u64>::total().count_in(..));
+        assert_eq!(u128::MAX, RangeSet::<u128>::total().count_in(..));
+        assert_eq!(u128::MAX, RangeSet::<u128>::total().count_in(1..));
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_sum_in() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-128, -100, 0, 0, 10, 127]);
+        assert_eq!(Some(range.iter().map(i128::from).sum()), range.sum_in(..));
+        assert_eq!(Some(-101 - 100), range.sum_in(-101..=5));
+        assert_eq!(Some(0), range.sum_in(-1..10));
+        assert_eq!(Some(0), range.sum_in(20..10));
+        assert_eq!(Some(0), RangeSet::<i8>::empty().sum_in(..));
+
+        let range = RangeSet::<u64>::total();
+        assert_eq!(Some(i128::from(u64::MAX) * (1 << 63)), range.sum_in(..));
+        assert_eq!(Some(i128::MIN), RangeSet::<i128>::total().sum_in(..));
+        assert_eq!(Some(0), RangeSet::<i128>::total().sum_in(-i128::MAX..));
+        assert_eq!(None, RangeSet::<i128>::total().sum_in(1..));
+        assert_eq!(
+            None,
+            RangeSet::<u128>::new_from_range(u128::MAX..).sum_in(..)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_gaps_within() -> Result<(), String> {
+        let range = RangeSet::<u8>(vec![0, 1, 5, 6, 10, 12, 20, 255]);
+        assert_eq!(
+            vec![2..=4, 7..=9, 13..=19],
+            range.gaps_within(..).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![8..=9], range.gaps_within(8..=11).collect::<Vec<_>>());
+        assert_eq!(0, range.gaps_within(20..).count());
+        assert_eq!(0, range.gaps_within(5..=6).count());
+        assert_eq!(0, range.gaps_within(9..3).count());
+
+        assert_eq!(
+            vec![0..=255],
+            RangeSet::<u8>::empty().gaps_within(..).collect::<Vec<_>>()
+        );
+        assert_eq!(0, RangeSet::<u8>::total().gaps_within(..).count());
+
+        let range = RangeSet::<i8>(vec![-128, -100, 100, 127]);
+        assert_eq!(
+            range
+                .complement()
+                .ranges_overlapping(..)
+                .collect::<Vec<_>>(),
+            range.gaps_within(..).collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_set_operations_with_range() -> Result<(), String> {
+        let sets = [
+            RangeSet::<u8>::empty(),
+            RangeSet::<u8>::total(),
+            RangeSet::<u8>(vec![0, 0, 255, 255]),
+            RangeSet::<u8>(vec![2, 4, 10, 20, 22, 22, 30, 254]),
+        ];
+        for set in &sets {
+            for min in 0..=255 {
+                for max in 0..=255 {
+                    let range = RangeSet::new_from_range(min..=max);
+                    assert_eq!(set.union(&range), set.union_range(min..=max));
+                    assert_eq!(set.intersection(&range), set.intersection_range(min..=max));
+                    assert_eq!(set.difference(&range), set.difference_range(min..=max));
+                }
+            }
+            assert_eq!(set, &set.union_range(9..3));
+            assert!(set.intersection_range(9..3).is_empty());
+            assert_eq!(set, &set.difference_range(9..3));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_new_from_iter() -> Result<(), String> {
+        assert!(RangeSet::<u8>::new_from_iter(Vec::<std::ops::Range<u8>>::new()).is_empty());
+        assert!(RangeSet::<u8>::new_from_iter([9..3, 20..10]).is_empty());
+        assert!(RangeSet::<u8>::new_from_iter([200..=255, 0..=199]).is_total());
+
+        assert_eq!(
+            RangeSet(vec![3, 14]),
+            RangeSet::<i32>::new_from_iter([9..15, 3..6, 6..9])
+        );
+        assert_eq!(
+            RangeSet(vec![-5, 0, 250, 255]),
+            RangeSet::<i32>::new_from_iter([250..=255, -5..=-1, -1..=0, 251..=252])
+        );
+        assert_eq!(
+            RangeSet(vec![3, 255]),
+            RangeSet::<u8>::new_from_iter([
+                (Bound::Excluded(2), Bound::Unbounded),
+                (Bound::Included(200), Bound::Included(255))
+            ])
+        );
+
+        let values = [7u64, 1, 2, 3, 9, 7, 10];
+        assert_eq!(
+            RangeSet(vec![1, 3, 7, 7, 9, 10]),
+            RangeSet::new_from_iter(values.iter().map(|&value| value..=value))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_from_values() -> Result<(), String> {
+        assert_eq!(RangeSet(vec![-3, -3]), RangeSet::<i8>::singleton(-3));
+        assert!(RangeSet::<u8>::new_from_values(&[]).is_empty());
+        assert!(RangeSet::<u8>::new_from_values(&(0..=255).rev().collect::<Vec<_>>()).is_total());
+        assert_eq!(
+            RangeSet(vec![0, 0, 254, 255]),
+            RangeSet::<u8>::new_from_values(&[255, 0, 254, 255, 0])
+        );
+
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(202);
+        for _ in 0..100 {
+            let values: Vec<i8> = (0..rng.gen_range(0..50)).map(|_| rng.gen()).collect();
+            assert_eq!(
+                RangeSet::new_from_iter(values.iter().map(|&value| value..=value)),
+                RangeSet::new_from_values(&values)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_union_disjoint() -> Result<(), String> {
+        assert_eq!(
+            Ok(RangeSet::total()),
+            RangeSet::<u8>::empty().try_union_disjoint(&RangeSet::total())
+        );
+        assert_eq!(
+            Err(OverlapError { min: 0, max: 255 }),
+            RangeSet::<u8>::total().try_union_disjoint(&RangeSet::total())
+        );
+
+        let range = RangeSet::<i32>(vec![-10, -5, 0, 4, 10, 20]);
+        assert_eq!(
+            Ok(RangeSet(vec![-10, -5, -3, -2, 0, 20])),
+            range.try_union_disjoint(&RangeSet(vec![-3, -2, 5, 9]))
+        );
+        assert_eq!(
+            Err(OverlapError { min: 4, max: 4 }),
+            range.try_union_disjoint(&RangeSet(vec![-3, -2, 4, 9, 15, 30]))
+        );
+        assert_eq!(
+            Err(OverlapError { min: 12, max: 13 }),
+            range.try_union_disjoint(&RangeSet(vec![12, 13]))
+        );
+        assert_eq!(
+            "the values 12..=13 are contained in both sets",
+            OverlapError { min: 12, max: 13 }.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_changed() -> Result<(), String> {
+        let mut range = RangeSet::<u8>::empty();
+        assert!(!range.union_changed(&RangeSet::empty()));
+        assert!(!range.intersect_changed(&RangeSet::total()));
+        assert!(range.union_changed(&RangeSet(vec![0, 4, 250, 255])));
+        assert!(!range.union_changed(&RangeSet(vec![0, 0, 252, 255])));
+        assert!(range.union_changed(&RangeSet(vec![5, 5])));
+        assert_eq!(RangeSet(vec![0, 5, 250, 255]), range);
+
+        assert!(!range.intersect_changed(&RangeSet(vec![0, 10, 200, 255])));
+        assert!(range.intersect_changed(&RangeSet(vec![3, 251])));
+        assert_eq!(RangeSet(vec![3, 5, 250, 251]), range);
+        assert!(range.intersect_changed(&RangeSet::empty()));
+        assert!(!range.intersect_changed(&RangeSet::empty()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_advance_to() -> Result<(), String> {
+        let range = RangeSet::<u8>(vec![0, 3, 10, 12, 20, 20, 250, 255]);
+        for start in 0..=255 {
+            for target in 0..=255 {
+                let mut iter = range.iter();
+                iter.advance_to(start);
+                iter.advance_to(target);
+                let expected: Vec<u8> = range
+                    .iter()
+                    .filter(|&value| value >= start && value >= target)
+                    .collect();
+                assert_eq!(expected, iter.collect::<Vec<_>>());
+            }
+        }
+
+        let mut iter = range.iter();
+        assert_eq!(Some(0), iter.next());
+        iter.advance_to(1);
+        assert_eq!(Some(1), iter.next());
+        iter.advance_to(255);
+        assert_eq!(Some(255), iter.next());
+        iter.advance_to(255);
+        assert_eq!(None, iter.next());
+
+        let empty = RangeSet::<u8>::empty();
+        let mut iter = empty.iter();
+        iter.advance_to(5);
+        assert_eq!(None, iter.next());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_into() -> Result<(), String> {
+        let sets = [
+            RangeSet::<u8>::empty(),
+            RangeSet::<u8>::total(),
+            RangeSet::<u8>(vec![0, 0, 255, 255]),
+            RangeSet::<u8>(vec![2, 4, 10, 20, 22, 22, 30, 254]),
+            RangeSet::<u8>(vec![0, 3, 5, 10, 21, 21, 23, 40]),
+            RangeSet::<u8>(vec![4, 4, 11, 11, 19, 29, 100, 255]),
+        ];
+
+        let mut out = RangeSet(vec![1, 2, 3, 4, 5, 6]);
+        for set1 in &sets {
+            set1.complement_into(&mut out);
+            assert_eq!(set1.complement(), out);
+            assert_eq!(
+                set1.complement().0,
+                RangeSet::new_from_iter(
+                    (0..=255u8)
+                        .filter(|&value| !set1.contains(value))
+                        .map(|value| value..=value)
+                )
+                .0
+            );
+
+            for set2 in &sets {
+                let values = |f: &dyn Fn(u8) -> bool| {
+                    RangeSet::new_from_iter((0..=255u8).filter(|&v| f(v)).map(|v| v..=v))
+                };
+
+                set1.union_into(set2, &mut out);
+                assert_eq!(values(&|v| set1.contains(v) || set2.contains(v)), out);
+                assert_eq!(set1.union(set2), out);
 
-    use super::*;
+                set1.intersection_into(set2, &mut out);
+                assert_eq!(values(&|v| set1.contains(v) && set2.contains(v)), out);
+                assert_eq!(set1.intersection(set2), out);
 
-    macro_rules! test_empty_and_total_for_types {
-        ($($t:ty),*) => {
-            $(
-                let empty = RangeSet::<$t>::empty();
-                assert!(empty.is_empty());
-                assert!(!empty.is_total());
+                set1.difference_into(set2, &mut out);
+                assert_eq!(values(&|v| set1.contains(v) && !set2.contains(v)), out);
+                assert_eq!(set1.difference(set2), out);
+            }
+        }
 
-                let total = RangeSet::<$t>::total();
-                assert!(!total.is_empty());
-                assert!(total.is_total());
-            )*
-        };
+        Ok(())
     }
 
     #[test]
-    fn test_empty_and_total() -> Result<(), String> {
-        test_empty_and_total_for_types!(
-            u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+    fn test_filter_blocks() -> Result<(), String> {
+        let range = RangeSet::<u8>(vec![0, 3, 10, 12, 20, 20, 250, 255]);
+        let mut blocks = vec![];
+        for min in (0..=255u8).step_by(3) {
+            for max in [min, min.saturating_add(1), min.saturating_add(7)] {
+                blocks.push((min, max));
+            }
+        }
+        blocks.push((7, 3));
+        let expected: Vec<bool> = blocks
+            .iter()
+            .map(|&(min, max)| range.has_intersection(&RangeSet::new_from_range(min..=max)))
+            .collect();
+        assert_eq!(expected, range.filter_blocks(blocks.iter().copied()));
+        assert_eq!(
+            expected.iter().rev().copied().collect::<Vec<_>>(),
+            range.filter_blocks(blocks.iter().rev().copied())
+        );
+
+        assert_eq!(
+            vec![false],
+            RangeSet::<u8>::empty().filter_blocks([(0, 255)].into_iter())
         );
 
         Ok(())
     }
 
     #[test]
-    fn test_new_from_range() -> Result<(), String> {
+    fn test_simplified() -> Result<(), String> {
+        assert!(RangeSet::<u8>::empty().simplified(0).is_empty());
+        assert!(RangeSet::<u8>::total().simplified(1).is_total());
+
+        let range = RangeSet::<i8>(vec![-128, -100, -90, -90, -80, 0, 5, 5, 10, 127]);
+        assert_eq!(range, range.simplified(5));
+        assert_eq!(range, range.simplified(usize::MAX));
         assert_eq!(
-            RangeSet::<u8>(vec![3, 4]),
-            RangeSet::<u8>::new_from_range(3..5)
+            RangeSet(vec![-128, -100, -90, -90, -80, 127]),
+            range.simplified(3)
         );
+        assert_eq!(RangeSet(vec![-128, -90, -80, 127]), range.simplified(2));
+        assert_eq!(RangeSet(vec![-128, 127]), range.simplified(1));
+
+        for max_ranges in 1..6 {
+            let simplified = range.simplified(max_ranges);
+            assert!(simplified.contains_all(&range));
+            assert_eq!(std::cmp::min(max_ranges, 5), simplified.0.len() / 2);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_simplified_zero() {
+        RangeSet::<u8>(vec![1, 2]).simplified(0);
+    }
 
+    #[test]
+    fn test_aligned_blocks() -> Result<(), String> {
+        assert_eq!(0, RangeSet::<u8>::empty().aligned_blocks().count());
         assert_eq!(
-            RangeSet::<u8>(vec![3, 5]),
-            RangeSet::<u8>::new_from_range(3..=5)
+            vec![(0, 8)],
+            RangeSet::<u8>::total().aligned_blocks().collect::<Vec<_>>()
         );
-
         assert_eq!(
-            RangeSet::<u8>(vec![0, 5]),
-            RangeSet::<u8>::new_from_range(..=5)
+            vec![(i128::MIN, 128)],
+            RangeSet::<i128>::total()
+                .aligned_blocks()
+                .collect::<Vec<_>>()
         );
-
         assert_eq!(
-            RangeSet::<u8>(vec![2, 255]),
-            RangeSet::<u8>::new_from_range(2..)
+            vec![
+                (-128, 7),
+                (0, 6),
+                (64, 5),
+                (96, 4),
+                (112, 3),
+                (120, 2),
+                (124, 1),
+                (126, 0)
+            ],
+            RangeSet::<i8>(vec![-128, 126])
+                .aligned_blocks()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(-8, 3), (0, 2), (4, 0), (255, 0)],
+            RangeSet::<i16>(vec![-8, 4, 255, 255])
+                .aligned_blocks()
+                .collect::<Vec<_>>()
         );
 
+        let range = RangeSet::<u8>(vec![1, 6, 9, 9, 13, 200, 250, 255]);
+        let blocks: Vec<(u8, u32)> = range.aligned_blocks().collect();
+        for &(start, k) in &blocks {
+            assert_eq!(0, u32::from(start) % (1 << k));
+        }
         assert_eq!(
-            RangeSet::<u8>(vec![0, 255]),
-            RangeSet::<u8>::new_from_range(..)
+            range,
+            RangeSet::new_from_iter(
+                blocks
+                    .iter()
+                    .map(|&(start, k)| start..=start + ((1u16 << k) - 1) as u8)
+            )
         );
 
         Ok(())
     }
 
     #[test]
-    fn test_new_from_ranges() -> Result<(), String> {
+    fn test_align() -> Result<(), String> {
+        let range = RangeSet::<u8>(vec![0, 3, 5, 6, 12, 12, 17, 255]);
+        assert_eq!(RangeSet(vec![0, 7, 12, 255]), range.align_outward(4));
+        assert_eq!(RangeSet(vec![0, 3, 20, 255]), range.align_inward(4));
+        assert_eq!(range, range.align_outward(1));
+        assert_eq!(range, range.align_inward(1));
+
+        let range = RangeSet::<i8>(vec![-127, -120, -5, 5, 126, 126]);
         assert_eq!(
-            RangeSet(vec![3, 5, 9, 14]),
-            RangeSet::new_from_ranges(&[AnyRange::from(3..=5), AnyRange::from(9..15)])
+            RangeSet(vec![-128, -118, -6, 5, 126, 127]),
+            range.align_outward(3)
         );
+        assert_eq!(RangeSet(vec![-126, -121, -3, 5]), range.align_inward(3));
+        assert_eq!(RangeSet(vec![-127, 126]), range.align_outward(127));
+        assert!(range.align_inward(127).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_align_zero() {
+        RangeSet::<u8>(vec![1, 2]).align_outward(0);
+    }
 
+    #[test]
+    fn test_filter_residue() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-128, -100, -5, 5, 120, 127]);
+        for m in 1..=10 {
+            for r in -10..=10i8 {
+                let expected = RangeSet::new_from_iter(
+                    range
+                        .iter()
+                        .filter(|value| value.rem_euclid(m) == r.rem_euclid(m))
+                        .map(|value| value..=value),
+                );
+                assert_eq!(expected, range.filter_residue(m, r));
+            }
+        }
         assert_eq!(
-            RangeSet(vec![3, 5, 9, 14]),
-            RangeSet::new_from_ranges(&[AnyRange::from(9..15), AnyRange::from(3..=5)])
+            RangeSet(vec![-127, -127, 0, 0, 127, 127]),
+            range.filter_residue(127, 0)
         );
+        assert!(RangeSet::<u8>::empty().filter_residue(2, 0).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lossless_sign_conversion() -> Result<(), String> {
+        assert!(RangeSet::<i32>::empty().to_unsigned_lossless().is_empty());
+        assert!(RangeSet::<i64>::total().to_unsigned_lossless().is_total());
+        assert!(RangeSet::<u128>::total()
+            .to_signed_lossless::<i128>()
+            .is_total());
 
+        let range = RangeSet::<i8>(vec![-128, -100, -5, 5, 10, 10, 120, 127]);
+        let unsigned = range.to_unsigned_lossless();
+        for value in i8::MIN..=i8::MAX {
+            assert_eq!(
+                range.contains(value),
+                unsigned.contains((value as u8) ^ 0x80)
+            );
+        }
+        assert_eq!(range, unsigned.to_signed_lossless());
+
+        let range = RangeSet::<u32>(vec![0, 9, 1 << 31, u32::MAX]);
+        assert_eq!(range, range.to_unsigned_lossless());
+        assert_eq!(range, range.to_signed_lossless::<u32>());
         assert_eq!(
-            RangeSet(vec![3, 5, 9, 14]),
-            RangeSet::new_from_ranges(&[
-                AnyRange::from(9..15),
-                AnyRange::from(23..18),
-                AnyRange::from(3..=5),
-                AnyRange::from(4..=4)
-            ])
+            RangeSet::<i32>(vec![i32::MIN, i32::MIN + 9, 0, i32::MAX]),
+            range.to_signed_lossless()
         );
 
+        Ok(())
+    }
+
+    #[test]
+    fn test_quantize() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-128, -100, -5, 5, 120, 127]);
+        for bucket in 1..=127 {
+            let quantized = range.quantize(bucket);
+            let expected = RangeSet::new_from_iter(
+                range
+                    .iter()
+                    .map(|value| value.div_euclid(bucket))
+                    .map(|index| index..=index),
+            );
+            assert_eq!(expected, quantized);
+
+            let dequantized = quantized.dequantize(bucket);
+            let expected = RangeSet::new_from_iter(
+                (-128..=127i8)
+                    .filter(|value| quantized.contains(value.div_euclid(bucket)))
+                    .map(|value| value..=value),
+            );
+            assert_eq!(expected, dequantized);
+            assert!(dequantized.contains_all(&range));
+        }
+
+        assert_eq!(range, range.quantize(1));
+        assert_eq!(range, range.dequantize(1));
         assert_eq!(
-            RangeSet(vec![3, 5, 9, 14]),
-            RangeSet::new_from_ranges(&[
-                AnyRange::from(9..15),
-                AnyRange::from(23..18),
-                AnyRange::from(3..=5)
-            ])
+            RangeSet(vec![-128, -121, 120, 127]),
+            RangeSet::<i8>(vec![-100, -13, 12, 100]).dequantize(10)
         );
 
+        Ok(())
+    }
+
+    #[test]
+    fn test_project_mod() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-128, -120, -5, 5, 100, 100, 120, 127]);
+        for m in 1..=127 {
+            let expected = RangeSet::new_from_iter(
+                range
+                    .iter()
+                    .map(|value| value.rem_euclid(m))
+                    .map(|residue| residue..=residue),
+            );
+            assert_eq!(expected, range.project_mod(m));
+        }
+
+        assert!(RangeSet::<u8>::empty().project_mod(7).is_empty());
         assert_eq!(
-            RangeSet(vec![3, 255]),
-            RangeSet::<u8>::new_from_ranges(&[AnyRange::from(4..), AnyRange::from(3..=5)])
+            RangeSet(vec![0, 254]),
+            RangeSet::<u8>::total().project_mod(255)
         );
-
         assert_eq!(
-            RangeSet(vec![0, 5]),
-            RangeSet::<u8>::new_from_ranges(&[AnyRange::from(..=4), AnyRange::from(3..=5)])
+            RangeSet(vec![0, u128::MAX - 1]),
+            RangeSet::<u128>::total().project_mod(u128::MAX)
         );
 
+        Ok(())
+    }
+
+    #[test]
+    fn test_arith() -> Result<(), String> {
+        fn apply(
+            x: &RangeSet<i8>,
+            y: &RangeSet<i8>,
+            overflow: Overflow,
+            f: fn(i8, i8) -> (i8, bool),
+            saturated: fn(i8, i8) -> i8,
+        ) -> RangeSet<i8> {
+            let mut values = vec![];
+            for a in x.iter() {
+                for b in y.iter() {
+                    let (wrapped, overflowed) = f(a, b);
+                    match overflow {
+                        Overflow::Saturate => values.push(saturated(a, b)),
+                        Overflow::Wrap => values.push(wrapped),
+                        Overflow::Discard if !overflowed => values.push(wrapped),
+                        Overflow::Discard => {}
+                    }
+                }
+            }
+            RangeSet::new_from_iter(values.into_iter().map(|value| value..=value))
+        }
+
+        let sets = [
+            RangeSet::<i8>::empty(),
+            RangeSet::<i8>(vec![0, 0]),
+            RangeSet::<i8>(vec![-128, -120, -5, 5, 100, 100, 120, 127]),
+            RangeSet::<i8>(vec![-1, 3, 60, 70]),
+            RangeSet::<i8>(vec![-100, -90]),
+        ];
+        for x in &sets {
+            for y in &sets {
+                for overflow in [Overflow::Saturate, Overflow::Wrap, Overflow::Discard] {
+                    assert_eq!(
+                        apply(x, y, overflow, i8::overflowing_add, i8::saturating_add),
+                        x.arith_add(y, overflow)
+                    );
+                    assert_eq!(
+                        apply(x, y, overflow, i8::overflowing_sub, i8::saturating_sub),
+                        x.arith_sub(y, overflow)
+                    );
+                    let products = apply(x, y, overflow, i8::overflowing_mul, i8::saturating_mul);
+                    let product = x.arith_mul(y, overflow);
+                    assert!(product.contains_all(&products));
+                    if overflow == Overflow::Saturate {
+                        assert_eq!(products.0.first(), product.0.first());
+                        assert_eq!(products.0.last(), product.0.last());
+                    }
+                }
+            }
+        }
+
         assert_eq!(
             RangeSet::total(),
-            RangeSet::new_from_ranges(&[AnyRange::from(..), AnyRange::from(3..=5)])
+            RangeSet::<u8>::total().arith_add(&RangeSet(vec![1, 1]), Overflow::Wrap)
         );
-
         assert_eq!(
-            RangeSet::empty(),
-            RangeSet::new_from_ranges(&[AnyRange::from(3..1), AnyRange::from(10..=5)])
+            RangeSet(vec![0, 255]),
+            RangeSet::<u8>(vec![0, 128]).arith_add(&RangeSet(vec![0, 127]), Overflow::Wrap)
+        );
+        assert_eq!(
+            RangeSet::total(),
+            RangeSet::<u128>::total().arith_add(&RangeSet::total(), Overflow::Wrap)
         );
 
         Ok(())
     }
 
     #[test]
-    fn test_contains_all() -> Result<(), String> {
-        assert!(RangeSet::<u8>::empty().contains_all(&RangeSet::empty()));
-        assert!(RangeSet::<u8>::total().contains_all(&RangeSet::empty()));
-        assert!(!RangeSet::<u8>::empty().contains_all(&RangeSet::total()));
+    fn test_invert() -> Result<(), String> {
+        let mut range = RangeSet::<i8>::empty();
+        range.invert();
+        assert!(range.is_total());
+        range.invert();
+        assert!(range.is_empty());
 
-        assert!(!RangeSet(vec![2, 44]).contains_all(&RangeSet(vec![56, 60])));
-        assert!(!RangeSet(vec![56, 60]).contains_all(&RangeSet(vec![2, 44])));
-        assert!(RangeSet(vec![56, 60]).contains_all(&RangeSet(vec![56, 60])));
-        assert!(RangeSet(vec![56, 61]).contains_all(&RangeSet(vec![56, 61])));
-        assert!(RangeSet(vec![55, 61]).contains_all(&RangeSet(vec![56, 60])));
+        for bounds in [
+            vec![-128, -128],
+            vec![127, 127],
+            vec![-128, 0, 127, 127],
+            vec![-5, 5],
+            vec![-128, -100, -5, 5, 10, 10],
+            vec![-100, -5, 5, 10, 120, 127],
+        ] {
+            let range = RangeSet::<i8>(bounds);
+            let mut inverted = range.clone();
+            inverted.invert();
+            assert_eq!(range.complement(), inverted);
+        }
 
-        assert!(RangeSet(vec![56, 60]).contains_all(&RangeSet(vec![57, 59])));
-        assert!(RangeSet(vec![56, 60]).contains_all(&RangeSet(vec![57, 60])));
-        assert!(RangeSet(vec![56, 60]).contains_all(&RangeSet(vec![56, 59])));
+        let mut range = RangeSet::<u8>(Vec::with_capacity(8));
+        range.0.extend_from_slice(&[0, 9, 20, 29]);
+        range.invert();
+        assert_eq!(8, range.0.capacity());
 
-        assert!(RangeSet(vec![19, 33]).contains_all(&RangeSet(vec![20, 21, 30, 32])));
-        assert!(
-            RangeSet(vec![19, 33, 53, 70]).contains_all(&RangeSet(vec![20, 21, 30, 32, 66, 69]))
-        );
-        assert!(
-            !RangeSet(vec![19, 33, 53, 70]).contains_all(&RangeSet(vec![20, 21, 30, 32, 66, 71]))
-        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_allocation_policy() -> Result<(), String> {
+        let range1 = RangeSet::<u32>::new_from_iter((0..10).map(|i| i * 10..i * 10 + 5));
+        let range2 = RangeSet::<u32>(vec![0, 1000]);
+
+        let union = range1.union_with_policy(&range2, AllocationPolicy::KeepCapacity);
+        assert_eq!(range2, union);
+        assert!(union.0.capacity() >= union.0.len());
+        let union = range1.union_with_policy(&range2, AllocationPolicy::ShrinkToFit);
+        assert_eq!(union.0.len(), union.0.capacity());
+        let union = range1.union(&range2);
+        assert!(union.0.capacity() <= 2 * union.0.len());
+
+        let intersection = range1.intersection(&range2);
+        assert_eq!(range1, intersection);
+        assert!(intersection.0.capacity() <= 2 * intersection.0.len());
+        let difference = range1.difference_with_policy(&range2, AllocationPolicy::Balanced);
+        assert!(difference.is_empty());
+        assert!(difference.0.capacity() <= 2 * difference.0.len());
+        let complement = range1.complement_with_policy(AllocationPolicy::KeepCapacity);
+        assert_eq!(range1, complement.complement());
+        assert!(complement.0.capacity() >= complement.0.len());
 
         Ok(())
     }
 
     #[test]
-    fn test_iter_and_contains() -> Result<(), String> {
-        let empty = RangeSet::<u8>::empty();
-        assert_eq!(0, empty.iter().count());
-        assert!(!empty.contains(0));
+    fn test_operation_iters() -> Result<(), String> {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
 
-        let total = RangeSet::<u8>::total();
-        assert_eq!(256, total.iter().collect::<HashSet<_>>().len());
-        assert!(total.iter().all(|v| total.contains(v)));
+        let mut rng = StdRng::seed_from_u64(213);
+        let mut random_set = |count: usize| {
+            RangeSet::<i8>::new_from_iter((0..count).map(|_| {
+                let min: i8 = rng.gen();
+                min..=min.saturating_add(rng.gen_range(0..40))
+            }))
+        };
+        let mut sets = vec![RangeSet::empty(), RangeSet::total()];
+        sets.extend((0..30).map(|i| random_set(i % 8)));
 
-        let range = RangeSet(vec![19, 33, 53, 70]);
-        assert_eq!(33, range.iter().collect::<HashSet<_>>().len()); // (33 - 19 + 1) + (77 - 53 + 1) = 33
-        assert!(range.iter().all(|v| range.contains(v)));
-        assert!(!range.contains(34));
+        for range1 in &sets {
+            for range2 in &sets {
+                let union = range1.union(range2);
+                let intersection = range1.intersection(range2);
+                let difference = range1.difference(range2);
+                assert_eq!(
+                    union,
+                    RangeSet::new_from_iter(range1.union_ranges_iter(range2))
+                );
+                assert_eq!(union.0.len() / 2, range1.union_ranges_iter(range2).count());
+                assert_eq!(
+                    intersection,
+                    RangeSet::new_from_iter(range1.intersection_ranges_iter(range2))
+                );
+                assert_eq!(
+                    intersection.0.len() / 2,
+                    range1.intersection_ranges_iter(range2).count()
+                );
+                assert_eq!(
+                    difference,
+                    RangeSet::new_from_iter(range1.difference_ranges_iter(range2))
+                );
+                assert_eq!(
+                    difference.0.len() / 2,
+                    range1.difference_ranges_iter(range2).count()
+                );
+                assert!(union.iter().eq(range1.union_iter(range2)));
+                assert!(intersection.iter().eq(range1.intersection_iter(range2)));
+                assert!(difference.iter().eq(range1.difference_iter(range2)));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_union_random() -> Result<(), String> {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(210);
+        for _ in 0..1000 {
+            let random_set = |rng: &mut StdRng| {
+                let count = rng.gen_range(0..8);
+                RangeSet::<i8>::new_from_iter((0..count).map(|_| {
+                    let min: i8 = rng.gen();
+                    min..=min.saturating_add(rng.gen_range(0..40))
+                }))
+            };
+            let range1 = random_set(&mut rng);
+            let range2 = random_set(&mut rng);
+            let expected = RangeSet::new_from_iter(
+                range1
+                    .0
+                    .chunks_exact(2)
+                    .chain(range2.0.chunks_exact(2))
+                    .map(|range| range[0]..=range[1]),
+            );
+            assert_eq!(expected, range1.union(&range2));
+            assert_eq!(expected, range2.union(&range1));
+        }
 
         Ok(())
     }
@@ -849,6 +5864,145 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_widen_and_narrow() -> Result<(), String> {
+        let empty = RangeSet::<u8>::empty();
+        assert!(empty.widen(&empty).is_empty());
+        assert_eq!(
+            RangeSet(vec![3, 4]),
+            RangeSet(vec![3, 4])
+                .widen(&empty)
+                .narrow(&RangeSet(vec![3, 4]))
+        );
+        assert!(RangeSet::<u8>(vec![3, 4]).widen(&empty).is_total());
+
+        let previous = RangeSet::<u8>(vec![10, 20, 30, 40, 50, 60]);
+        assert_eq!(previous, previous.widen(&previous));
+        assert_eq!(previous, RangeSet(vec![12, 14]).widen(&previous));
+        assert_eq!(
+            RangeSet(vec![0, 20, 30, 40, 50, 60]),
+            RangeSet(vec![9, 9]).widen(&previous)
+        );
+        assert_eq!(
+            RangeSet(vec![10, 40, 50, 255]),
+            RangeSet(vec![25, 25, 61, 61]).widen(&previous)
+        );
+        assert_eq!(
+            RangeSet(vec![10, 20, 30, 60]),
+            RangeSet(vec![40, 45]).widen(&previous)
+        );
+
+        let mut current = RangeSet::<u8>(vec![0, 0]);
+        let mut iterations = 0;
+        loop {
+            let next = current.union(&RangeSet::new_from_range(
+                current.0[1]..=current.0[1].saturating_add(1),
+            ));
+            let widened = next.widen(&current);
+            if widened == current {
+                break;
+            }
+            current = widened;
+            iterations += 1;
+        }
+        assert_eq!(1, iterations);
+        assert_eq!(RangeSet(vec![0, 255]), current);
+
+        let mut current = RangeSet::<u8>::empty();
+        for i in 0..50 {
+            let next = current.union(&RangeSet::singleton(i * 5));
+            let widened = next.widen_with_max_ranges(&current, 4);
+            assert!(widened.0.len() / 2 <= 4);
+            assert!(widened.contains_all(&next));
+            current = widened;
+        }
+        assert!(RangeSet::<u8>::empty()
+            .widen_with_max_ranges(&empty, 0)
+            .is_empty());
+
+        let current = RangeSet(vec![0, 255]);
+        let narrowed = current.narrow(&RangeSet(vec![0, 100]));
+        assert_eq!(RangeSet(vec![0, 100]), narrowed);
+        assert_eq!(narrowed, narrowed.narrow(&RangeSet(vec![0, 50])));
+        assert!(current.narrow(&empty).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-3, 0, 10, 10]);
+        assert_eq!("RangeSet([-3, 0, 10, 10])", format!("{:?}", range));
+        assert_eq!(
+            "RangeSet {\n    ranges: [\n        -3..=0,\n        10..=10,\n    ],\n    range_count: 2,\n    cardinality: 5,\n}",
+            format!("{:#?}", range)
+        );
+        assert_eq!(
+            "RangeSet {\n    ranges: [],\n    range_count: 0,\n    cardinality: 0,\n}",
+            format!("{:#?}", RangeSet::<u8>::empty())
+        );
+        assert!(format!("{:#?}", RangeSet::<u128>::total())
+            .contains(&format!("cardinality: {},", u128::MAX)));
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_eq_ranges() -> Result<(), String> {
+        let range = RangeSet::<u8>(vec![3, 9]);
+        assert_eq!(range, 3..=9);
+        assert_eq!(range, 3..10);
+        assert_ne!(range, 3..9);
+        assert_ne!(range, 2..=9);
+        assert_eq!(RangeSet::<u8>::total(), 0..=255);
+        assert_eq!(RangeSet::<u8>::empty(), 5..5);
+        assert_eq!(RangeSet::<u8>::empty(), 5..=4);
+        assert_ne!(RangeSet::<u8>(vec![0, 0]), 0..0);
+
+        let range = RangeSet::<i32>(vec![-5, 0, 10, 19]);
+        assert_eq!(range, &[-5..=0, 10..=19][..]);
+        assert_eq!(range, &[10..=15, -5..=-1, 0..=0, 12..=19][..]);
+        assert_ne!(range, &[-5..=0][..]);
+        assert_eq!(RangeSet::<i32>::empty(), &[][..]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_math() -> Result<(), String> {
+        let range = RangeSet::<u8>(vec![0, 0, 10, 20, 250, 255]);
+        assert_eq!(
+            "[0,0] ∪ [10,20] ∪ [250,255]",
+            range.display_math().to_string()
+        );
+        assert_eq!(
+            "[0,1) ∪ [10,21) ∪ [250,255]",
+            range.display_math().half_open().to_string()
+        );
+        assert_eq!(
+            "[0,0] ∪ [10,20] ∪ [250,255]",
+            range.display_math().half_open().closed().to_string()
+        );
+
+        assert_eq!(range.to_string(), range.display_limited(3).to_string());
+        assert_eq!(
+            "[ 0..=0 ... 250..=255 ] (3 ranges)",
+            range.display_limited(2).to_string()
+        );
+        assert_eq!(
+            "[ 0..=0 ... ] (3 ranges)",
+            range.display_limited(1).to_string()
+        );
+        assert_eq!("[ ... ] (3 ranges)", range.display_limited(0).to_string());
+        assert_eq!(
+            "[ ]",
+            RangeSet::<u8>::empty().display_limited(0).to_string()
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn readme() -> Result<(), String> {
         let range1 =
@@ -899,6 +6053,9 @@ mod tests {
                 let serialized = serde_json::to_string(&range).unwrap();
                 let unserialized: RangeSet<$t> = serde_json::from_str(&serialized).unwrap();
                 assert_eq!(range, unserialized);
+                let serialized = postcard::to_stdvec(&range).unwrap();
+                let unserialized: RangeSet<$t> = postcard::from_bytes(&serialized).unwrap();
+                assert_eq!(range, unserialized);
             )*
         };
     }
@@ -908,4 +6065,44 @@ mod tests {
     fn serde_test() {
         serde_test!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_representation() -> Result<(), String> {
+        let range = RangeSet::<i32>(vec![-10, -5, 0, 0, 3, 7]);
+        assert_eq!(
+            "[[-10,-5],[0,0],[3,7]]",
+            serde_json::to_string(&range).unwrap()
+        );
+        assert_eq!(
+            postcard::to_stdvec(&range.0).unwrap(),
+            postcard::to_stdvec(&range).unwrap()
+        );
+
+        assert_eq!(
+            RangeSet(vec![-10, 0]),
+            serde_json::from_str::<RangeSet<i32>>("[[-5,0],[-10,-6]]").unwrap()
+        );
+        assert!(serde_json::from_str::<RangeSet<i32>>("[[0,-5]]").is_err());
+        assert_eq!(
+            RangeSet(vec![-10, -5, 3, 7]),
+            serde_json::from_str::<RangeSet<i32>>("[-10,-5,3,7]").unwrap()
+        );
+        assert_eq!(
+            RangeSet::<i32>::empty(),
+            serde_json::from_str::<RangeSet<i32>>("[]").unwrap()
+        );
+        assert_eq!(
+            RangeSet(vec![1, 5]),
+            serde_json::from_str::<RangeSet<u128>>("[1,5]").unwrap()
+        );
+        assert!(serde_json::from_str::<RangeSet<i32>>("[-5,-10]").is_err());
+        assert!(serde_json::from_str::<RangeSet<i32>>("[-10,-5,-4,0]").is_err());
+        assert!(serde_json::from_str::<RangeSet<i32>>("[[-10,-5],3]").is_err());
+
+        let invalid = postcard::to_stdvec(&vec![1, 3, 2, 5]).unwrap();
+        assert!(postcard::from_bytes::<RangeSet<i32>>(&invalid).is_err());
+
+        Ok(())
+    }
 }
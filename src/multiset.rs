@@ -0,0 +1,319 @@
+use std::ops::RangeBounds;
+
+use crate::{integer::NumericInteger, range_to_bounds, RangeSet};
+
+/// A structure counting how many times each value has been covered by ranges.
+///
+/// The counts are stored as a step function: each step `(start, count)` holds the count of all the values from `start` up to the start of the next step.
+/// The values before the first step have a count of `0`.
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct RangeMultiSet<T: NumericInteger> {
+    steps: Vec<(T, usize)>,
+}
+
+impl<T: NumericInteger> std::fmt::Display for RangeMultiSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[ ")?;
+        for (min, max, count) in self.iter() {
+            write!(f, "{}..={}: {} ", min, max, count)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<T: NumericInteger> RangeMultiSet<T> {
+    /// Create a new instance where every value has a count of `0`.
+    #[inline]
+    pub fn new() -> RangeMultiSet<T> {
+        RangeMultiSet { steps: vec![] }
+    }
+
+    /// Create a new instance counting how many of the given sets contain each value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::multiset::RangeMultiSet;
+    ///
+    /// let coverage = RangeMultiSet::coverage_profile(&[
+    ///     RangeSet::<u32>::new_from_range(0..10),
+    ///     RangeSet::new_from_iter([5..15, 20..30]),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     vec![(0, 4, 1), (5, 9, 2), (10, 14, 1), (20, 29, 1)],
+    ///     coverage.iter().collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn coverage_profile(sets: &[RangeSet<T>]) -> RangeMultiSet<T> {
+        let mut events = Vec::new();
+        for set in sets {
+            for range in set.0.chunks_exact(2) {
+                events.push((range[0], true));
+                if range[1] < T::max_value() {
+                    events.push((range[1] + T::one(), false));
+                }
+            }
+        }
+        events.sort_by_key(|&(point, _)| point);
+
+        let mut steps: Vec<(T, usize)> = Vec::new();
+        let mut count = 0;
+        let mut i = 0;
+        while i < events.len() {
+            let point = events[i].0;
+            while i < events.len() && events[i].0 == point {
+                if events[i].1 {
+                    count += 1;
+                } else {
+                    count -= 1;
+                }
+                i += 1;
+            }
+            if count != steps.last().map_or(0, |&(_, last)| last) {
+                steps.push((point, count));
+            }
+        }
+
+        RangeMultiSet { steps }
+    }
+
+    /// Return `true` if every value has a count of `0`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Add `count` to every value of the given range.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::multiset::RangeMultiSet;
+    ///
+    /// let mut coverage = RangeMultiSet::<u32>::new();
+    /// coverage.add_range(0..10, 1);
+    /// coverage.add_range(5..15, 2);
+    ///
+    /// assert_eq!(1, coverage.count_at(2));
+    /// assert_eq!(3, coverage.count_at(7));
+    /// assert_eq!(2, coverage.count_at(12));
+    /// assert_eq!(0, coverage.count_at(15));
+    /// ```
+    pub fn add_range<R: RangeBounds<T>>(&mut self, range: R, count: usize) {
+        self.update(range, |c| c + count);
+    }
+
+    /// Subtract `count` from every value of the given range, the counts being saturated at `0`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::multiset::RangeMultiSet;
+    ///
+    /// let mut coverage = RangeMultiSet::<u32>::new();
+    /// coverage.add_range(0..10, 2);
+    /// coverage.remove_range(5..15, 1);
+    ///
+    /// assert_eq!(2, coverage.count_at(2));
+    /// assert_eq!(1, coverage.count_at(7));
+    /// assert_eq!(0, coverage.count_at(12));
+    /// ```
+    pub fn remove_range<R: RangeBounds<T>>(&mut self, range: R, count: usize) {
+        self.update(range, |c| c.saturating_sub(count));
+    }
+
+    /// Return how many times the given value is covered.
+    pub fn count_at(&self, value: T) -> usize {
+        let position = self.steps.partition_point(|&(start, _)| start <= value);
+        if position == 0 {
+            0
+        } else {
+            self.steps[position - 1].1
+        }
+    }
+
+    /// Return the maximum count of all the values.
+    pub fn max_count(&self) -> usize {
+        self.steps
+            .iter()
+            .map(|&(_, count)| count)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Return the values covered at least `k` times, all the values if `k` is `0`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::multiset::RangeMultiSet;
+    ///
+    /// let mut coverage = RangeMultiSet::<u32>::new();
+    /// coverage.add_range(0..10, 1);
+    /// coverage.add_range(5..15, 1);
+    /// coverage.add_range(8..20, 1);
+    ///
+    /// assert_eq!(RangeSet(vec![0, 19]), coverage.at_least(1));
+    /// assert_eq!(RangeSet(vec![5, 14]), coverage.at_least(2));
+    /// assert_eq!(RangeSet(vec![8, 9]), coverage.at_least(3));
+    /// assert!(coverage.at_least(4).is_empty());
+    /// ```
+    pub fn at_least(&self, k: usize) -> RangeSet<T> {
+        if k == 0 {
+            return RangeSet::total();
+        }
+
+        let mut bounds = Vec::new();
+        for (min, max, count) in self.iter() {
+            if count >= k {
+                if bounds
+                    .last()
+                    .is_some_and(|&last: &T| last + T::one() == min)
+                {
+                    *bounds.last_mut().unwrap() = max;
+                } else {
+                    bounds.push(min);
+                    bounds.push(max);
+                }
+            }
+        }
+        RangeSet(bounds)
+    }
+
+    /// Return an iterator over the maximal ranges with a non-zero count, as `(min, max, count)`.
+    pub fn iter(&self) -> impl Iterator<Item = (T, T, usize)> + '_ {
+        self.steps
+            .iter()
+            .enumerate()
+            .filter(|(_, &(_, count))| count > 0)
+            .map(|(i, &(start, count))| {
+                let end = match self.steps.get(i + 1) {
+                    Some(&(next, _)) => next - T::one(),
+                    None => T::max_value(),
+                };
+                (start, end, count)
+            })
+    }
+
+    fn update<R: RangeBounds<T>, F: Fn(usize) -> usize>(&mut self, range: R, f: F) {
+        let (min, max) = range_to_bounds(&range);
+        if min > max {
+            return;
+        }
+
+        let first = self.split_at(min);
+        let last = if max < T::max_value() {
+            self.split_at(max + T::one())
+        } else {
+            self.steps.len()
+        };
+        for step in &mut self.steps[first..last] {
+            step.1 = f(step.1);
+        }
+
+        // Restore the canonical form around the updated steps.
+        let from = first.saturating_sub(1);
+        let to = std::cmp::min(last + 1, self.steps.len());
+        let mut previous = if from == 0 { 0 } else { self.steps[from - 1].1 };
+        let mut kept = Vec::with_capacity(to - from);
+        for &(start, count) in &self.steps[from..to] {
+            if count != previous {
+                kept.push((start, count));
+                previous = count;
+            }
+        }
+        self.steps.splice(from..to, kept);
+    }
+
+    /// Make sure that a step starts at the given value and return its index.
+    fn split_at(&mut self, value: T) -> usize {
+        let position = self.steps.partition_point(|&(start, _)| start < value);
+        if self
+            .steps
+            .get(position)
+            .is_none_or(|&(start, _)| start != value)
+        {
+            let count = if position == 0 {
+                0
+            } else {
+                self.steps[position - 1].1
+            };
+            self.steps.insert(position, (value, count));
+        }
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multiset() -> Result<(), String> {
+        let mut coverage = RangeMultiSet::<u8>::new();
+        coverage.add_range(.., 1);
+        coverage.add_range(10..=20, 2);
+        coverage.add_range(200.., 1);
+        assert_eq!(
+            vec![(0, 9, 1), (10, 20, 3), (21, 199, 1), (200, 255, 2)],
+            coverage.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(3, coverage.max_count());
+        assert!(coverage.at_least(1).is_total());
+
+        coverage.remove_range(.., 1);
+        assert_eq!(
+            vec![(10, 20, 2), (200, 255, 1)],
+            coverage.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(RangeSet(vec![10, 20, 200, 255]), coverage.at_least(1));
+
+        coverage.add_range(21..200, 1);
+        assert_eq!(RangeSet(vec![10, 255]), coverage.at_least(1));
+        assert_eq!(RangeSet(vec![10, 20]), coverage.at_least(2));
+        assert!(coverage.at_least(0).is_total());
+
+        coverage.remove_range(.., 5);
+        assert!(coverage.is_empty());
+        assert_eq!(RangeMultiSet::new(), coverage);
+        assert!(coverage.at_least(0).is_total());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_coverage_profile() -> Result<(), String> {
+        assert!(RangeMultiSet::<u8>::coverage_profile(&[]).is_empty());
+
+        let sets = [
+            RangeSet::<u8>(vec![0, 10, 20, 30]),
+            RangeSet::<u8>(vec![5, 25]),
+            RangeSet::<u8>(vec![11, 19, 250, 255]),
+            RangeSet::<u8>::empty(),
+            RangeSet::<u8>::total(),
+        ];
+        let mut expected = RangeMultiSet::new();
+        for set in &sets {
+            for range in set.0.chunks_exact(2) {
+                expected.add_range(range[0]..=range[1], 1);
+            }
+        }
+        let coverage = RangeMultiSet::coverage_profile(&sets);
+        assert_eq!(expected, coverage);
+        assert_eq!(
+            vec![
+                (0, 4, 2),
+                (5, 25, 3),
+                (26, 30, 2),
+                (31, 249, 1),
+                (250, 255, 2)
+            ],
+            coverage.iter().collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+}
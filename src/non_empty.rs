@@ -0,0 +1,147 @@
+use std::ops::RangeBounds;
+
+use crate::{integer::NumericInteger, RangeSet};
+
+/// A `RangeSet` containing at least one value.
+///
+/// The emptiness is checked once by the constructors, so the smallest and the largest values are returned without `Option`.
+#[derive(PartialEq, Eq, Hash, Clone, Debug, PartialOrd, Ord)]
+pub struct NonEmptyRangeSet<T: NumericInteger>(RangeSet<T>);
+
+/// The error returned when converting an empty `RangeSet` into a `NonEmptyRangeSet`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct EmptyRangeSetError;
+
+impl std::fmt::Display for EmptyRangeSetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "the set does not contain any value")
+    }
+}
+
+impl std::error::Error for EmptyRangeSetError {}
+
+impl<T: NumericInteger> std::fmt::Display for NonEmptyRangeSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: NumericInteger> NonEmptyRangeSet<T> {
+    /// Return the given `RangeSet` if it contains at least one value, `None` otherwise.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::non_empty::NonEmptyRangeSet;
+    ///
+    /// let range = NonEmptyRangeSet::new(RangeSet::<u16>(vec![80, 80, 8000, 8080])).unwrap();
+    /// assert_eq!(80, range.first());
+    /// assert_eq!(8080, range.last());
+    ///
+    /// assert_eq!(None, NonEmptyRangeSet::new(RangeSet::<u16>::empty()));
+    /// ```
+    #[inline]
+    pub fn new(set: RangeSet<T>) -> Option<NonEmptyRangeSet<T>> {
+        (!set.is_empty()).then_some(NonEmptyRangeSet(set))
+    }
+
+    /// Create a new instance containing only the given value.
+    #[inline]
+    pub fn singleton(value: T) -> NonEmptyRangeSet<T> {
+        NonEmptyRangeSet(RangeSet(vec![value, value]))
+    }
+
+    /// Create a new instance from the given range, `None` if it is empty.
+    #[inline]
+    pub fn new_from_range<R: RangeBounds<T>>(range: R) -> Option<NonEmptyRangeSet<T>> {
+        NonEmptyRangeSet::new(RangeSet::new_from_range(range))
+    }
+
+    /// Return the smallest value contained.
+    #[inline]
+    pub fn first(&self) -> T {
+        self.0 .0[0]
+    }
+
+    /// Return the largest value contained.
+    #[inline]
+    pub fn last(&self) -> T {
+        self.0 .0[self.0 .0.len() - 1]
+    }
+
+    /// Return the values contained as a `RangeSet`.
+    #[inline]
+    pub fn as_range_set(&self) -> &RangeSet<T> {
+        &self.0
+    }
+
+    /// Return the values contained as a `RangeSet`.
+    #[inline]
+    pub fn into_inner(self) -> RangeSet<T> {
+        self.0
+    }
+
+    /// Return `true` if it contains the given value.
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.0.contains(value)
+    }
+
+    /// Insert the values of the given range.
+    pub fn insert_range<R: RangeBounds<T>>(&mut self, range: R) {
+        self.0 = self.0.union_range(range);
+    }
+
+    /// Return the union with the given `RangeSet`, which is not empty.
+    pub fn union(&self, that: &RangeSet<T>) -> NonEmptyRangeSet<T> {
+        NonEmptyRangeSet(self.0.union(that))
+    }
+}
+
+impl<T: NumericInteger> TryFrom<RangeSet<T>> for NonEmptyRangeSet<T> {
+    type Error = EmptyRangeSetError;
+
+    fn try_from(value: RangeSet<T>) -> Result<Self, Self::Error> {
+        NonEmptyRangeSet::new(value).ok_or(EmptyRangeSetError)
+    }
+}
+
+impl<T: NumericInteger> From<NonEmptyRangeSet<T>> for RangeSet<T> {
+    fn from(value: NonEmptyRangeSet<T>) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_non_empty() -> Result<(), String> {
+        let mut range = NonEmptyRangeSet::singleton(-5i8);
+        assert_eq!((-5, -5), (range.first(), range.last()));
+        range.insert_range(10..=20);
+        range.insert_range(1..1);
+        assert_eq!((-5, 20), (range.first(), range.last()));
+        assert!(range.contains(15));
+        assert!(!range.contains(0));
+        assert_eq!("[ -5..=-5 10..=20 ]", range.to_string());
+
+        let union = range.union(&RangeSet::total());
+        assert_eq!((i8::MIN, i8::MAX), (union.first(), union.last()));
+        assert_eq!(RangeSet(vec![-5, -5, 10, 20]), RangeSet::from(range));
+
+        assert_eq!(None, NonEmptyRangeSet::<u8>::new_from_range(3..3));
+        assert_eq!(
+            Err(EmptyRangeSetError),
+            NonEmptyRangeSet::try_from(RangeSet::<u8>::empty())
+        );
+        let range =
+            NonEmptyRangeSet::try_from(RangeSet::<u8>(vec![3, 4])).map_err(|e| e.to_string())?;
+        assert_eq!(&RangeSet(vec![3, 4]), range.as_range_set());
+
+        Ok(())
+    }
+}
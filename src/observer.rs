@@ -0,0 +1,140 @@
+use crate::{
+    delta::{impl_delta_mutations, RangeSetDelta},
+    integer::NumericInteger,
+    RangeSet,
+};
+
+/// A `RangeSet` wrapper reporting the values added and removed by each mutation to a callback.
+///
+/// Useful to update incrementally the caches derived from a set, such as bitmaps or counters, instead of comparing snapshots.
+/// The callback is not called when a mutation does not change the set.
+pub struct ObservedRangeSet<T: NumericInteger, F: FnMut(&RangeSetDelta<T>)> {
+    set: RangeSet<T>,
+    on_change: F,
+}
+
+impl<T: NumericInteger, F: FnMut(&RangeSetDelta<T>)> ObservedRangeSet<T, F> {
+    /// Create a new instance holding the given `RangeSet` and reporting its changes to `on_change`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::observer::ObservedRangeSet;
+    ///
+    /// let mut count = 0u128;
+    /// let mut set = ObservedRangeSet::new(RangeSet::<u32>::empty(), |delta| {
+    ///     count += delta.added.iter().count() as u128;
+    ///     count -= delta.removed.iter().count() as u128;
+    /// });
+    ///
+    /// set.insert_range(0..10);
+    /// set.insert_range(5..15);
+    /// set.remove_range(0..=1);
+    /// assert_eq!(&RangeSet(vec![2, 14]), set.as_range_set());
+    /// drop(set);
+    ///
+    /// assert_eq!(13, count);
+    /// ```
+    pub fn new(set: RangeSet<T>, on_change: F) -> ObservedRangeSet<T, F> {
+        ObservedRangeSet { set, on_change }
+    }
+
+    /// Return the observed `RangeSet`.
+    #[inline]
+    pub fn as_range_set(&self) -> &RangeSet<T> {
+        &self.set
+    }
+
+    /// Return the observed `RangeSet`, dropping the callback.
+    #[inline]
+    pub fn into_inner(self) -> RangeSet<T> {
+        self.set
+    }
+
+    impl_delta_mutations!();
+
+    fn changed(&mut self, delta: RangeSetDelta<T>) {
+        if !delta.is_empty() {
+            (self.on_change)(&delta);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_observer() -> Result<(), String> {
+        let mut deltas = vec![];
+        let mut set = ObservedRangeSet::new(RangeSet::<u8>(vec![10, 20]), |delta| {
+            deltas.push(delta.clone())
+        });
+
+        set.insert_range(12..=15);
+        set.insert_range(15..=25);
+        set.remove_range(0..=10);
+        set.intersect_with(&RangeSet(vec![0, 22]));
+        set.difference_with(&RangeSet(vec![100, 200]));
+        set.union_with(&RangeSet(vec![0, 0]));
+        set.complement();
+        assert_eq!(RangeSet(vec![1, 10, 23, 255]), set.into_inner());
+
+        assert_eq!(
+            vec![
+                RangeSetDelta {
+                    added: RangeSet(vec![21, 25]),
+                    removed: RangeSet::empty(),
+                },
+                RangeSetDelta {
+                    added: RangeSet::empty(),
+                    removed: RangeSet(vec![10, 10]),
+                },
+                RangeSetDelta {
+                    added: RangeSet::empty(),
+                    removed: RangeSet(vec![23, 25]),
+                },
+                RangeSetDelta {
+                    added: RangeSet(vec![0, 0]),
+                    removed: RangeSet::empty(),
+                },
+                RangeSetDelta {
+                    added: RangeSet(vec![1, 10, 23, 255]),
+                    removed: RangeSet(vec![0, 0, 11, 22]),
+                },
+            ],
+            deltas
+        );
+
+        let mut rng = StdRng::seed_from_u64(171);
+        let mut deltas = vec![];
+        let mut set =
+            ObservedRangeSet::new(RangeSet::<u8>::empty(), |delta| deltas.push(delta.clone()));
+        let mut states = vec![RangeSet::empty()];
+        for _ in 0..500 {
+            let (a, b) = (rng.gen_range(0..=255), rng.gen_range(0..=255));
+            let that = RangeSet::new_from_range(a.min(b)..=a.max(b));
+            match rng.gen_range(0..6) {
+                0 => set.insert_range(a..=b),
+                1 => set.remove_range(a..=b),
+                2 => set.union_with(&that),
+                3 => set.intersect_with(&that.complement()),
+                4 => set.difference_with(&that),
+                _ => set.complement(),
+            }
+            if set.as_range_set() != states.last().unwrap() {
+                states.push(set.as_range_set().clone());
+            }
+        }
+        drop(set);
+        assert_eq!(states.len() - 1, deltas.len());
+        for (states, delta) in states.windows(2).zip(&deltas) {
+            assert_eq!(&RangeSetDelta::between(&states[0], &states[1]), delta);
+        }
+
+        Ok(())
+    }
+}
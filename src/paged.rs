@@ -0,0 +1,496 @@
+use std::{
+    hash::{Hash, Hasher},
+    ops::{RangeBounds, RangeInclusive},
+};
+
+use crate::{
+    integer::{Measure, NumericInteger},
+    is_contained_at, range_to_bounds, stream, RangeSet,
+};
+
+/// The default maximum number of bounds of a page.
+const DEFAULT_PAGE_SIZE: usize = 1024;
+
+/// A set of ranges whose bounds are stored in pages of bounded size, indexed by the first bound of each page.
+///
+/// A mutation only rewrites the pages around the given range instead of moving all the following bounds, and a lookup searches the small array of fences before a single page.
+/// With pages of about `√n` bounds, inserting or removing a range takes `O(√n)`, which suits sets of tens of millions of ranges.
+///
+/// Two instances are equal if they contain the same values, whatever their page size and the split of their pages.
+#[derive(Clone, Debug)]
+pub struct PagedRangeSet<T: NumericInteger> {
+    /// The canonical bounds, split in non-empty pages holding an even number of bounds.
+    pages: Vec<Vec<T>>,
+    /// The first bound of each page.
+    fences: Vec<T>,
+    page_size: usize,
+}
+
+impl<T: NumericInteger> Default for PagedRangeSet<T> {
+    fn default() -> Self {
+        PagedRangeSet::with_page_size(DEFAULT_PAGE_SIZE)
+    }
+}
+
+impl<T: NumericInteger> PartialEq for PagedRangeSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bounds().eq(other.bounds())
+    }
+}
+
+impl<T: NumericInteger> Eq for PagedRangeSet<T> {}
+
+impl<T: NumericInteger + Hash> Hash for PagedRangeSet<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.pages.iter().map(Vec::len).sum());
+        self.bounds().for_each(|bound| bound.hash(state));
+    }
+}
+
+impl<T: NumericInteger> PagedRangeSet<T> {
+    /// Create a new instance that does not contain any value, with pages of at most 1024 bounds.
+    #[inline]
+    pub fn empty() -> PagedRangeSet<T> {
+        PagedRangeSet::default()
+    }
+
+    /// Create a new instance that does not contain any value, with pages of at most `page_size` bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` is lower than 2 or odd.
+    pub fn with_page_size(page_size: usize) -> PagedRangeSet<T> {
+        assert!(
+            page_size >= 2 && page_size.is_multiple_of(2),
+            "the page size must be an even number greater than or equal to 2, got {page_size}"
+        );
+        PagedRangeSet {
+            pages: vec![],
+            fences: vec![],
+            page_size,
+        }
+    }
+
+    /// Return the maximum number of bounds of a page.
+    #[inline]
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Return the number of pages.
+    #[inline]
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Return the number of ranges.
+    pub fn len(&self) -> usize {
+        self.pages.iter().map(|page| page.len() / 2).sum()
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    /// Return `true` if it contains the given value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::paged::PagedRangeSet;
+    ///
+    /// let mut range = PagedRangeSet::<u64>::with_page_size(4);
+    /// for i in 0..100 {
+    ///     range.insert_range(i * 10..i * 10 + 5);
+    /// }
+    /// assert_eq!(100, range.len());
+    /// assert!(range.page_count() >= 50);
+    ///
+    /// assert!(range.contains(504));
+    /// assert!(!range.contains(505));
+    ///
+    /// range.remove_range(3..=992);
+    /// assert_eq!(RangeSet(vec![0, 2, 993, 994]), RangeSet::from(&range));
+    /// ```
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.containing_range(value).is_some()
+    }
+
+    /// Return the range containing the given value.
+    pub fn containing_range(&self, value: T) -> Option<RangeInclusive<T>> {
+        let page = self.fences.partition_point(|&fence| fence <= value);
+        if page == 0 {
+            return None;
+        }
+        let page = &self.pages[page - 1];
+        let position = page.partition_point(|&bound| bound < value);
+        if is_contained_at(page, position, value) {
+            let start = position - position % 2;
+            Some(page[start]..=page[start + 1])
+        } else {
+            None
+        }
+    }
+
+    /// Return an iterator over the bounds of all the pages, in order.
+    fn bounds(&self) -> impl Iterator<Item = T> + '_ {
+        self.pages.iter().flatten().copied()
+    }
+
+    /// Return an iterator over the ranges, in order.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.pages
+            .iter()
+            .flat_map(|page| page.chunks_exact(2).map(|range| range[0]..=range[1]))
+    }
+
+    /// Return an iterator over all the values contained, in order.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        stream::values(self.ranges())
+    }
+
+    /// Return an iterator over the ranges having a common value with the given window, in order.
+    pub fn ranges_overlapping<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        let (min, max) = range_to_bounds(&range);
+        let first = self
+            .fences
+            .partition_point(|&fence| fence <= min)
+            .saturating_sub(1);
+        let pages = if min <= max {
+            &self.pages[first..]
+        } else {
+            &[]
+        };
+
+        pages
+            .iter()
+            .flat_map(|page| page.chunks_exact(2).map(|range| range[0]..=range[1]))
+            .skip_while(move |range| *range.end() < min)
+            .take_while(move |range| *range.start() <= max)
+    }
+
+    /// Return `true` if it contains all the values of the given set.
+    pub fn contains_all(&self, that: &PagedRangeSet<T>) -> bool {
+        that.ranges().all(|range| {
+            self.containing_range(*range.start())
+                .is_some_and(|containing| containing.end() >= range.end())
+        })
+    }
+
+    /// Return `true` if it has at least one value in common with the given set.
+    pub fn has_intersection(&self, that: &PagedRangeSet<T>) -> bool {
+        stream::merge_intersection(self.ranges(), that.ranges())
+            .next()
+            .is_some()
+    }
+
+    /// Return the number of values contained measured with the given type, `None` if it does not fit.
+    pub fn covered_len<M: Measure<T>>(&self) -> Option<M> {
+        self.ranges().try_fold(M::zero(), |len, range| {
+            len.checked_sum(M::measure(*range.start(), *range.end())?)
+        })
+    }
+
+    /// Return the number of values contained within the given window, saturating at `u128::MAX`.
+    pub fn count_in<R: RangeBounds<T>>(&self, range: R) -> u128 {
+        let (min, max) = range_to_bounds(&range);
+        self.ranges_overlapping(min..=max)
+            .map(|range| {
+                let (range_min, range_max) = range.into_inner();
+                let extent =
+                    T::distance(std::cmp::max(range_min, min), std::cmp::min(range_max, max));
+                T::distance_to_u128(extent)
+            })
+            .fold(0u128, |count, extent| {
+                count.saturating_add(extent).saturating_add(1)
+            })
+    }
+
+    /// Return an iterator over the ranges of values not contained within the given window, in order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::paged::PagedRangeSet;
+    ///
+    /// let mut range = PagedRangeSet::<u32>::with_page_size(2);
+    /// for (min, max) in [(0, 1), (5, 6), (10, 12), (20, 25)] {
+    ///     range.insert_range(min..=max);
+    /// }
+    ///
+    /// let gaps: Vec<_> = range.gaps_within(4..=15).collect();
+    /// assert_eq!(vec![4..=4, 7..=9, 13..=15], gaps);
+    /// assert_eq!(3, range.count_in(6..=11));
+    /// assert_eq!(vec![5..=6, 10..=12], range.ranges_overlapping(6..=11).collect::<Vec<_>>());
+    /// assert_eq!(range, PagedRangeSet::from(&RangeSet(vec![0, 1, 5, 6, 10, 12, 20, 25])));
+    /// ```
+    pub fn gaps_within<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        let (min, max) = range_to_bounds(&range);
+        stream::merge_difference(
+            (min <= max).then_some(min..=max),
+            self.ranges_overlapping(min..=max),
+        )
+    }
+
+    /// Return the union with the given set, with the page size of `self`.
+    ///
+    /// The pages are merged as streams of ranges, without building a `RangeSet` of all the bounds.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::paged::PagedRangeSet;
+    ///
+    /// let range1 = PagedRangeSet::<u32>::from(&RangeSet(vec![0, 9, 20, 29]));
+    /// let range2 = PagedRangeSet::<u32>::from(&RangeSet(vec![5, 24]));
+    ///
+    /// assert_eq!(RangeSet(vec![0, 29]), RangeSet::from(&range1.union(&range2)));
+    /// assert_eq!(
+    ///     RangeSet(vec![5, 9, 20, 24]),
+    ///     RangeSet::from(&range1.intersection(&range2))
+    /// );
+    /// assert_eq!(
+    ///     RangeSet(vec![0, 4, 25, 29]),
+    ///     RangeSet::from(&range1.difference(&range2))
+    /// );
+    /// assert_eq!(Some(20u32), range1.covered_len());
+    /// ```
+    pub fn union(&self, that: &PagedRangeSet<T>) -> PagedRangeSet<T> {
+        self.collect_pages(stream::merge_union(self.ranges(), that.ranges()))
+    }
+
+    /// Return the intersection with the given set, with the page size of `self`.
+    pub fn intersection(&self, that: &PagedRangeSet<T>) -> PagedRangeSet<T> {
+        self.collect_pages(stream::merge_intersection(self.ranges(), that.ranges()))
+    }
+
+    /// Return the difference with the given set, with the page size of `self`.
+    pub fn difference(&self, that: &PagedRangeSet<T>) -> PagedRangeSet<T> {
+        self.collect_pages(stream::merge_difference(self.ranges(), that.ranges()))
+    }
+
+    /// Return a new instance with the page size of `self` holding the given sorted and disjoint ranges, in full pages.
+    fn collect_pages(&self, ranges: impl Iterator<Item = RangeInclusive<T>>) -> PagedRangeSet<T> {
+        let mut paged = PagedRangeSet::with_page_size(self.page_size);
+        for range in ranges {
+            match paged.pages.last_mut() {
+                Some(page) if page.len() < paged.page_size => {
+                    page.extend_from_slice(&[*range.start(), *range.end()])
+                }
+                _ => {
+                    paged.fences.push(*range.start());
+                    paged.pages.push(vec![*range.start(), *range.end()]);
+                }
+            }
+        }
+        paged
+    }
+
+    /// Insert the values of the given range.
+    pub fn insert_range<R: RangeBounds<T>>(&mut self, range: R) {
+        let (min, max) = range_to_bounds(&range);
+        if min <= max {
+            self.rewrite(min, max, |set| set.union_range(min..=max));
+        }
+    }
+
+    /// Remove the values of the given range.
+    pub fn remove_range<R: RangeBounds<T>>(&mut self, range: R) {
+        let (min, max) = range_to_bounds(&range);
+        if min <= max && !self.is_empty() {
+            self.rewrite(min, max, |set| set.difference_range(min..=max));
+        }
+    }
+
+    /// Apply the given operation to the pages having a value within `min - 1..=max + 1`, and split the result into new pages.
+    fn rewrite(&mut self, min: T, max: T, operation: impl FnOnce(RangeSet<T>) -> RangeSet<T>) {
+        let low = if min > T::min_value() {
+            min - T::one()
+        } else {
+            min
+        };
+        let high = if max < T::max_value() {
+            max + T::one()
+        } else {
+            max
+        };
+        let mut first = self
+            .pages
+            .partition_point(|page| page[page.len() - 1] < low);
+        let mut last = self.fences.partition_point(|&fence| fence <= high);
+        if first >= last {
+            // No page is touched, the range goes at the end of the previous page or at the start of the next one.
+            first = first.min(self.pages.len().saturating_sub(1));
+            last = (first + 1).min(self.pages.len());
+        }
+
+        let mut bounds: Vec<T> = self.pages[first..last].concat();
+        bounds = operation(RangeSet(bounds)).0;
+        // Merge the small pages left by the removals with the next page.
+        while bounds.len() < self.page_size / 2 && last < self.pages.len() {
+            bounds.extend_from_slice(&self.pages[last]);
+            last += 1;
+        }
+
+        let count = bounds.len().div_ceil(self.page_size);
+        let per_page = bounds.len().div_ceil(2 * count.max(1)) * 2;
+        let pages: Vec<Vec<T>> = bounds.chunks(per_page.max(2)).map(<[T]>::to_vec).collect();
+        self.fences
+            .splice(first..last, pages.iter().map(|page| page[0]));
+        self.pages.splice(first..last, pages);
+    }
+}
+
+impl<T: NumericInteger> From<&RangeSet<T>> for PagedRangeSet<T> {
+    fn from(value: &RangeSet<T>) -> Self {
+        let mut paged = PagedRangeSet::default();
+        paged.pages = value.0.chunks(paged.page_size).map(<[T]>::to_vec).collect();
+        paged.fences = paged.pages.iter().map(|page| page[0]).collect();
+        paged
+    }
+}
+
+impl<T: NumericInteger> From<&PagedRangeSet<T>> for RangeSet<T> {
+    fn from(value: &PagedRangeSet<T>) -> Self {
+        RangeSet(value.pages.concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_mutations() -> Result<(), String> {
+        let mut rng = StdRng::seed_from_u64(192);
+        for page_size in [2, 4, 6, 16] {
+            let mut expected = RangeSet::<u16>::empty();
+            let mut range = PagedRangeSet::<u16>::with_page_size(page_size);
+            for _ in 0..2000 {
+                let min = rng.gen_range(0..=1000);
+                let length = if rng.gen_bool(0.9) { 3 } else { 200 };
+                let max = min + rng.gen_range(0..=length);
+                if rng.gen_bool(0.6) {
+                    expected = expected.union_range(min..=max);
+                    range.insert_range(min..=max);
+                } else {
+                    expected = expected.difference_range(min..=max);
+                    range.remove_range(min..=max);
+                }
+                assert_eq!(expected, RangeSet::from(&range));
+                assert_eq!(expected.0.len() / 2, range.len());
+                assert!(range
+                    .pages
+                    .iter()
+                    .all(|page| !page.is_empty() && page.len() <= page_size));
+                assert_eq!(
+                    range.fences,
+                    range.pages.iter().map(|page| page[0]).collect::<Vec<_>>()
+                );
+
+                for _ in 0..10 {
+                    let value = rng.gen_range(0..=1300);
+                    assert_eq!(expected.contains(value), range.contains(value));
+                }
+            }
+            assert_eq!(
+                expected.ranges_overlapping(..).collect::<Vec<_>>(),
+                range.ranges().collect::<Vec<_>>()
+            );
+        }
+
+        let mut rng = StdRng::seed_from_u64(193);
+        for page_size in [2, 6] {
+            for _ in 0..200 {
+                let mut sets = [RangeSet::<u16>::empty(), RangeSet::<u16>::empty()];
+                for set in &mut sets {
+                    for _ in 0..rng.gen_range(0..50) {
+                        let min = rng.gen_range(0..=1000);
+                        *set = set.union_range(min..=min + rng.gen_range(0..=20));
+                    }
+                }
+                let [a, b] = &sets;
+                let mut paged_a = PagedRangeSet::with_page_size(page_size);
+                for range in a.ranges_overlapping(..) {
+                    paged_a.insert_range(range);
+                }
+                let paged_b = PagedRangeSet::from(b);
+
+                for (expected, paged) in [
+                    (a.union(b), paged_a.union(&paged_b)),
+                    (a.intersection(b), paged_a.intersection(&paged_b)),
+                    (a.difference(b), paged_a.difference(&paged_b)),
+                ] {
+                    assert_eq!(expected, RangeSet::from(&paged));
+                    assert_eq!(page_size, paged.page_size());
+                    assert!(paged
+                        .pages
+                        .iter()
+                        .all(|page| !page.is_empty() && page.len() <= page_size));
+                    assert_eq!(expected.covered_len::<u32>(), paged.covered_len());
+                    assert!(expected.iter().eq(paged.iter()));
+                }
+
+                for _ in 0..10 {
+                    let min = rng.gen_range(0..=1100);
+                    let max = rng.gen_range(0..=1100);
+                    assert!(a
+                        .ranges_overlapping(min..=max)
+                        .eq(paged_a.ranges_overlapping(min..=max)));
+                    assert!(a.gaps_within(min..=max).eq(paged_a.gaps_within(min..=max)));
+                    assert_eq!(a.count_in(min..=max), paged_a.count_in(min..=max));
+                }
+                assert_eq!(a.contains_all(b), paged_a.contains_all(&paged_b));
+                assert_eq!(a.has_intersection(b), paged_a.has_intersection(&paged_b));
+                assert!(paged_a.contains_all(&PagedRangeSet::from(&a.intersection(b))));
+            }
+        }
+
+        // The same values split differently in pages of different sizes.
+        let mut inserted = PagedRangeSet::<u32>::empty();
+        let mut small_pages = PagedRangeSet::<u32>::with_page_size(8);
+        for i in 0..600 {
+            inserted.insert_range(i * 10..i * 10 + 5);
+            small_pages.insert_range(i * 10..i * 10 + 5);
+        }
+        let converted = PagedRangeSet::from(&RangeSet::from(&inserted));
+        assert_ne!(inserted.pages, converted.pages);
+        assert_eq!(inserted, converted);
+        assert_eq!(small_pages, converted);
+        let hash = |range: &PagedRangeSet<u32>| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            range.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&inserted), hash(&converted));
+        assert_eq!(hash(&small_pages), hash(&converted));
+        small_pages.remove_range(0..=0);
+        assert_ne!(small_pages, converted);
+
+        let mut range = PagedRangeSet::<i8>::from(&RangeSet::total());
+        range.remove_range(i8::MIN..=i8::MIN);
+        range.remove_range(i8::MAX..);
+        range.insert_range(i8::MIN..=i8::MIN);
+        assert_eq!(RangeSet(vec![-128, 126]), RangeSet::from(&range));
+        assert!(!range.contains(i8::MAX));
+        range.remove_range(..);
+        assert!(range.is_empty());
+        assert_eq!(0, range.page_count());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use rayon::prelude::*;
+
+use crate::{integer::NumericInteger, RangeSet};
+
+/// The number of ranges below which the operations run on the current thread.
+const SEQUENTIAL_THRESHOLD: usize = 1 << 14;
+
+/// The number of partitions per thread, to balance the partitions of uneven cost.
+const PARTITIONS_PER_THREAD: usize = 4;
+
+impl<T: NumericInteger + Send + Sync> RangeSet<T> {
+    /// Return the union with the given `RangeSet`, computed in parallel with `rayon`.
+    ///
+    /// The domain is split at the starts of evenly spaced ranges, the partitions are merged in parallel and their results concatenated.
+    /// Small sets are merged on the current thread.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let even = RangeSet::<u64>::new_from_iter((0..100_000).map(|i| i * 4..=i * 4 + 1));
+    /// let odd = RangeSet::<u64>::new_from_iter((0..100_000).map(|i| i * 4 + 2..=i * 4 + 2));
+    ///
+    /// assert_eq!(even.union(&odd), even.par_union(&odd));
+    /// ```
+    pub fn par_union(&self, that: &RangeSet<T>) -> RangeSet<T> {
+        par_operation(self, that, RangeSet::union_into)
+    }
+
+    /// Return the intersection with the given `RangeSet`, computed in parallel with `rayon`.
+    pub fn par_intersection(&self, that: &RangeSet<T>) -> RangeSet<T> {
+        par_operation(self, that, RangeSet::intersection_into)
+    }
+
+    /// Return the difference with the given `RangeSet`, computed in parallel with `rayon`.
+    pub fn par_difference(&self, that: &RangeSet<T>) -> RangeSet<T> {
+        par_operation(self, that, RangeSet::difference_into)
+    }
+}
+
+/// Apply the operation to each partition of the domain in parallel, and concatenate the results.
+fn par_operation<T: NumericInteger + Send + Sync>(
+    a: &RangeSet<T>,
+    b: &RangeSet<T>,
+    operation: fn(&RangeSet<T>, &RangeSet<T>, &mut RangeSet<T>),
+) -> RangeSet<T> {
+    let ranges = (a.0.len() + b.0.len()) / 2;
+    let partitions = rayon::current_num_threads() * PARTITIONS_PER_THREAD;
+    if ranges < SEQUENTIAL_THRESHOLD || partitions < 2 {
+        let mut out = RangeSet::empty();
+        operation(a, b, &mut out);
+        return out;
+    }
+
+    // The starts of evenly spaced ranges of the larger set split the domain.
+    let larger = if a.0.len() >= b.0.len() { a } else { b };
+    let step = (larger.0.len() / 2).div_ceil(partitions).max(1);
+    let fences: Vec<T> = larger
+        .0
+        .chunks_exact(2)
+        .step_by(step)
+        .skip(1)
+        .map(|range| range[0])
+        .collect();
+
+    let parts: Vec<RangeSet<T>> = (0..=fences.len())
+        .into_par_iter()
+        .map(|i| {
+            let min = if i == 0 {
+                T::min_value()
+            } else {
+                fences[i - 1]
+            };
+            let max = if i == fences.len() {
+                T::max_value()
+            } else {
+                fences[i] - T::one()
+            };
+            let mut out = RangeSet::empty();
+            operation(&clip(a, min, max), &clip(b, min, max), &mut out);
+            out
+        })
+        .collect();
+
+    let mut bounds = Vec::with_capacity(parts.iter().map(|part| part.0.len()).sum());
+    for part in parts {
+        match (bounds.last(), part.0.first()) {
+            // The ranges split at a fence are joined back.
+            (Some(&end), Some(&start)) if end + T::one() == start => {
+                bounds.pop();
+                bounds.extend_from_slice(&part.0[1..]);
+            }
+            _ => bounds.extend_from_slice(&part.0),
+        }
+    }
+    RangeSet(bounds)
+}
+
+/// Return the values of the given set within `min..=max`, found with a binary search.
+fn clip<T: NumericInteger>(set: &RangeSet<T>, min: T, max: T) -> RangeSet<T> {
+    let start = set.0.partition_point(|&bound| bound < min);
+    let end = set.0.partition_point(|&bound| bound <= max);
+    let mut bounds = Vec::with_capacity(end - start + 2);
+    if start % 2 != 0 {
+        bounds.push(min);
+    }
+    bounds.extend_from_slice(&set.0[start..end]);
+    if end % 2 != 0 {
+        bounds.push(max);
+    }
+    RangeSet(bounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_operations() -> Result<(), String> {
+        let mut rng = StdRng::seed_from_u64(193);
+        for len in [0, 10, SEQUENTIAL_THRESHOLD, 3 * SEQUENTIAL_THRESHOLD] {
+            let mut random = |len: usize| {
+                let mut values: Vec<i32> = (0..len * 2)
+                    .map(|_| rng.gen_range(-(len as i32) * 8..=len as i32 * 8))
+                    .collect();
+                values.sort();
+                RangeSet::new_from_iter(values.chunks_exact(2).map(|r| r[0]..=r[1]))
+            };
+            let (a, b) = (random(len), random(len / 2));
+
+            assert_eq!(a.union(&b), a.par_union(&b));
+            assert_eq!(a.union(&b), b.par_union(&a));
+            assert_eq!(a.intersection(&b), a.par_intersection(&b));
+            assert_eq!(a.difference(&b), a.par_difference(&b));
+            assert_eq!(b.difference(&a), b.par_difference(&a));
+            assert!(a.par_difference(&RangeSet::total()).is_empty());
+            assert_eq!(RangeSet::total(), a.par_union(&a.complement()));
+        }
+
+        assert_eq!(
+            RangeSet(vec![-7, -5, -3, 0, 2, 3]),
+            clip(&RangeSet(vec![-10, -5, -3, 0, 2, 5]), -7, 3)
+        );
+        assert!(clip(&RangeSet(vec![-10, -5]), -4, 3).is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+use std::ops::Bound;
+
+use sqlx::{
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{types::PgRange, PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres},
+    Decode, Encode, Type,
+};
+
+use crate::{range::AnyRange, RangeSet};
+
+// https://github.com/postgres/postgres/blob/master/src/include/utils/rangetypes.h
+const EMPTY: u8 = 0x01;
+const LB_INC: u8 = 0x02;
+const UB_INC: u8 = 0x04;
+const LB_INF: u8 = 0x08;
+const UB_INF: u8 = 0x10;
+
+/// Stored as an `int8multirange`.
+impl Type<Postgres> for RangeSet<i64> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("int8multirange")
+    }
+}
+
+impl Encode<'_, Postgres> for RangeSet<i64> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend_from_slice(&i32::try_from(self.0.len() / 2)?.to_be_bytes());
+        for range in self.0.chunks_exact(2) {
+            encode_range(range[0], range[1], buf);
+        }
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for RangeSet<i64> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => decode_binary(value.as_bytes()?),
+            PgValueFormat::Text => decode_text(value.as_str()?),
+        }
+    }
+}
+
+/// Stored as an `int8range`, an empty range being one with `min > max`.
+impl Type<Postgres> for AnyRange<i64> {
+    fn type_info() -> PgTypeInfo {
+        PgRange::<i64>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        PgRange::<i64>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for AnyRange<i64> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let &(min, max) = self.get_bounds();
+        let range = if min > max {
+            PgRange::from(0..0)
+        } else {
+            PgRange {
+                start: match min {
+                    i64::MIN => Bound::Unbounded,
+                    min => Bound::Included(min),
+                },
+                end: match max {
+                    i64::MAX => Bound::Unbounded,
+                    max => Bound::Included(max),
+                },
+            }
+        };
+        range.encode_by_ref(buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for AnyRange<i64> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let range = PgRange::<i64>::decode(value)?;
+        Ok(to_any_range(range.start, range.end))
+    }
+}
+
+/// Write a range with the format of `range_send`, the infinite bounds standing for the limits of `i64` since an inclusive `i64::MAX` cannot be canonicalized.
+fn encode_range(min: i64, max: i64, buf: &mut Vec<u8>) {
+    let mut flags = 0;
+    let mut bounds = vec![];
+    if min == i64::MIN {
+        flags |= LB_INF;
+    } else {
+        flags |= LB_INC;
+        bounds.push(min);
+    }
+    if max == i64::MAX {
+        flags |= UB_INF;
+    } else {
+        flags |= UB_INC;
+        bounds.push(max);
+    }
+
+    buf.extend_from_slice(&(1 + 12 * bounds.len() as i32).to_be_bytes());
+    buf.push(flags);
+    for bound in bounds {
+        buf.extend_from_slice(&8i32.to_be_bytes());
+        buf.extend_from_slice(&bound.to_be_bytes());
+    }
+}
+
+/// Convert the bounds of a range, an exclusive bound at the limit of `i64` giving an empty range instead of overflowing.
+fn to_any_range(start: Bound<i64>, end: Bound<i64>) -> AnyRange<i64> {
+    if start == Bound::Excluded(i64::MAX) || end == Bound::Excluded(i64::MIN) {
+        AnyRange::new(1, 0)
+    } else {
+        AnyRange::from((start, end))
+    }
+}
+
+/// Read a multirange with the format of `multirange_send`.
+fn decode_binary(mut buf: &[u8]) -> Result<RangeSet<i64>, BoxDynError> {
+    fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8], BoxDynError> {
+        if buf.len() < len {
+            return Err("unexpected end of multirange".into());
+        }
+        let (head, tail) = buf.split_at(len);
+        *buf = tail;
+        Ok(head)
+    }
+    fn take_i32(buf: &mut &[u8]) -> Result<i32, BoxDynError> {
+        Ok(i32::from_be_bytes(take(buf, 4)?.try_into()?))
+    }
+    fn take_bound(buf: &mut &[u8]) -> Result<i64, BoxDynError> {
+        if take_i32(buf)? != 8 {
+            return Err("unexpected length of int8 bound".into());
+        }
+        Ok(i64::from_be_bytes(take(buf, 8)?.try_into()?))
+    }
+
+    let count = take_i32(&mut buf)?;
+    // Each range takes at least 5 bytes, the count cannot reserve more than the buffer holds.
+    let mut ranges = Vec::with_capacity(usize::try_from(count)?.min(buf.len() / 5));
+    for _ in 0..count {
+        let len = usize::try_from(take_i32(&mut buf)?)?;
+        let mut range = take(&mut buf, len)?;
+        let flags = take(&mut range, 1)?[0];
+        if flags & EMPTY != 0 {
+            continue;
+        }
+        let start = match flags & (LB_INF | LB_INC) {
+            LB_INF => Bound::Unbounded,
+            LB_INC => Bound::Included(take_bound(&mut range)?),
+            _ => Bound::Excluded(take_bound(&mut range)?),
+        };
+        let end = match flags & (UB_INF | UB_INC) {
+            UB_INF => Bound::Unbounded,
+            UB_INC => Bound::Included(take_bound(&mut range)?),
+            _ => Bound::Excluded(take_bound(&mut range)?),
+        };
+        ranges.push(to_any_range(start, end));
+    }
+
+    Ok(RangeSet::new_from_ranges(&ranges))
+}
+
+/// Read a multirange with the format of `multirange_out`, such as `{[1,6),[8,9)}`.
+fn decode_text(s: &str) -> Result<RangeSet<i64>, BoxDynError> {
+    let ranges = s
+        .trim()
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or_else(|| format!("invalid multirange `{s}`"))?;
+
+    let mut bounds = vec![];
+    let mut rest = ranges.trim();
+    while !rest.is_empty() {
+        let end = rest
+            .find([')', ']'])
+            .ok_or_else(|| format!("invalid multirange `{s}`"))?;
+        let (range, tail) = rest.split_at(end + 1);
+        rest = tail.trim_start().trim_start_matches(',').trim_start();
+
+        let range = range.trim();
+        if range.len() < 2 || !range.starts_with(['[', '(']) || !range.ends_with([']', ')']) {
+            return Err(format!("invalid range `{range}`").into());
+        }
+        let (lower, upper) = range[1..range.len() - 1]
+            .split_once(',')
+            .ok_or_else(|| format!("invalid range `{range}`"))?;
+        let start: Bound<i64> = match lower.trim() {
+            "" => Bound::Unbounded,
+            lower if range.starts_with('[') => Bound::Included(lower.parse()?),
+            lower => Bound::Excluded(lower.parse()?),
+        };
+        let end: Bound<i64> = match upper.trim() {
+            "" => Bound::Unbounded,
+            upper if range.ends_with(']') => Bound::Included(upper.parse()?),
+            upper => Bound::Excluded(upper.parse()?),
+        };
+        bounds.push(to_any_range(start, end));
+    }
+
+    Ok(RangeSet::new_from_ranges(&bounds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multirange() -> Result<(), String> {
+        for range in [
+            RangeSet::<i64>::empty(),
+            RangeSet::<i64>::total(),
+            RangeSet::<i64>(vec![i64::MIN, -100, 0, 0, 10, 20, 30, i64::MAX]),
+        ] {
+            let mut buf = PgArgumentBuffer::default();
+            assert!(matches!(range.encode_by_ref(&mut buf), Ok(IsNull::No)));
+            assert_eq!(range, decode_binary(&buf).map_err(|e| e.to_string())?);
+        }
+
+        let mut buf = PgArgumentBuffer::default();
+        let _ = RangeSet::<i64>(vec![1, 5]).encode_by_ref(&mut buf);
+        assert_eq!(
+            vec![
+                0,
+                0,
+                0,
+                1, // count
+                0,
+                0,
+                0,
+                25, // length
+                LB_INC | UB_INC,
+                0,
+                0,
+                0,
+                8,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                1, // 1
+                0,
+                0,
+                0,
+                8,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                5, // 5
+            ],
+            buf.to_vec()
+        );
+
+        // Canonical form sent by Postgres.
+        let bytes = [
+            0, 0, 0, 2, // count
+            0, 0, 0, 13, // length
+            LB_INF, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 6, // (,6)
+            0, 0, 0, 25, // length
+            LB_INC, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 8, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0,
+            10, // [8,10)
+        ];
+        assert_eq!(
+            RangeSet(vec![i64::MIN, 5, 8, 9]),
+            decode_binary(&bytes).map_err(|e| e.to_string())?
+        );
+        assert!(decode_binary(&bytes[..20]).is_err());
+        assert!(decode_binary(&i32::MAX.to_be_bytes()).is_err());
+        let bytes = [
+            0, 0, 0, 1, // count
+            0, 0, 0, 13, // length
+            UB_INF, 0, 0, 0, 8, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // (i64::MAX,)
+        ];
+        assert_eq!(
+            RangeSet::<i64>::empty(),
+            decode_binary(&bytes).map_err(|e| e.to_string())?
+        );
+
+        assert_eq!(
+            RangeSet(vec![i64::MIN, 5, 8, 9, 12, i64::MAX]),
+            decode_text("{(,6), [8,10), (11,)}").map_err(|e| e.to_string())?
+        );
+        assert_eq!(
+            RangeSet::<i64>::empty(),
+            decode_text("{}").map_err(|e| e.to_string())?
+        );
+        assert_eq!(
+            RangeSet(vec![-5, 5]),
+            decode_text("{[-5,5]}").map_err(|e| e.to_string())?
+        );
+        assert_eq!(
+            RangeSet::<i64>::empty(),
+            decode_text("{(9223372036854775807,), [1,-9223372036854775808)}")
+                .map_err(|e| e.to_string())?
+        );
+        assert_eq!(
+            RangeSet(vec![0, 0]),
+            decode_text("{(,-9223372036854775808), [0,0]}").map_err(|e| e.to_string())?
+        );
+        assert!(decode_text("[1,2)").is_err());
+        assert!(decode_text("{[1,x)}").is_err());
+        assert!(decode_text("{)}").is_err());
+        assert!(decode_text("{1,2)}").is_err());
+        assert!(decode_text("{[1,2),é)}").is_err());
+
+        Ok(())
+    }
+}
@@ -1,14 +1,65 @@
-use std::ops::RangeBounds;
+use core::ops::{Bound, Range, RangeBounds, RangeInclusive};
 
 use crate::{integer::NumericInteger, range_to_bounds};
 
+/// Error returned when creating an `AnyRange` with a minimum greater than its maximum.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct InvertedRange<T: NumericInteger> {
+    pub min: T,
+    pub max: T,
+}
+
+impl<T: NumericInteger> core::fmt::Display for InvertedRange<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "the minimum {} is greater than the maximum {}",
+            self.min, self.max
+        )
+    }
+}
+
+impl<T: NumericInteger + core::fmt::Debug> core::error::Error for InvertedRange<T> {}
+
+/// A range of integers with inclusive bounds, empty if `min > max`.
+///
+/// With the `serde` feature flag it is serialized as `[min, max]`, and deserializing inverted bounds fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AnyRange<T: NumericInteger>((T, T));
 
+#[cfg(feature = "serde")]
+impl<'de, T: NumericInteger + serde::Deserialize<'de>> serde::Deserialize<'de> for AnyRange<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (min, max) = <(T, T)>::deserialize(deserializer)?;
+        AnyRange::try_new(min, max).map_err(serde::de::Error::custom)
+    }
+}
+
 impl<T: NumericInteger> AnyRange<T> {
     pub fn new(min: T, max: T) -> AnyRange<T> {
         AnyRange((min, max))
     }
-    
+
+    /// Create a new instance from the given bounds, failing if `min > max`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::range::AnyRange;
+    ///
+    /// assert!(AnyRange::<u8>::try_new(4, 5).is_ok());
+    /// assert!(AnyRange::<u8>::try_new(5, 5).is_ok());
+    /// assert!(AnyRange::<u8>::try_new(5, 4).is_err());
+    /// ```
+    pub fn try_new(min: T, max: T) -> Result<AnyRange<T>, InvertedRange<T>> {
+        if min > max {
+            Err(InvertedRange { min, max })
+        } else {
+            Ok(AnyRange((min, max)))
+        }
+    }
+
     pub fn from<R: RangeBounds<T>>(range: R) -> AnyRange<T> {
         AnyRange(range_to_bounds(&range))
     }
@@ -16,4 +67,134 @@ impl<T: NumericInteger> AnyRange<T> {
     pub fn get_bounds(&self) -> &(T, T) {
         &self.0
     }
+
+    /// Return `true` if it does not contain any value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::range::AnyRange;
+    ///
+    /// assert!(AnyRange::<u8>::new(5, 4).is_empty());
+    /// assert!(!AnyRange::<u8>::new(5, 5).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0 .0 > self.0 .1
+    }
+
+    /// Return the number of values contained, saturating at `u128::MAX`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::range::AnyRange;
+    ///
+    /// assert_eq!(3, AnyRange::<i8>::from(-1..=1).len());
+    /// assert_eq!(0, AnyRange::<i8>::from(1..1).len());
+    /// ```
+    pub fn len(&self) -> u128 {
+        let (min, max) = self.0;
+        if min > max {
+            0
+        } else {
+            T::distance_to_u128(T::distance(min, max)).saturating_add(1)
+        }
+    }
+}
+
+impl<T: NumericInteger> RangeBounds<T> for AnyRange<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        Bound::Included(&self.0 .0)
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        Bound::Included(&self.0 .1)
+    }
+}
+
+impl<T: NumericInteger> From<Range<T>> for AnyRange<T> {
+    fn from(range: Range<T>) -> Self {
+        AnyRange(range_to_bounds(&range))
+    }
+}
+
+impl<T: NumericInteger> From<RangeInclusive<T>> for AnyRange<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        AnyRange(range.into_inner())
+    }
+}
+
+impl<T: NumericInteger> From<(T, T)> for AnyRange<T> {
+    fn from(bounds: (T, T)) -> Self {
+        AnyRange(bounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RangeSet;
+
+    #[test]
+    fn test_conversions() -> Result<(), String> {
+        let range: AnyRange<u8> = (3..7).into();
+        assert_eq!(AnyRange::new(3, 6), range);
+        let range: AnyRange<u8> = (3..=7).into();
+        assert_eq!(AnyRange::new(3, 7), range);
+        let range: AnyRange<u8> = (3, 7).into();
+        assert_eq!(AnyRange::new(3, 7), range);
+
+        assert!(range.contains(&3));
+        assert!(range.contains(&7));
+        assert!(!range.contains(&8));
+        assert_eq!(
+            RangeSet(vec![3, 7]),
+            RangeSet::new_from_range(AnyRange::<u8>::new(3, 7))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_new() -> Result<(), String> {
+        assert_eq!(Ok(AnyRange::new(-3, 3)), AnyRange::<i64>::try_new(-3, 3));
+        assert_eq!(
+            Err(InvertedRange { min: 3, max: -3 }),
+            AnyRange::<i64>::try_new(3, -3)
+        );
+        assert_eq!(
+            "the minimum 3 is greater than the maximum -3",
+            InvertedRange { min: 3, max: -3 }.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() -> Result<(), String> {
+        let ranges = vec![AnyRange::<i64>::new(-3, 3), AnyRange::new(7, 7)];
+        let serialized = serde_json::to_string(&ranges).unwrap();
+        assert_eq!("[[-3,3],[7,7]]", serialized);
+        let unserialized: Vec<AnyRange<i64>> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(ranges, unserialized);
+
+        let error = serde_json::from_str::<AnyRange<i64>>("[3,-3]").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("the minimum 3 is greater than the maximum -3"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_len() -> Result<(), String> {
+        assert!(AnyRange::<u8>::new(1, 0).is_empty());
+        assert_eq!(0, AnyRange::<u8>::new(1, 0).len());
+        assert_eq!(1, AnyRange::<u8>::new(0, 0).len());
+        assert_eq!(256, AnyRange::<u8>::from(..).len());
+        assert_eq!(u128::MAX, AnyRange::<i128>::from(..).len());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,338 @@
+use std::ops::RangeBounds;
+
+use crate::{integer::NumericInteger, range_to_bounds, RangeSet};
+
+/// An axis-aligned rectangle with inclusive bounds.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Rectangle<T: NumericInteger> {
+    /// The inclusive bounds `(min, max)` on the x axis.
+    pub x: (T, T),
+    /// The inclusive bounds `(min, max)` on the y axis.
+    pub y: (T, T),
+}
+
+/// A structure holding a union of axis-aligned rectangles over integer coordinates.
+///
+/// The plane is cut into horizontal slabs: each slab covers a range of `y` with the same `RangeSet` of `x`.
+/// The slabs are disjoint, sorted, and two adjacent slabs never hold the same `RangeSet`, which makes the representation canonical.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct RangeSet2D<T: NumericInteger> {
+    slabs: Vec<(T, T, RangeSet<T>)>,
+}
+
+impl<T: NumericInteger> std::fmt::Display for RangeSet2D<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[ ")?;
+        for (min, max, xs) in &self.slabs {
+            write!(f, "{}..={}: {} ", min, max, xs)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<T: NumericInteger> RangeSet2D<T> {
+    /// Create a new instance that does not contain any point.
+    #[inline]
+    pub fn empty() -> RangeSet2D<T> {
+        RangeSet2D { slabs: vec![] }
+    }
+
+    /// Create a new instance that contains all possible points.
+    #[inline]
+    pub fn total() -> RangeSet2D<T> {
+        RangeSet2D {
+            slabs: vec![(T::min_value(), T::max_value(), RangeSet::total())],
+        }
+    }
+
+    /// Create a new instance from the given rectangle.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::range2d::RangeSet2D;
+    ///
+    /// // Contains the points with 0 <= x < 10 and 5 <= y <= 8
+    /// RangeSet2D::<i32>::new_from_rectangle(0..10, 5..=8);
+    /// ```
+    pub fn new_from_rectangle<Rx: RangeBounds<T>, Ry: RangeBounds<T>>(
+        x: Rx,
+        y: Ry,
+    ) -> RangeSet2D<T> {
+        let xs = RangeSet::new_from_range(x);
+        let (min, max) = range_to_bounds(&y);
+        if xs.is_empty() || min > max {
+            RangeSet2D::empty()
+        } else {
+            RangeSet2D {
+                slabs: vec![(min, max, xs)],
+            }
+        }
+    }
+
+    /// Return the horizontal slabs, as `(min_y, max_y, xs)`.
+    #[inline]
+    pub fn slabs(&self) -> &[(T, T, RangeSet<T>)] {
+        &self.slabs
+    }
+
+    /// Return `true` if it does not contain any point.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.slabs.is_empty()
+    }
+
+    /// Return `true` if it contains all the possible points.
+    #[inline]
+    pub fn is_total(&self) -> bool {
+        self.slabs.len() == 1
+            && self.slabs[0].0 == T::min_value()
+            && self.slabs[0].1 == T::max_value()
+            && self.slabs[0].2.is_total()
+    }
+
+    /// Return `true` if it contains the given point.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::range2d::RangeSet2D;
+    ///
+    /// let range = RangeSet2D::<i32>::new_from_rectangle(0..10, 5..=8);
+    /// assert!(range.contains(0, 8));
+    /// assert!(!range.contains(10, 8));
+    /// ```
+    pub fn contains(&self, x: T, y: T) -> bool {
+        let position = self.slabs.partition_point(|slab| slab.1 < y);
+        self.slabs
+            .get(position)
+            .is_some_and(|(min, _, xs)| *min <= y && xs.contains(x))
+    }
+
+    /// Return `true` if it contains all the points of the given rectangle.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::range2d::RangeSet2D;
+    ///
+    /// let range = RangeSet2D::<i32>::new_from_rectangle(0..10, 0..10)
+    ///     .union(&RangeSet2D::new_from_rectangle(0..5, 10..20));
+    /// assert!(range.contains_rectangle(2..4, 5..15));
+    /// assert!(!range.contains_rectangle(2..6, 5..15));
+    /// ```
+    pub fn contains_rectangle<Rx: RangeBounds<T>, Ry: RangeBounds<T>>(&self, x: Rx, y: Ry) -> bool {
+        self.contains_all(&RangeSet2D::new_from_rectangle(x, y))
+    }
+
+    /// Return `true` if it contains the given `RangeSet2D`.
+    pub fn contains_all(&self, that: &RangeSet2D<T>) -> bool {
+        that.difference(self).is_empty()
+    }
+
+    /// Return the union with the given `RangeSet2D`.
+    pub fn union(&self, that: &RangeSet2D<T>) -> RangeSet2D<T> {
+        self.combine(that, |a, b| a.union(b))
+    }
+
+    /// Return the intersection with the given `RangeSet2D`.
+    pub fn intersection(&self, that: &RangeSet2D<T>) -> RangeSet2D<T> {
+        self.combine(that, |a, b| a.intersection(b))
+    }
+
+    /// Return the difference with the given `RangeSet2D`.
+    pub fn difference(&self, that: &RangeSet2D<T>) -> RangeSet2D<T> {
+        self.combine(that, |a, b| a.difference(b))
+    }
+
+    /// Return the complement.
+    pub fn complement(&self) -> RangeSet2D<T> {
+        RangeSet2D::total().difference(self)
+    }
+
+    /// Return the rectangles covering it, sorted by their lower `y` then lower `x` bound.
+    ///
+    /// The rectangles are disjoint and every rectangle is extended along the `y` axis as long as its `x` bounds are a range of the slabs below.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::range2d::{RangeSet2D, Rectangle};
+    ///
+    /// let range = RangeSet2D::<i32>::new_from_rectangle(0..=9, 0..=9)
+    ///     .union(&RangeSet2D::new_from_rectangle(20..=29, 5..=14));
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         Rectangle { x: (0, 9), y: (0, 9) },
+    ///         Rectangle { x: (20, 29), y: (5, 14) },
+    ///     ],
+    ///     range.rectangles().collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn rectangles(&self) -> impl Iterator<Item = Rectangle<T>> {
+        let mut rectangles = Vec::new();
+        let mut open: Vec<Rectangle<T>> = Vec::new();
+
+        for (i, (min, max, xs)) in self.slabs.iter().enumerate() {
+            let adjacent = i > 0 && self.slabs[i - 1].1 + T::one() == *min;
+
+            let mut still_open = Vec::with_capacity(xs.0.len() / 2);
+            for bounds in xs.0.chunks_exact(2) {
+                let x = (bounds[0], bounds[1]);
+                let continued = if adjacent {
+                    open.iter().position(|rectangle| rectangle.x == x)
+                } else {
+                    None
+                };
+                match continued {
+                    Some(index) => {
+                        let mut rectangle = open.swap_remove(index);
+                        rectangle.y.1 = *max;
+                        still_open.push(rectangle);
+                    }
+                    None => still_open.push(Rectangle { x, y: (*min, *max) }),
+                }
+            }
+            rectangles.append(&mut open);
+            open = still_open;
+        }
+        rectangles.append(&mut open);
+
+        rectangles.sort_by_key(|rectangle| (rectangle.y.0, rectangle.x.0));
+        rectangles.into_iter()
+    }
+
+    fn combine<F>(&self, that: &RangeSet2D<T>, operation: F) -> RangeSet2D<T>
+    where
+        F: Fn(&RangeSet<T>, &RangeSet<T>) -> RangeSet<T>,
+    {
+        let empty = RangeSet::empty();
+        let mut slabs: Vec<(T, T, RangeSet<T>)> =
+            Vec::with_capacity(self.slabs.len() + that.slabs.len());
+
+        let (mut i, mut j) = (0, 0);
+        let mut start = Some(T::min_value());
+        while let Some(y) = start {
+            while i < self.slabs.len() && self.slabs[i].1 < y {
+                i += 1;
+            }
+            while j < that.slabs.len() && that.slabs[j].1 < y {
+                j += 1;
+            }
+            let (self_xs, self_end) = Self::slab_at(&self.slabs, i, y, &empty);
+            let (that_xs, that_end) = Self::slab_at(&that.slabs, j, y, &empty);
+            let end = std::cmp::min(self_end, that_end);
+
+            let xs = operation(self_xs, that_xs);
+            if !xs.is_empty() {
+                match slabs.last_mut() {
+                    Some(last) if last.1 + T::one() == y && last.2 == xs => last.1 = end,
+                    _ => slabs.push((y, end, xs)),
+                }
+            }
+
+            start = if end < T::max_value() {
+                Some(end + T::one())
+            } else {
+                None
+            };
+        }
+
+        RangeSet2D { slabs }
+    }
+
+    /// Return the `RangeSet` of `x` at the given `y`, and the last `y` for which it stays the same.
+    fn slab_at<'a>(
+        slabs: &'a [(T, T, RangeSet<T>)],
+        i: usize,
+        y: T,
+        empty: &'a RangeSet<T>,
+    ) -> (&'a RangeSet<T>, T) {
+        match slabs.get(i) {
+            Some((min, max, xs)) if *min <= y => (xs, *max),
+            Some((min, _, _)) => (empty, *min - T::one()),
+            None => (empty, T::max_value()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations() -> Result<(), String> {
+        let range1 = RangeSet2D::<u8>::new_from_rectangle(0..10, 0..10);
+        let range2 = RangeSet2D::<u8>::new_from_rectangle(5..15, 5..15);
+
+        let union = range1.union(&range2);
+        assert_eq!(
+            vec![
+                (0, 4, RangeSet(vec![0, 9])),
+                (5, 9, RangeSet(vec![0, 14])),
+                (10, 14, RangeSet(vec![5, 14])),
+            ],
+            union.slabs()
+        );
+        assert!(union.contains(14, 14));
+        assert!(!union.contains(0, 14));
+
+        let intersection = range1.intersection(&range2);
+        assert_eq!(RangeSet2D::new_from_rectangle(5..10, 5..10), intersection);
+
+        let difference = range1.difference(&range2);
+        assert_eq!(
+            vec![(0, 4, RangeSet(vec![0, 9])), (5, 9, RangeSet(vec![0, 4])),],
+            difference.slabs()
+        );
+        assert_eq!(range1, difference.union(&intersection));
+
+        assert!(range1.complement().intersection(&range1).is_empty());
+        assert!(range1.complement().union(&range1).is_total());
+        assert!(RangeSet2D::<u8>::total().complement().is_empty());
+        assert!(RangeSet2D::<u8>::empty().complement().is_total());
+
+        assert!(union.contains_all(&range1));
+        assert!(!range1.contains_all(&union));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rectangles() -> Result<(), String> {
+        let range = RangeSet2D::<i16>::new_from_rectangle(0..=9, 0..=9)
+            .union(&RangeSet2D::new_from_rectangle(20..=29, 5..=14))
+            .union(&RangeSet2D::new_from_rectangle(0..=9, 20..=29));
+
+        assert_eq!(
+            vec![
+                Rectangle {
+                    x: (0, 9),
+                    y: (0, 9)
+                },
+                Rectangle {
+                    x: (20, 29),
+                    y: (5, 14)
+                },
+                Rectangle {
+                    x: (0, 9),
+                    y: (20, 29)
+                },
+            ],
+            range.rectangles().collect::<Vec<_>>()
+        );
+
+        let rebuilt = range
+            .rectangles()
+            .fold(RangeSet2D::empty(), |acc, rectangle| {
+                acc.union(&RangeSet2D::new_from_rectangle(
+                    rectangle.x.0..=rectangle.x.1,
+                    rectangle.y.0..=rectangle.y.1,
+                ))
+            });
+        assert_eq!(range, rebuilt);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use std::cmp::Ordering;
+
+use crate::{
+    integer::{Arithmetic, Bounded, NumericInteger},
+    wrapping::serial_cmp,
+    RangeSet,
+};
+
+/// A structure detecting the replayed sequence numbers within a sliding window, as the anti-replay window of IPsec but of any size.
+///
+/// The window holds the `size` values ending at the highest sequence number received, compared with `irange::wrapping::serial_cmp` so that the sequence numbers can wrap around.
+/// A sequence number older than the window is considered as replayed.
+/// The values leaving the window are evicted by batches, once the window has moved by its size.
+#[derive(Clone, Debug)]
+pub struct ReplayWindow<T: NumericInteger> {
+    size: T::DistanceType,
+    highest: Option<T>,
+    /// The values received, all from `evicted` to `highest` included.
+    seen: RangeSet<T>,
+    /// The start of the window at the last eviction.
+    evicted: T,
+}
+
+impl<T: NumericInteger> ReplayWindow<T> {
+    /// Create a new instance with a window of `size` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero or greater than half of the domain.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::replay::ReplayWindow;
+    ///
+    /// let mut window = ReplayWindow::<u32>::new(1024);
+    /// assert!(window.insert(u32::MAX - 1));
+    /// assert!(window.insert(3));
+    /// assert!(window.insert(u32::MAX));
+    ///
+    /// assert!(window.is_duplicate(u32::MAX));
+    /// assert!(!window.is_duplicate(1));
+    /// assert!(!window.insert(u32::MAX));
+    ///
+    /// assert!(window.insert(5000));
+    /// assert!(window.is_duplicate(3000));
+    /// ```
+    pub fn new(size: T::DistanceType) -> ReplayWindow<T> {
+        let one = T::DistanceType::one();
+        let half = T::distance(T::min_value(), T::max_value()).div_euclid(one + one) + one;
+        assert!(
+            size >= one && size <= half,
+            "the size of the window must be between 1 and {half}, got {size}"
+        );
+
+        ReplayWindow {
+            size,
+            highest: None,
+            seen: RangeSet::empty(),
+            evicted: T::min_value(),
+        }
+    }
+
+    /// Return the number of values of the window.
+    #[inline]
+    pub fn size(&self) -> T::DistanceType {
+        self.size
+    }
+
+    /// Return the highest sequence number received, `None` if none was.
+    #[inline]
+    pub fn highest(&self) -> Option<T> {
+        self.highest
+    }
+
+    /// Return `true` if the given sequence number was already received or is older than the window.
+    pub fn is_duplicate(&self, seq: T) -> bool {
+        let Some(highest) = self.highest else {
+            return false;
+        };
+        if T::distance(seq, highest) < self.size {
+            self.seen.contains(seq)
+        } else {
+            serial_cmp(highest, seq) != Some(Ordering::Less)
+        }
+    }
+
+    /// Record the given sequence number, return `false` if it is a duplicate.
+    pub fn insert(&mut self, seq: T) -> bool {
+        if self.is_duplicate(seq) {
+            return false;
+        }
+
+        let start = seq.sub_distance(self.size - T::DistanceType::one());
+        match self.highest {
+            None => self.evicted = start,
+            Some(highest) if serial_cmp(highest, seq) == Some(Ordering::Less) => {
+                if T::distance(highest, seq) >= self.size {
+                    self.seen = RangeSet::empty();
+                    self.evicted = start;
+                } else if T::distance(self.evicted, start) >= self.size {
+                    self.evict(start);
+                }
+            }
+            Some(_) => {
+                self.seen.entry(seq).or_insert();
+                return true;
+            }
+        }
+
+        self.highest = Some(seq);
+        self.seen.entry(seq).or_insert();
+        true
+    }
+
+    /// Forget the values received before `start`, the new start of the window.
+    fn evict(&mut self, start: T) {
+        let end = start.sub_distance(T::DistanceType::one());
+        self.seen = if self.evicted <= end {
+            self.seen.difference_range(self.evicted..=end)
+        } else {
+            self.seen
+                .difference_range(self.evicted..)
+                .difference_range(..=end)
+        };
+        self.evicted = start;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_insert() -> Result<(), String> {
+        let mut rng = StdRng::seed_from_u64(189);
+        for size in [1, 2, 7, 32, 128] {
+            let mut window = ReplayWindow::<u8>::new(size);
+            // The reference works on the unwrapped sequence numbers.
+            let mut highest: Option<i64> = None;
+            let mut received = HashSet::new();
+            let mut seq = 0i64;
+            for _ in 0..5000 {
+                seq += rng.gen_range(-40..=40);
+                let wrapped = seq.rem_euclid(256) as u8;
+                let absolute = match highest {
+                    None => seq,
+                    Some(highest) => {
+                        let ahead = wrapped.wrapping_sub(highest.rem_euclid(256) as u8);
+                        if ahead != 0 && ahead < 128 {
+                            highest + ahead as i64
+                        } else {
+                            highest - (256 - ahead as i64) % 256
+                        }
+                    }
+                };
+                seq = absolute;
+
+                let expected = highest
+                    .is_some_and(|h| absolute <= h - size as i64 || received.contains(&absolute));
+                assert_eq!(expected, window.is_duplicate(wrapped));
+                assert_eq!(!expected, window.insert(wrapped));
+                if !expected {
+                    received.insert(absolute);
+                    highest = Some(highest.map_or(absolute, |h| h.max(absolute)));
+                }
+                assert_eq!(highest.map(|h| h.rem_euclid(256) as u8), window.highest());
+                assert!(window.seen.0.len() <= 2 * (2 * size as usize + 2));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "the size of the window must be between 1 and 128, got 129")]
+    fn test_new_too_large() {
+        ReplayWindow::<i8>::new(129);
+    }
+}
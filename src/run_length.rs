@@ -0,0 +1,168 @@
+use crate::{integer::NumericInteger, RangeSet};
+
+/// A `RangeSet` encoded as `(start, extent)` pairs, where the extent is the number of values of the range minus one.
+///
+/// The extent is stored with the unsigned type of the same width, so every range fits including the total one.
+/// This encoding is better suited to delta or variable-length integer compression since the extents of small ranges are small numbers.
+///
+/// It is an encoding to store or send a set, the operations are done on the `RangeSet` it converts to and from.
+/// A `RangeSet` field can also be serialized with this encoding with `#[serde(with = "irange::serde_helpers::run_length")]`.
+/// When deserializing, the ranges must be sorted, neither overlapping nor adjacent, and must not overflow `T`.
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct RunLengthRangeSet<T: NumericInteger>(Vec<(T, T::DistanceType)>);
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for RunLengthRangeSet<T>
+where
+    T: NumericInteger + serde::Serialize,
+    T::DistanceType: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for RunLengthRangeSet<T>
+where
+    T: NumericInteger + serde::Deserialize<'de>,
+    T::DistanceType: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let runs = Vec::<(T, T::DistanceType)>::deserialize(deserializer)?;
+        if !is_canonical_runs(&runs) {
+            return Err(serde::de::Error::custom(
+                "the runs are not sorted ranges within the bounds of the type, neither overlapping nor adjacent",
+            ));
+        }
+
+        Ok(RunLengthRangeSet(runs))
+    }
+}
+
+/// Return `true` if the runs are sorted ranges that do not overflow `T`, neither overlapping nor adjacent.
+#[cfg(feature = "serde")]
+fn is_canonical_runs<T: NumericInteger>(runs: &[(T, T::DistanceType)]) -> bool {
+    let mut previous_max = None;
+    runs.iter().all(|&(start, extent)| {
+        let after_previous =
+            previous_max.is_none_or(|max: T| max < T::max_value() && max + T::one() < start);
+        let fits = extent <= T::distance(start, T::max_value());
+        previous_max = fits.then(|| start.add_distance(extent));
+        after_previous && fits
+    })
+}
+
+impl<T: NumericInteger> RunLengthRangeSet<T> {
+    /// Return the ranges in increasing order, as `(start, extent)` pairs.
+    #[inline]
+    pub fn runs(&self) -> &[(T, T::DistanceType)] {
+        &self.0
+    }
+
+    /// Return `true` if it contains the given value.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::run_length::RunLengthRangeSet;
+    ///
+    /// let range = RunLengthRangeSet::from(&RangeSet::<i8>(vec![-128, 127]));
+    /// assert_eq!(&[(-128, 255)], range.runs());
+    /// assert!(range.contains(0));
+    /// ```
+    pub fn contains(&self, value: T) -> bool {
+        let position = self.0.partition_point(|&(start, _)| start <= value);
+        position > 0 && {
+            let (start, extent) = self.0[position - 1];
+            T::distance(start, value) <= extent
+        }
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T: NumericInteger> From<&RangeSet<T>> for RunLengthRangeSet<T> {
+    fn from(value: &RangeSet<T>) -> Self {
+        RunLengthRangeSet(
+            value
+                .0
+                .chunks_exact(2)
+                .map(|bounds| (bounds[0], T::distance(bounds[0], bounds[1])))
+                .collect(),
+        )
+    }
+}
+
+impl<T: NumericInteger> From<&RunLengthRangeSet<T>> for RangeSet<T> {
+    fn from(value: &RunLengthRangeSet<T>) -> Self {
+        let mut bounds = Vec::with_capacity(value.0.len() * 2);
+        for &(start, extent) in &value.0 {
+            bounds.push(start);
+            bounds.push(start.add_distance(extent));
+        }
+        RangeSet(bounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> Result<(), String> {
+        let range = RangeSet::<i16>(vec![i16::MIN, -5, 0, 0, 10, i16::MAX]);
+        let encoded = RunLengthRangeSet::from(&range);
+        assert_eq!(&[(i16::MIN, 32763), (0, 0), (10, 32757)], encoded.runs());
+        assert_eq!(range, RangeSet::from(&encoded));
+
+        for value in [i16::MIN, -5, 0, 10, i16::MAX] {
+            assert!(encoded.contains(value));
+        }
+        for value in [-4, -1, 1, 9] {
+            assert!(!encoded.contains(value));
+        }
+
+        let total = RunLengthRangeSet::from(&RangeSet::<u64>::total());
+        assert_eq!(&[(0, u64::MAX)], total.runs());
+        assert!(RunLengthRangeSet::from(&RangeSet::<u64>::empty()).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() -> Result<(), String> {
+        let range = RangeSet::<i8>(vec![-128, -5, 0, 0, 10, 127]);
+        let encoded = RunLengthRangeSet::from(&range);
+        let json = serde_json::to_string(&encoded).map_err(|e| e.to_string())?;
+        assert_eq!("[[-128,123],[0,0],[10,117]]", json);
+        let decoded: RunLengthRangeSet<i8> =
+            serde_json::from_str(&json).map_err(|e| e.to_string())?;
+        assert_eq!(encoded, decoded);
+        assert_eq!(range, RangeSet::from(&decoded));
+
+        assert!(serde_json::from_str::<RunLengthRangeSet<i8>>("[]").is_ok());
+        for invalid in [
+            // The extent overflows.
+            "[[10,118]]",
+            // Unsorted.
+            "[[10,0],[0,0]]",
+            // Overlapping.
+            "[[0,5],[5,0]]",
+            // Adjacent.
+            "[[0,5],[6,0]]",
+            // After a range ending at the maximum.
+            "[[120,7],[127,0]]",
+        ] {
+            assert!(serde_json::from_str::<RunLengthRangeSet<i8>>(invalid).is_err());
+        }
+
+        Ok(())
+    }
+}
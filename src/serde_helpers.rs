@@ -0,0 +1,484 @@
+//! Adapters to use with `#[serde(with = "...")]` on `RangeSet` fields.
+
+use std::{fmt::Formatter, marker::PhantomData};
+
+use serde::de::{Error, IgnoredAny, IntoDeserializer, SeqAccess, Visitor};
+
+use crate::{integer::NumericInteger, is_canonical, range::AnyRange, RangeSet};
+
+const NOT_CANONICAL: &str =
+    "the bounds are not sorted pairs of ranges, neither overlapping nor adjacent";
+
+/// Serialize the bounds of a `RangeSet` as decimal strings.
+///
+/// Useful for `RangeSet<u128>` and `RangeSet<i128>` since many formats (JSON consumed by JavaScript for instance) cannot represent 128-bit integers losslessly.
+///
+/// When deserializing, the bounds must already be sorted pairs of ranges, neither overlapping nor adjacent.
+///
+/// # Example:
+///
+/// ```
+/// use irange::RangeSet;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "irange::serde_helpers::decimal")]
+///     ids: RangeSet<u128>,
+/// }
+///
+/// let config = Config {
+///     ids: RangeSet::new_from_range(3..=u128::MAX),
+/// };
+///
+/// let serialized = serde_json::to_string(&config).unwrap();
+/// assert_eq!(
+///     r#"{"ids":["3","340282366920938463463374607431768211455"]}"#,
+///     serialized
+/// );
+/// ```
+pub mod decimal {
+    use std::{fmt::Display, str::FromStr};
+
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::{integer::NumericInteger, is_canonical, RangeSet};
+
+    pub fn serialize<T, S>(range_set: &RangeSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: NumericInteger,
+        S: Serializer,
+    {
+        serializer.collect_seq(range_set.0.iter().map(|bound| bound.to_string()))
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<RangeSet<T>, D::Error>
+    where
+        T: NumericInteger + FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        let bounds = Vec::<String>::deserialize(deserializer)?;
+        let bounds = bounds
+            .iter()
+            .map(|bound| bound.parse::<T>().map_err(D::Error::custom))
+            .collect::<Result<Vec<T>, _>>()?;
+        if !is_canonical(&bounds) {
+            return Err(D::Error::custom(super::NOT_CANONICAL));
+        }
+
+        Ok(RangeSet(bounds))
+    }
+}
+
+/// Serialize a `RangeSet` as a human readable string of comma separated values and ranges, such as `"1-5,9,20-30"`.
+///
+/// When deserializing, the ranges can be given in any order and can overlap.
+///
+/// # Example:
+///
+/// ```
+/// use irange::RangeSet;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "irange::serde_helpers::string")]
+///     ports: RangeSet<u16>,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{"ports":"8080, 20-22, 21-25"}"#).unwrap();
+/// assert_eq!(RangeSet(vec![20, 25, 8080, 8080]), config.ports);
+///
+/// let serialized = serde_json::to_string(&config).unwrap();
+/// assert_eq!(r#"{"ports":"20-25,8080"}"#, serialized);
+/// ```
+pub mod string {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::{integer::NumericInteger, RangeSet};
+
+    pub fn serialize<T, S>(range_set: &RangeSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: NumericInteger,
+        S: Serializer,
+    {
+        serializer.serialize_str(&range_set.to_compact_string())
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<RangeSet<T>, D::Error>
+    where
+        T: NumericInteger,
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+        RangeSet::parse_compact(&string).map_err(D::Error::custom)
+    }
+}
+
+/// Serialize a `RangeSet` as an array of `[min, max]` pairs.
+///
+/// When deserializing, the ranges can be given in any order and can overlap.
+///
+/// # Example:
+///
+/// ```
+/// use irange::RangeSet;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "irange::serde_helpers::pairs")]
+///     ports: RangeSet<u16>,
+/// }
+///
+/// let config = Config {
+///     ports: RangeSet(vec![20, 25, 8080, 8080]),
+/// };
+///
+/// let serialized = serde_json::to_string(&config).unwrap();
+/// assert_eq!(r#"{"ports":[[20,25],[8080,8080]]}"#, serialized);
+/// ```
+pub mod pairs {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{integer::NumericInteger, range::AnyRange, RangeSet};
+
+    pub fn serialize<T, S>(range_set: &RangeSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: NumericInteger + Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(
+            range_set
+                .0
+                .chunks_exact(2)
+                .map(|range| (range[0], range[1])),
+        )
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<RangeSet<T>, D::Error>
+    where
+        T: NumericInteger + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let ranges = Vec::<(T, T)>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(min, max)| AnyRange::try_new(min, max).map_err(D::Error::custom))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(RangeSet::new_from_ranges(&ranges))
+    }
+}
+
+/// Serialize a `RangeSet` as the flat array of its bounds, like the `Serialize` implementation does for binary formats.
+///
+/// When deserializing, the bounds must already be sorted pairs of ranges, neither overlapping nor adjacent.
+///
+/// # Example:
+///
+/// ```
+/// use irange::RangeSet;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct Config {
+///     #[serde(with = "irange::serde_helpers::raw")]
+///     ports: RangeSet<u16>,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{"ports":[20,25,8080,8080]}"#).unwrap();
+/// assert_eq!(RangeSet(vec![20, 25, 8080, 8080]), config.ports);
+///
+/// assert!(serde_json::from_str::<Config>(r#"{"ports":[20,25,26,30]}"#).is_err());
+/// ```
+pub mod raw {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{integer::NumericInteger, is_canonical, RangeSet};
+
+    pub fn serialize<T, S>(range_set: &RangeSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: NumericInteger + Serialize,
+        S: Serializer,
+    {
+        range_set.0.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<RangeSet<T>, D::Error>
+    where
+        T: NumericInteger + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let bounds = Vec::<T>::deserialize(deserializer)?;
+        if !is_canonical(&bounds) {
+            return Err(D::Error::custom(super::NOT_CANONICAL));
+        }
+
+        Ok(RangeSet(bounds))
+    }
+}
+
+/// Serialize a `RangeSet` as an array of `[start, extent]` pairs, the extent being the number of values of the range minus one, see `irange::run_length::RunLengthRangeSet`.
+///
+/// When deserializing, the ranges must be sorted, neither overlapping nor adjacent, and must not overflow `T`.
+///
+/// # Example:
+///
+/// ```
+/// use irange::RangeSet;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct Config {
+///     #[serde(with = "irange::serde_helpers::run_length")]
+///     ports: RangeSet<u16>,
+/// }
+///
+/// let config = Config {
+///     ports: RangeSet(vec![20, 25, 8080, 8080]),
+/// };
+///
+/// let serialized = serde_json::to_string(&config).unwrap();
+/// assert_eq!(r#"{"ports":[[20,5],[8080,0]]}"#, serialized);
+///
+/// assert!(serde_json::from_str::<Config>(r#"{"ports":[[20,5],[26,4]]}"#).is_err());
+/// ```
+pub mod run_length {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{integer::NumericInteger, run_length::RunLengthRangeSet, RangeSet};
+
+    pub fn serialize<T, S>(range_set: &RangeSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: NumericInteger + Serialize,
+        T::DistanceType: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(
+            range_set
+                .0
+                .chunks_exact(2)
+                .map(|range| (range[0], T::distance(range[0], range[1]))),
+        )
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<RangeSet<T>, D::Error>
+    where
+        T: NumericInteger + Deserialize<'de>,
+        T::DistanceType: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let runs = RunLengthRangeSet::deserialize(deserializer)?;
+        Ok(RangeSet::from(&runs))
+    }
+}
+
+/// An element of the arrays accepted by the `Deserialize` implementation in human readable formats:
+/// a `[min, max]` pair, or a bound of the flat array written before version 2.0.
+enum PairOrBound<T> {
+    Pair(T, T),
+    Bound(T),
+}
+
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for PairOrBound<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(PairOrBoundVisitor(PhantomData))
+    }
+}
+
+struct PairOrBoundVisitor<T>(PhantomData<T>);
+
+impl<T> PairOrBoundVisitor<T> {
+    fn bound<'de, V, E>(value: V) -> Result<PairOrBound<T>, E>
+    where
+        T: serde::Deserialize<'de>,
+        V: IntoDeserializer<'de, E>,
+        E: Error,
+    {
+        T::deserialize(value.into_deserializer()).map(PairOrBound::Bound)
+    }
+}
+
+impl<'de, T: serde::Deserialize<'de>> Visitor<'de> for PairOrBoundVisitor<T> {
+    type Value = PairOrBound<T>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a [min, max] pair or a bound")
+    }
+
+    fn visit_i64<E: Error>(self, value: i64) -> Result<Self::Value, E> {
+        Self::bound(value)
+    }
+
+    fn visit_u64<E: Error>(self, value: u64) -> Result<Self::Value, E> {
+        Self::bound(value)
+    }
+
+    fn visit_i128<E: Error>(self, value: i128) -> Result<Self::Value, E> {
+        Self::bound(value)
+    }
+
+    fn visit_u128<E: Error>(self, value: u128) -> Result<Self::Value, E> {
+        Self::bound(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let min = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let max = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(Error::invalid_length(3, &self));
+        }
+
+        Ok(PairOrBound::Pair(min, max))
+    }
+}
+
+/// Deserialize an array of `[min, max]` pairs like `pairs`, or the flat array of bounds like `raw`.
+///
+/// The bounds of the flat array are read through `deserialize_any`,
+/// so 128-bit bounds outside of the 64-bit range are only accepted by the formats supporting them.
+pub(crate) fn deserialize_pairs_or_raw<'de, T, D>(deserializer: D) -> Result<RangeSet<T>, D::Error>
+where
+    T: NumericInteger + serde::Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    let elements = <Vec<PairOrBound<T>> as serde::Deserialize>::deserialize(deserializer)?;
+    if let Some(PairOrBound::Bound(_)) = elements.first() {
+        let bounds = elements
+            .into_iter()
+            .map(|element| match element {
+                PairOrBound::Bound(bound) => Ok(bound),
+                PairOrBound::Pair(..) => Err(D::Error::custom(
+                    "expected a flat array of bounds, found a pair",
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if !is_canonical(&bounds) {
+            return Err(D::Error::custom(NOT_CANONICAL));
+        }
+        Ok(RangeSet(bounds))
+    } else {
+        let ranges = elements
+            .into_iter()
+            .map(|element| match element {
+                PairOrBound::Pair(min, max) => {
+                    AnyRange::try_new(min, max).map_err(D::Error::custom)
+                }
+                PairOrBound::Bound(_) => Err(D::Error::custom(
+                    "expected an array of pairs, found a bound",
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(RangeSet::new_from_ranges(&ranges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::RangeSet;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Decimal<T: crate::integer::NumericInteger + std::str::FromStr>
+    where
+        T::Err: std::fmt::Display,
+    {
+        #[serde(with = "super::decimal")]
+        set: RangeSet<T>,
+    }
+
+    #[test]
+    fn test_decimal() -> Result<(), String> {
+        let value = Decimal {
+            set: RangeSet::<i128>(vec![i128::MIN, -3, 7, i128::MAX]),
+        };
+        let serialized = serde_json::to_string(&value).unwrap();
+        assert_eq!(
+            r#"{"set":["-170141183460469231731687303715884105728","-3","7","170141183460469231731687303715884105727"]}"#,
+            serialized
+        );
+        let unserialized: Decimal<i128> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(value, unserialized);
+
+        let value = Decimal {
+            set: RangeSet::<u128>::empty(),
+        };
+        let serialized = serde_json::to_string(&value).unwrap();
+        assert_eq!(r#"{"set":[]}"#, serialized);
+        let unserialized: Decimal<u128> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(value, unserialized);
+
+        assert!(serde_json::from_str::<Decimal<u8>>(r#"{"set":["3","256"]}"#).is_err());
+        assert!(serde_json::from_str::<Decimal<u8>>(r#"{"set":["5","3"]}"#).is_err());
+        assert!(serde_json::from_str::<Decimal<u8>>(r#"{"set":["1","3","4","5"]}"#).is_err());
+        assert!(serde_json::from_str::<Decimal<u8>>(r#"{"set":["1"]}"#).is_err());
+
+        Ok(())
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Representations {
+        #[serde(with = "super::string")]
+        string: RangeSet<i16>,
+        #[serde(with = "super::pairs")]
+        pairs: RangeSet<i16>,
+        #[serde(with = "super::raw")]
+        raw: RangeSet<i16>,
+    }
+
+    #[test]
+    fn test_representations() -> Result<(), String> {
+        let set = RangeSet::<i16>(vec![-10, -5, 0, 0, 3, 7]);
+        let value = Representations {
+            string: set.clone(),
+            pairs: set.clone(),
+            raw: set.clone(),
+        };
+        let serialized = serde_json::to_string(&value).unwrap();
+        assert_eq!(
+            r#"{"string":"-10--5,0,3-7","pairs":[[-10,-5],[0,0],[3,7]],"raw":[-10,-5,0,0,3,7]}"#,
+            serialized
+        );
+        let unserialized: Representations = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(value, unserialized);
+
+        let unserialized: Representations = serde_json::from_str(
+            r#"{"string":" 3 - 7, -1, 1, 0 ,, -10 - -5 ","pairs":[[3,7],[-1,1]],"raw":[]}"#,
+        )
+        .unwrap();
+        assert_eq!(RangeSet(vec![-10, -5, -1, 1, 3, 7]), unserialized.string);
+        assert_eq!(RangeSet(vec![-1, 1, 3, 7]), unserialized.pairs);
+        assert!(unserialized.raw.is_empty());
+
+        let value = Representations {
+            string: RangeSet::empty(),
+            pairs: RangeSet::empty(),
+            raw: RangeSet::empty(),
+        };
+        let serialized = serde_json::to_string(&value).unwrap();
+        assert_eq!(r#"{"string":"","pairs":[],"raw":[]}"#, serialized);
+        let unserialized: Representations = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(value, unserialized);
+
+        for invalid in [
+            r#"{"string":"7-3","pairs":[],"raw":[]}"#,
+            r#"{"string":"1-x","pairs":[],"raw":[]}"#,
+            r#"{"string":"é-5","pairs":[],"raw":[]}"#,
+            r#"{"string":"1,é","pairs":[],"raw":[]}"#,
+            r#"{"string":"","pairs":[[7,3]],"raw":[]}"#,
+            r#"{"string":"","pairs":[],"raw":[7,3]}"#,
+            r#"{"string":"","pairs":[],"raw":[1,3,2,5]}"#,
+            r#"{"string":"","pairs":[],"raw":[1]}"#,
+        ] {
+            assert!(serde_json::from_str::<Representations>(invalid).is_err());
+        }
+
+        Ok(())
+    }
+}
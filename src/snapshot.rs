@@ -0,0 +1,133 @@
+use std::{
+    ops::RangeBounds,
+    sync::{Arc, Mutex},
+};
+
+use arc_swap::ArcSwap;
+
+use crate::{integer::NumericInteger, RangeSet};
+
+/// A `RangeSet` shared between threads, optimized for workloads with many more reads than writes.
+///
+/// The reads are lock-free and never wait for a writer, they see the snapshot that was current when they started.
+/// The writers are serialized and replace the whole set with a modified copy.
+#[derive(Debug)]
+pub struct SnapshotRangeSet<T: NumericInteger> {
+    current: ArcSwap<RangeSet<T>>,
+    writer: Mutex<()>,
+}
+
+impl<T: NumericInteger> SnapshotRangeSet<T> {
+    /// Create a new instance holding the given `RangeSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use std::{sync::Arc, thread};
+    /// use irange::RangeSet;
+    /// use irange::snapshot::SnapshotRangeSet;
+    ///
+    /// let blocked = Arc::new(SnapshotRangeSet::new(RangeSet::<u32>::new_from_range(0..10)));
+    ///
+    /// let reader = {
+    ///     let blocked = blocked.clone();
+    ///     thread::spawn(move || blocked.contains(5))
+    /// };
+    /// blocked.insert_range(20..30);
+    ///
+    /// assert!(reader.join().unwrap());
+    /// assert_eq!(RangeSet(vec![0, 9, 20, 29]), *blocked.load());
+    /// ```
+    pub fn new(set: RangeSet<T>) -> SnapshotRangeSet<T> {
+        SnapshotRangeSet {
+            current: ArcSwap::from_pointee(set),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Return the current snapshot, which is not affected by the later writes.
+    #[inline]
+    pub fn load(&self) -> Arc<RangeSet<T>> {
+        self.current.load_full()
+    }
+
+    /// Return `true` if the current snapshot contains the given value.
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.current.load().contains(value)
+    }
+
+    /// Replace the current snapshot with the result of `f` applied on it, and return the new snapshot.
+    ///
+    /// The writers are serialized, so no concurrent update is lost.
+    pub fn update<F: FnOnce(&RangeSet<T>) -> RangeSet<T>>(&self, f: F) -> Arc<RangeSet<T>> {
+        let _guard = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let new = Arc::new(f(&self.current.load()));
+        self.current.store(new.clone());
+        new
+    }
+
+    /// Replace the current snapshot with the given `RangeSet`.
+    pub fn store(&self, set: RangeSet<T>) {
+        self.update(|_| set);
+    }
+
+    /// Add the values of the given range.
+    pub fn insert_range<R: RangeBounds<T>>(&self, range: R) {
+        self.update(|set| set.union_range(range));
+    }
+
+    /// Remove the values of the given range.
+    pub fn remove_range<R: RangeBounds<T>>(&self, range: R) {
+        self.update(|set| set.difference_range(range));
+    }
+}
+
+impl<T: NumericInteger> From<RangeSet<T>> for SnapshotRangeSet<T> {
+    fn from(value: RangeSet<T>) -> Self {
+        SnapshotRangeSet::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn test_concurrent_writes() -> Result<(), String> {
+        let set = Arc::new(SnapshotRangeSet::from(RangeSet::<u32>::empty()));
+        let snapshot = set.load();
+
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let set = set.clone();
+                thread::spawn(move || {
+                    for j in 0..100 {
+                        let value = i * 1000 + j * 2;
+                        set.insert_range(value..=value);
+                        assert!(set.contains(value));
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().map_err(|_| "a writer panicked")?;
+        }
+
+        assert!(snapshot.is_empty());
+        let set = set.load();
+        assert_eq!(800, set.0.len() / 2);
+        for i in 0..8 {
+            for j in 0..100 {
+                assert!(set.contains(i * 1000 + j * 2));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use core::fmt::Write;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{integer::NumericInteger, RangeSet};
+
+/// The style of the placeholders of a parameterized predicate.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Placeholder {
+    /// `?`, used by MySQL and SQLite.
+    QuestionMark,
+    /// `$1`, `$2`, ..., used by Postgres, numbered from the given index.
+    Dollar(usize),
+}
+
+/// A builder rendering a `RangeSet` as a SQL predicate such as `(id BETWEEN 1 AND 5 OR id IN (7, 9))`.
+///
+/// The ranges of at most `in_threshold` values are listed in a single `IN`, the others are rendered with `BETWEEN`.
+///
+/// The column is written as is, without quoting nor escaping: it must be a trusted identifier, quoted by the caller if needed, and never come from user input.
+#[derive(Clone, Debug)]
+pub struct SqlPredicate<'a> {
+    column: &'a str,
+    in_threshold: u128,
+    placeholder: Placeholder,
+}
+
+/// The largest `in_threshold` accepted, to keep the `IN` list proportional to the number of ranges.
+pub const MAX_IN_THRESHOLD: u128 = 1024;
+
+impl<'a> SqlPredicate<'a> {
+    /// Create a new builder for the given trusted column, listing the ranges of at most 2 values in the `IN` and using `?` placeholders.
+    pub fn new(column: &'a str) -> SqlPredicate<'a> {
+        SqlPredicate {
+            column,
+            in_threshold: 2,
+            placeholder: Placeholder::QuestionMark,
+        }
+    }
+
+    /// Set the number of values up to which a range is listed in the `IN` instead of being rendered with `BETWEEN`.
+    ///
+    /// The threshold is capped at `MAX_IN_THRESHOLD`.
+    pub fn in_threshold(mut self, in_threshold: u128) -> SqlPredicate<'a> {
+        self.in_threshold = in_threshold.min(MAX_IN_THRESHOLD);
+        self
+    }
+
+    /// Set the style of the placeholders used by `render_parameterized`.
+    pub fn placeholder(mut self, placeholder: Placeholder) -> SqlPredicate<'a> {
+        self.placeholder = placeholder;
+        self
+    }
+
+    /// Render the predicate with the values written inline.
+    ///
+    /// An empty set is rendered as `FALSE`, the other ones only with `BETWEEN`, `=` and `IN`, even at the bounds of the domain.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::sql::SqlPredicate;
+    ///
+    /// let ids = RangeSet::<u32>(vec![1, 100, 200, 201, 300, 300]);
+    ///
+    /// assert_eq!(
+    ///     "(id BETWEEN 1 AND 100 OR id IN (200, 201, 300))",
+    ///     SqlPredicate::new("id").render(&ids)
+    /// );
+    /// assert_eq!(
+    ///     "(id BETWEEN 1 AND 100 OR id BETWEEN 200 AND 201 OR id = 300)",
+    ///     SqlPredicate::new("id").in_threshold(1).render(&ids)
+    /// );
+    /// ```
+    pub fn render<T: NumericInteger>(&self, set: &RangeSet<T>) -> String {
+        self.render_with(set, |value| value.to_string())
+    }
+
+    /// Render the predicate with placeholders, returning it with the values to bind in order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::sql::{Placeholder, SqlPredicate};
+    ///
+    /// let ids = RangeSet::<i64>(vec![1, 100, 200, 200, 300, i64::MAX]);
+    ///
+    /// let (predicate, values) = SqlPredicate::new("id")
+    ///     .placeholder(Placeholder::Dollar(2))
+    ///     .render_parameterized(&ids);
+    /// assert_eq!("(id BETWEEN $2 AND $3 OR id BETWEEN $4 AND $5 OR id = $6)", predicate);
+    /// assert_eq!(vec![1, 100, 300, i64::MAX, 200], values);
+    /// ```
+    pub fn render_parameterized<T: NumericInteger>(&self, set: &RangeSet<T>) -> (String, Vec<T>) {
+        let mut values = vec![];
+        let predicate = self.render_with(set, |value| {
+            values.push(value);
+            match self.placeholder {
+                Placeholder::QuestionMark => "?".to_string(),
+                Placeholder::Dollar(first) => format!("${}", first + values.len() - 1),
+            }
+        });
+        (predicate, values)
+    }
+
+    fn render_with<T: NumericInteger>(
+        &self,
+        set: &RangeSet<T>,
+        mut value: impl FnMut(T) -> String,
+    ) -> String {
+        if set.is_empty() {
+            return "FALSE".to_string();
+        }
+
+        let column = self.column;
+        let mut terms = vec![];
+        let mut listed = vec![];
+        for range in set.0.chunks_exact(2) {
+            let (min, max) = (range[0], range[1]);
+            if T::distance_to_u128(T::distance(min, max)) < self.in_threshold {
+                let mut current = min;
+                loop {
+                    listed.push(current);
+                    if current == max {
+                        break;
+                    }
+                    current += T::one();
+                }
+            } else {
+                terms.push(format!(
+                    "{column} BETWEEN {} AND {}",
+                    value(min),
+                    value(max)
+                ));
+            }
+        }
+
+        match listed[..] {
+            [] => {}
+            [single] => terms.push(format!("{column} = {}", value(single))),
+            _ => {
+                let mut term = format!("{column} IN (");
+                for (i, &listed) in listed.iter().enumerate() {
+                    if i > 0 {
+                        term.push_str(", ");
+                    }
+                    let _ = write!(term, "{}", value(listed));
+                }
+                term.push(')');
+                terms.push(term);
+            }
+        }
+
+        if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            format!("({})", terms.join(" OR "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() -> Result<(), String> {
+        assert_eq!("FALSE", RangeSet::<u8>::empty().to_sql_predicate("x"));
+        assert_eq!(
+            "x BETWEEN 0 AND 255",
+            RangeSet::<u8>::total().to_sql_predicate("x")
+        );
+        assert_eq!(
+            "x BETWEEN 0 AND 65535",
+            SqlPredicate::new("x")
+                .in_threshold(u128::MAX)
+                .render(&RangeSet::<u16>::total())
+        );
+        assert_eq!("x = 5", RangeSet::<u8>(vec![5, 5]).to_sql_predicate("x"));
+        assert_eq!(
+            "x IN (5, 6)",
+            RangeSet::<u8>(vec![5, 6]).to_sql_predicate("x")
+        );
+        assert_eq!(
+            "x BETWEEN 5 AND 7",
+            RangeSet::<u8>(vec![5, 7]).to_sql_predicate("x")
+        );
+        assert_eq!(
+            "x IN (0, 255)",
+            RangeSet::<u8>(vec![0, 0, 255, 255]).to_sql_predicate("x")
+        );
+        assert_eq!(
+            "(\"t\".\"x\" BETWEEN -128 AND -1 OR \"t\".\"x\" IN (3, 4, 5))",
+            SqlPredicate::new("\"t\".\"x\"")
+                .in_threshold(10)
+                .render(&RangeSet::<i8>(vec![-128, -1, 3, 5]))
+        );
+        assert_eq!(
+            "(x BETWEEN 1 AND 2 OR x BETWEEN 4 AND 4)",
+            SqlPredicate::new("x")
+                .in_threshold(0)
+                .render(&RangeSet::<u8>(vec![1, 2, 4, 4]))
+        );
+
+        assert_eq!(
+            ("FALSE".to_string(), vec![]),
+            RangeSet::<u8>::empty().to_sql_predicate_parameterized("x")
+        );
+        assert_eq!(
+            (
+                "(x BETWEEN ? AND ? OR x IN (?, ?))".to_string(),
+                vec![10, 20, 1, 3]
+            ),
+            RangeSet::<u64>(vec![1, 1, 3, 3, 10, 20]).to_sql_predicate_parameterized("x")
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,379 @@
+use core::{iter::Peekable, ops::RangeInclusive};
+
+use crate::integer::NumericInteger;
+
+/// An iterator over the ranges of the union of two sorted streams of ranges, computed lazily.
+///
+/// Constructed with `merge_union` or `RangeSet::union_ranges_iter`.
+pub struct Union<
+    T: NumericInteger,
+    A: Iterator<Item = RangeInclusive<T>>,
+    B: Iterator<Item = RangeInclusive<T>>,
+> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+    /// The range being extended with the following ones that touch it.
+    pending: Option<(T, T)>,
+}
+
+impl<
+        T: NumericInteger,
+        A: Iterator<Item = RangeInclusive<T>>,
+        B: Iterator<Item = RangeInclusive<T>>,
+    > Union<T, A, B>
+{
+    pub(crate) fn new(a: A, b: B) -> Union<T, A, B> {
+        Union {
+            a: a.peekable(),
+            b: b.peekable(),
+            pending: None,
+        }
+    }
+}
+
+impl<
+        T: NumericInteger,
+        A: Iterator<Item = RangeInclusive<T>>,
+        B: Iterator<Item = RangeInclusive<T>>,
+    > Iterator for Union<T, A, B>
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next = match (self.a.peek(), self.b.peek()) {
+                (Some(a), Some(b)) if b.start() < a.start() => self.b.next(),
+                (Some(_), _) => self.a.next(),
+                (None, _) => self.b.next(),
+            };
+            let Some(next) = next else {
+                return self.pending.take().map(|(min, max)| min..=max);
+            };
+            if next.is_empty() {
+                continue;
+            }
+
+            let (min, max) = next.into_inner();
+            match &mut self.pending {
+                Some((_, pending_max))
+                    if *pending_max == T::max_value() || min <= *pending_max + T::one() =>
+                {
+                    if max > *pending_max {
+                        *pending_max = max;
+                    }
+                }
+                pending => {
+                    if let Some((pending_min, pending_max)) = pending.replace((min, max)) {
+                        return Some(pending_min..=pending_max);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// An iterator over the ranges of the intersection of two sorted streams of ranges, computed lazily.
+///
+/// Constructed with `merge_intersection` or `RangeSet::intersection_ranges_iter`.
+pub struct Intersection<
+    T: NumericInteger,
+    A: Iterator<Item = RangeInclusive<T>>,
+    B: Iterator<Item = RangeInclusive<T>>,
+> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<
+        T: NumericInteger,
+        A: Iterator<Item = RangeInclusive<T>>,
+        B: Iterator<Item = RangeInclusive<T>>,
+    > Intersection<T, A, B>
+{
+    pub(crate) fn new(a: A, b: B) -> Intersection<T, A, B> {
+        Intersection {
+            a: a.peekable(),
+            b: b.peekable(),
+        }
+    }
+}
+
+impl<
+        T: NumericInteger,
+        A: Iterator<Item = RangeInclusive<T>>,
+        B: Iterator<Item = RangeInclusive<T>>,
+    > Iterator for Intersection<T, A, B>
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (a, b) = (self.a.peek()?, self.b.peek()?);
+            let min = core::cmp::max(*a.start(), *b.start());
+            let max = core::cmp::min(*a.end(), *b.end());
+            // Only the range ending first cannot have common values with the next ones.
+            match a.end().cmp(b.end()) {
+                core::cmp::Ordering::Less => {
+                    self.a.next();
+                }
+                core::cmp::Ordering::Greater => {
+                    self.b.next();
+                }
+                core::cmp::Ordering::Equal => {
+                    self.a.next();
+                    self.b.next();
+                }
+            }
+            if min <= max {
+                return Some(min..=max);
+            }
+        }
+    }
+}
+
+/// An iterator over the ranges of the difference of two sorted streams of ranges, computed lazily.
+///
+/// Constructed with `merge_difference` or `RangeSet::difference_ranges_iter`.
+pub struct Difference<
+    T: NumericInteger,
+    A: Iterator<Item = RangeInclusive<T>>,
+    B: Iterator<Item = RangeInclusive<T>>,
+> {
+    a: A,
+    b: Peekable<B>,
+    /// The values of the current range of `a` not handled yet.
+    current: Option<(T, T)>,
+}
+
+impl<
+        T: NumericInteger,
+        A: Iterator<Item = RangeInclusive<T>>,
+        B: Iterator<Item = RangeInclusive<T>>,
+    > Difference<T, A, B>
+{
+    pub(crate) fn new(a: A, b: B) -> Difference<T, A, B> {
+        Difference {
+            a,
+            b: b.peekable(),
+            current: None,
+        }
+    }
+}
+
+impl<
+        T: NumericInteger,
+        A: Iterator<Item = RangeInclusive<T>>,
+        B: Iterator<Item = RangeInclusive<T>>,
+    > Iterator for Difference<T, A, B>
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (min, max) = match self.current.take() {
+                Some(current) => current,
+                None => {
+                    let next = self.a.next()?;
+                    if next.is_empty() {
+                        continue;
+                    }
+                    next.into_inner()
+                }
+            };
+
+            while self.b.next_if(|b| *b.end() < min || b.is_empty()).is_some() {}
+            let Some(b) = self.b.peek() else {
+                return Some(min..=max);
+            };
+            let (b_min, b_max) = (*b.start(), *b.end());
+            if b_min > max {
+                return Some(min..=max);
+            }
+
+            // The range of `b` overlaps, so the values after it are handled with the next one.
+            if b_max < max {
+                self.current = Some((b_max + T::one(), max));
+                self.b.next();
+            }
+            if b_min > min {
+                return Some(min..=b_min - T::one());
+            }
+        }
+    }
+}
+
+/// An iterator over the values of a sorted stream of ranges.
+///
+/// Constructed with `values`, `RangeSet::union_iter`, `RangeSet::intersection_iter` or `RangeSet::difference_iter`.
+pub struct Values<T: NumericInteger, I: Iterator<Item = RangeInclusive<T>>> {
+    ranges: I,
+    /// The values of the current range not yielded yet.
+    current: Option<(T, T)>,
+}
+
+impl<T: NumericInteger, I: Iterator<Item = RangeInclusive<T>>> Values<T, I> {
+    pub(crate) fn new(ranges: I) -> Values<T, I> {
+        Values {
+            ranges,
+            current: None,
+        }
+    }
+}
+
+impl<T: NumericInteger, I: Iterator<Item = RangeInclusive<T>>> Iterator for Values<T, I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, max) = match self.current {
+            Some(current) => current,
+            None => loop {
+                let range = self.ranges.next()?;
+                if !range.is_empty() {
+                    break range.into_inner();
+                }
+            },
+        };
+
+        self.current = if min < max {
+            Some((min + T::one(), max))
+        } else {
+            None
+        };
+        Some(min)
+    }
+}
+
+/// Return an iterator over the ranges of the union of two streams of ranges, computed lazily.
+///
+/// The ranges of each stream must be sorted by their start, they can overlap or touch.
+/// The ranges returned are sorted and separated by at least one value, as in a `RangeSet`.
+///
+/// # Example:
+///
+/// ```
+/// use irange::stream::merge_union;
+///
+/// let evens = (0..5u32).map(|i| i * 20..=i * 20 + 9);
+/// let odds = [5..=12u32, 95..=99];
+///
+/// assert_eq!(
+///     vec![0..=12, 20..=29, 40..=49, 60..=69, 80..=89, 95..=99],
+///     merge_union(evens, odds).collect::<Vec<_>>()
+/// );
+/// ```
+pub fn merge_union<T: NumericInteger, A, B>(a: A, b: B) -> Union<T, A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator<Item = RangeInclusive<T>>,
+    B: IntoIterator<Item = RangeInclusive<T>>,
+{
+    Union::new(a.into_iter(), b.into_iter())
+}
+
+/// Return an iterator over the ranges of the intersection of two streams of ranges, computed lazily.
+///
+/// The ranges of each stream must be sorted and disjoint.
+///
+/// # Example:
+///
+/// ```
+/// use irange::stream::{merge_intersection, merge_union};
+///
+/// let a = [0..=9u32, 20..=29];
+/// let b = [5..=24u32];
+/// let c = [8..=8u32, 22..=40];
+///
+/// // The pipeline never builds a `RangeSet`.
+/// let result = merge_union(merge_intersection(a, b), c);
+/// assert_eq!(vec![5..=9, 20..=40], result.collect::<Vec<_>>());
+/// ```
+pub fn merge_intersection<T: NumericInteger, A, B>(
+    a: A,
+    b: B,
+) -> Intersection<T, A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator<Item = RangeInclusive<T>>,
+    B: IntoIterator<Item = RangeInclusive<T>>,
+{
+    Intersection::new(a.into_iter(), b.into_iter())
+}
+
+/// Return an iterator over the ranges of the difference of two streams of ranges, computed lazily.
+///
+/// The ranges of each stream must be sorted and disjoint.
+///
+/// # Example:
+///
+/// ```
+/// use irange::stream::merge_difference;
+///
+/// let a = [0..=9u32, 20..=29];
+/// let b = [5..=24u32];
+///
+/// assert_eq!(vec![0..=4, 25..=29], merge_difference(a, b).collect::<Vec<_>>());
+/// ```
+pub fn merge_difference<T: NumericInteger, A, B>(
+    a: A,
+    b: B,
+) -> Difference<T, A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator<Item = RangeInclusive<T>>,
+    B: IntoIterator<Item = RangeInclusive<T>>,
+{
+    Difference::new(a.into_iter(), b.into_iter())
+}
+
+/// Return an iterator over the values of a stream of ranges, which must be sorted and disjoint.
+///
+/// # Example:
+///
+/// ```
+/// use irange::stream::values;
+///
+/// assert_eq!(vec![1, 2, 3, 7], values([1..=3u8, 7..=7]).collect::<Vec<_>>());
+/// ```
+pub fn values<T: NumericInteger, I: IntoIterator<Item = RangeInclusive<T>>>(
+    ranges: I,
+) -> Values<T, I::IntoIter> {
+    Values::new(ranges.into_iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_merge() -> Result<(), String> {
+        let empty = Vec::<RangeInclusive<u8>>::new();
+        assert_eq!(0, merge_union(empty.clone(), empty.clone()).count());
+        assert_eq!(0, merge_intersection(empty.clone(), [0..=255u8]).count());
+        assert_eq!(
+            vec![0..=255],
+            merge_difference([0..=255u8], empty.clone()).collect::<Vec<_>>()
+        );
+
+        // Overlapping and empty ranges in a stream sorted by start.
+        assert_eq!(
+            vec![0..=30, 40..=255],
+            merge_union(
+                [0..=10u8, 5..=20, 9..=3, 21..=21, 250..=255],
+                [22..=30, 40..=251]
+            )
+            .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![5..=5, 250..=255],
+            merge_intersection([0..=5u8, 250..=255], [5..=9, 200..=255]).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![0..=4, 10..=19, 31..=255],
+            merge_difference([0..=255u8], [5..=9, 20..=30]).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![254, 255],
+            values(merge_difference([250..=255u8], [0..=253])).collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+}
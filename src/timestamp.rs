@@ -0,0 +1,277 @@
+//! Sets of timestamps, stored as a `RangeSet<i64>` of nanoseconds since the Unix epoch.
+//!
+//! Timestamps that cannot be represented on an `i64` (before 1677 or after 2262) are never contained,
+//! and the bounds of ranges beyond them are saturated to the representable timestamps.
+
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter, Result},
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::RangeSet;
+
+/// A timestamp type that can be stored in a `RangeSet<i64>`.
+pub trait Timestamp: Sized + Ord {
+    /// Return the number of nanoseconds since the Unix epoch, or `None` if it cannot be represented on an `i64`.
+    fn to_nanos(&self) -> Option<i64>;
+
+    /// Return the timestamp at the given number of nanoseconds since the Unix epoch.
+    fn from_nanos(nanos: i64) -> Self;
+
+    /// Write the timestamp in RFC 3339 format.
+    fn fmt_rfc3339(&self, f: &mut Formatter) -> Result;
+}
+
+#[cfg(feature = "chrono")]
+impl Timestamp for chrono::DateTime<chrono::Utc> {
+    fn to_nanos(&self) -> Option<i64> {
+        self.timestamp_nanos_opt()
+    }
+
+    fn from_nanos(nanos: i64) -> Self {
+        chrono::DateTime::from_timestamp_nanos(nanos)
+    }
+
+    fn fmt_rfc3339(&self, f: &mut Formatter) -> Result {
+        write!(
+            f,
+            "{}",
+            self.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+        )
+    }
+}
+
+#[cfg(feature = "time")]
+impl Timestamp for time::OffsetDateTime {
+    fn to_nanos(&self) -> Option<i64> {
+        i64::try_from(self.unix_timestamp_nanos()).ok()
+    }
+
+    fn from_nanos(nanos: i64) -> Self {
+        // Every i64 number of nanoseconds is within the range supported by `OffsetDateTime`.
+        time::OffsetDateTime::from_unix_timestamp_nanos(nanos as i128).unwrap()
+    }
+
+    fn fmt_rfc3339(&self, f: &mut Formatter) -> Result {
+        let formatted = self
+            .format(&time::format_description::well_known::Rfc3339)
+            .map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", formatted)
+    }
+}
+
+/// Return the number of nanoseconds since the Unix epoch of the timestamp,
+/// or whether it is before or after all the ones that can be represented on an `i64`.
+fn to_nanos_or_side<D: Timestamp>(timestamp: &D) -> std::result::Result<i64, Ordering> {
+    timestamp
+        .to_nanos()
+        .ok_or_else(|| timestamp.cmp(&D::from_nanos(0)))
+}
+
+/// Return the inclusive bounds in nanoseconds of the range of timestamps,
+/// or `None` if it does not contain any timestamp that can be represented on an `i64`.
+fn range_to_nanos<D: Timestamp, R: RangeBounds<D>>(range: &R) -> Option<(i64, i64)> {
+    let min = match range.start_bound() {
+        Bound::Included(t) => match to_nanos_or_side(t) {
+            Ok(nanos) => nanos,
+            Err(Ordering::Less) => i64::MIN,
+            Err(_) => return None,
+        },
+        Bound::Excluded(t) => match to_nanos_or_side(t) {
+            Ok(nanos) => nanos.checked_add(1)?,
+            Err(Ordering::Less) => i64::MIN,
+            Err(_) => return None,
+        },
+        Bound::Unbounded => i64::MIN,
+    };
+    let max = match range.end_bound() {
+        Bound::Included(t) => match to_nanos_or_side(t) {
+            Ok(nanos) => nanos,
+            Err(Ordering::Greater) => i64::MAX,
+            Err(_) => return None,
+        },
+        Bound::Excluded(t) => match to_nanos_or_side(t) {
+            Ok(nanos) => nanos.checked_sub(1)?,
+            Err(Ordering::Greater) => i64::MAX,
+            Err(_) => return None,
+        },
+        Bound::Unbounded => i64::MAX,
+    };
+
+    (min <= max).then_some((min, max))
+}
+
+impl RangeSet<i64> {
+    /// Create a new instance from the given range of timestamps.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # #[cfg(feature = "chrono")] {
+    /// use chrono::{TimeZone, Utc};
+    /// use irange::RangeSet;
+    ///
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+    ///
+    /// let range = RangeSet::new_from_timestamp_range(start..end);
+    /// assert!(range.contains_timestamp(&start));
+    /// assert!(!range.contains_timestamp(&end));
+    /// # }
+    /// ```
+    pub fn new_from_timestamp_range<D: Timestamp, R: RangeBounds<D>>(range: R) -> RangeSet<i64> {
+        match range_to_nanos(&range) {
+            Some((min, max)) => RangeSet(vec![min, max]),
+            None => RangeSet::empty(),
+        }
+    }
+
+    /// Return `true` if it contains the given timestamp, always `false` if it cannot be represented on an `i64`.
+    #[inline]
+    pub fn contains_timestamp<D: Timestamp>(&self, timestamp: &D) -> bool {
+        timestamp
+            .to_nanos()
+            .is_some_and(|nanos| self.contains(nanos))
+    }
+
+    /// Return an iterator over the ranges contained, as inclusive pairs of timestamps.
+    pub fn timestamp_ranges<D: Timestamp>(&self) -> impl Iterator<Item = (D, D)> + '_ {
+        self.0
+            .chunks_exact(2)
+            .map(|bounds| (D::from_nanos(bounds[0]), D::from_nanos(bounds[1])))
+    }
+
+    /// Return a helper to display the ranges with their bounds in RFC 3339 format.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # #[cfg(feature = "chrono")] {
+    /// use chrono::{DateTime, TimeZone, Utc};
+    /// use irange::RangeSet;
+    ///
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+    ///
+    /// let range = RangeSet::new_from_timestamp_range(start..=end);
+    /// assert_eq!(
+    ///     "[ 2024-01-01T00:00:00Z..=2024-01-02T00:00:00Z ]",
+    ///     range.display_rfc3339::<DateTime<Utc>>().to_string()
+    /// );
+    /// # }
+    /// ```
+    pub fn display_rfc3339<D: Timestamp>(&self) -> Rfc3339Display<'_, D> {
+        Rfc3339Display {
+            range_set: self,
+            timestamp: PhantomData,
+        }
+    }
+}
+
+/// A helper to display a `RangeSet` of timestamps in RFC 3339 format.
+///
+/// Constructed with `RangeSet::display_rfc3339`.
+pub struct Rfc3339Display<'a, D: Timestamp> {
+    range_set: &'a RangeSet<i64>,
+    timestamp: PhantomData<D>,
+}
+
+impl<D: Timestamp> Display for Rfc3339Display<'_, D> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "[ ")?;
+        for (min, max) in self.range_set.timestamp_ranges::<D>() {
+            min.fmt_rfc3339(f)?;
+            write!(f, "..=")?;
+            max.fmt_rfc3339(f)?;
+            write!(f, " ")?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_chrono() -> Result {
+        use chrono::{DateTime, TimeZone, Utc};
+
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 1, 1, 0, 0).unwrap();
+
+        let range = RangeSet::new_from_timestamp_range(start..end);
+        assert_eq!(
+            RangeSet(vec![1704067200000000000, 1704070799999999999]),
+            range
+        );
+        assert_eq!(
+            "[ 2024-01-01T00:00:00Z..=2024-01-01T00:59:59.999999999Z ]",
+            range.display_rfc3339::<DateTime<Utc>>().to_string()
+        );
+        assert_eq!(
+            vec![(start, end - chrono::Duration::nanoseconds(1))],
+            range.timestamp_ranges().collect::<Vec<_>>()
+        );
+
+        let far = Utc.with_ymd_and_hms(3000, 1, 1, 0, 0, 0).unwrap();
+        let old = Utc.with_ymd_and_hms(1000, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            RangeSet(vec![1704067200000000000, i64::MAX]),
+            RangeSet::new_from_timestamp_range(start..=far)
+        );
+        assert_eq!(
+            RangeSet(vec![i64::MIN, 1704067199999999999]),
+            RangeSet::new_from_timestamp_range(old..start)
+        );
+        assert_eq!(
+            RangeSet::<i64>::total(),
+            RangeSet::new_from_timestamp_range((Bound::Excluded(old), Bound::Excluded(far)))
+        );
+        assert!(RangeSet::new_from_timestamp_range(far..).is_empty());
+        assert!(RangeSet::new_from_timestamp_range(..=old).is_empty());
+
+        let last = DateTime::<Utc>::from_nanos(i64::MAX);
+        let first = DateTime::<Utc>::from_nanos(i64::MIN);
+        assert!(
+            RangeSet::new_from_timestamp_range((Bound::Excluded(last), Bound::Unbounded))
+                .is_empty()
+        );
+        assert!(RangeSet::new_from_timestamp_range(..first).is_empty());
+
+        let total = RangeSet::<i64>::total();
+        assert!(total.contains_timestamp(&last));
+        assert!(!total.contains_timestamp(&far));
+        assert!(!total.contains_timestamp(&old));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_time() -> Result {
+        use time::{macros::datetime, OffsetDateTime};
+
+        let start = datetime!(2024-01-01 0:00 UTC);
+        let end = datetime!(2024-01-01 1:00 UTC);
+
+        let range = RangeSet::new_from_timestamp_range(start..end);
+        assert_eq!(
+            RangeSet(vec![1704067200000000000, 1704070799999999999]),
+            range
+        );
+        assert_eq!(
+            "[ 2024-01-01T00:00:00Z..=2024-01-01T00:59:59.999999999Z ]",
+            range.display_rfc3339::<OffsetDateTime>().to_string()
+        );
+        assert!(!range.contains_timestamp(&datetime!(2024-01-01 0:30 +01:00)));
+        assert!(range.contains_timestamp(&datetime!(2024-01-01 1:30 +01:00)));
+        assert!(!RangeSet::<i64>::total().contains_timestamp(&datetime!(3000-01-01 0:00 UTC)));
+        assert!(RangeSet::new_from_timestamp_range(datetime!(3000-01-01 0:00 UTC)..).is_empty());
+
+        Ok(())
+    }
+}
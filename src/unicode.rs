@@ -0,0 +1,147 @@
+use regex_syntax::hir::{Class, ClassUnicode, ClassUnicodeRange, HirKind};
+
+use crate::RangeSet;
+
+/// The code points that are Unicode scalar values, the others cannot be converted to `char`.
+const SCALAR_VALUES: [u32; 4] = [0, 0xD7FF, 0xE000, 0x10FFFF];
+
+impl RangeSet<u32> {
+    /// Return the set extended with the code points that are equivalent under Unicode simple case folding.
+    ///
+    /// The code points that are not Unicode scalar values are kept unchanged.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let range = RangeSet::<u32>::new_from_range('a' as u32..='c' as u32);
+    /// let closure = range.case_insensitive_closure();
+    ///
+    /// assert_eq!(
+    ///     RangeSet::new_from_iter(['A' as u32..='C' as u32, 'a' as u32..='c' as u32]),
+    ///     closure
+    /// );
+    /// assert!(RangeSet::<u32>::new_from_range('k' as u32..='k' as u32)
+    ///     .case_insensitive_closure()
+    ///     .contains('\u{212A}' as u32)); // KELVIN SIGN
+    /// ```
+    pub fn case_insensitive_closure(&self) -> RangeSet<u32> {
+        let mut class = to_class(self);
+        class.case_fold_simple();
+        self.union(&from_class(&class))
+    }
+
+    /// Return the code points of the given Unicode General Category, such as `Lu` or `Uppercase_Letter`, `None` if the name is unknown.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    ///
+    /// let uppercase = RangeSet::<u32>::unicode_category("Lu").unwrap();
+    /// assert!(uppercase.contains('A' as u32));
+    /// assert!(uppercase.contains('Σ' as u32));
+    /// assert!(!uppercase.contains('a' as u32));
+    ///
+    /// assert_eq!(None, RangeSet::<u32>::unicode_category("Greek"));
+    /// ```
+    pub fn unicode_category(name: &str) -> Option<RangeSet<u32>> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ' ' || c == '-')
+        {
+            return None;
+        }
+
+        let hir = regex_syntax::parse(&format!(r"\p{{gc={name}}}")).ok()?;
+        match hir.kind() {
+            HirKind::Class(Class::Unicode(class)) => Some(from_class(class)),
+            _ => None,
+        }
+    }
+}
+
+/// Convert the Unicode scalar values of the set to a `ClassUnicode`.
+fn to_class(set: &RangeSet<u32>) -> ClassUnicode {
+    let scalars = set.intersection(&RangeSet(SCALAR_VALUES.to_vec()));
+    ClassUnicode::new(scalars.0.chunks_exact(2).map(|range| {
+        ClassUnicodeRange::new(
+            char::from_u32(range[0]).expect("a Unicode scalar value"),
+            char::from_u32(range[1]).expect("a Unicode scalar value"),
+        )
+    }))
+}
+
+/// Convert a `ClassUnicode` to a set of code points.
+fn from_class(class: &ClassUnicode) -> RangeSet<u32> {
+    RangeSet::new_from_iter(
+        class
+            .ranges()
+            .iter()
+            .map(|range| u32::from(range.start())..=u32::from(range.end())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_insensitive_closure() -> Result<(), String> {
+        assert!(RangeSet::<u32>::empty()
+            .case_insensitive_closure()
+            .is_empty());
+        assert!(RangeSet::<u32>::total()
+            .case_insensitive_closure()
+            .is_total());
+
+        let range = RangeSet::<u32>::new_from_iter([
+            '0' as u32..='9' as u32,
+            'σ' as u32..='σ' as u32,
+            0xD800..=0xDFFF,
+            0x110000..=0x110010,
+        ]);
+        let closure = range.case_insensitive_closure();
+        assert!(closure.contains_all(&range));
+        assert!(closure.contains('Σ' as u32));
+        assert!(closure.contains('ς' as u32));
+        assert_eq!(3 + 10 + 0x800 + 0x11, closure.iter().count());
+        assert_eq!(closure, closure.case_insensitive_closure());
+
+        let ascii = RangeSet::<u32>::new_from_range(0..=0x7F).case_insensitive_closure();
+        assert!(ascii.contains(0x17F)); // LATIN SMALL LETTER LONG S
+        assert!(ascii.contains(0x212A)); // KELVIN SIGN
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unicode_category() -> Result<(), String> {
+        let uppercase = RangeSet::<u32>::unicode_category("Lu").ok_or("Lu")?;
+        assert_eq!(
+            Some(&uppercase),
+            RangeSet::unicode_category("Uppercase_Letter").as_ref()
+        );
+        for c in ['A', 'Z', 'Σ', 'Ж'] {
+            assert!(uppercase.contains(c as u32));
+        }
+        for c in ['a', '0', ' ', 'σ'] {
+            assert!(!uppercase.contains(c as u32));
+        }
+
+        let letters = RangeSet::<u32>::unicode_category("L").ok_or("L")?;
+        assert!(letters.contains_all(&uppercase));
+        assert!(letters.contains_all(&RangeSet::<u32>::unicode_category("Ll").ok_or("Ll")?));
+
+        let digits = RangeSet::<u32>::unicode_category("Nd").ok_or("Nd")?;
+        assert!(digits.contains_all(&RangeSet::new_from_range('0' as u32..='9' as u32)));
+
+        assert_eq!(None, RangeSet::<u32>::unicode_category(""));
+        assert_eq!(None, RangeSet::<u32>::unicode_category("Xx"));
+        assert_eq!(None, RangeSet::<u32>::unicode_category("Lu}|\\p{Ll"));
+
+        Ok(())
+    }
+}
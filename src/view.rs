@@ -0,0 +1,359 @@
+use std::ops::{RangeBounds, RangeInclusive};
+
+use crate::{
+    integer::{Measure, NumericInteger},
+    is_canonical, is_contained_at, range_to_bounds, stream, RangeSet, RangeSetIter,
+};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// The primitive integer types, for which any bit pattern is a valid value.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait PrimitiveInteger: NumericInteger + private::Sealed {
+    /// Convert from the native endianness to little endian.
+    fn to_le(self) -> Self;
+
+    /// Convert from little endian to the native endianness.
+    fn from_le(value: Self) -> Self;
+}
+
+macro_rules! impl_primitive_integer {
+    ($($t:ty),*) => {
+        $(
+            impl private::Sealed for $t {}
+            impl PrimitiveInteger for $t {
+                #[inline]
+                fn to_le(self) -> Self {
+                    <$t>::to_le(self)
+                }
+
+                #[inline]
+                fn from_le(value: Self) -> Self {
+                    <$t>::from_le(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Error returned when a buffer cannot be viewed as a `RangeSetView`.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum ViewError {
+    /// The buffer is not aligned for the integer type.
+    Misaligned,
+    /// The length of the buffer is not a multiple of the size of a pair of bounds.
+    InvalidLength,
+    /// The bounds are not sorted, overlapping or adjacent.
+    NotCanonical,
+}
+
+impl std::fmt::Display for ViewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ViewError::Misaligned => write!(f, "the buffer is not aligned for the integer type"),
+            ViewError::InvalidLength => write!(
+                f,
+                "the length of the buffer is not a multiple of the size of a pair of bounds"
+            ),
+            ViewError::NotCanonical => {
+                write!(f, "the bounds are not sorted, overlapping or adjacent")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ViewError {}
+
+/// A read-only `RangeSet` borrowing its bounds, for instance from a memory-mapped buffer.
+///
+/// The bounds follow the same layout as `RangeSet`: the elements with even index are the lower bounds and the elements with odd index are the upper bounds, all inclusive.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct RangeSetView<'a, T: NumericInteger> {
+    bounds: &'a [T],
+}
+
+impl<'a, T: PrimitiveInteger> RangeSetView<'a, T> {
+    /// Create a view over the given buffer of bounds, stored with the native endianness.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::view::RangeSetView;
+    ///
+    /// let bounds: Vec<u32> = vec![2, 5, 10, 20];
+    /// let bytes: Vec<u8> = bounds.iter().flat_map(|b| b.to_ne_bytes()).collect();
+    ///
+    /// // Copy to a part of a buffer aligned for u32, as a memory-mapped file would be
+    /// let mut storage = vec![0u8; bytes.len() + 3];
+    /// let offset = storage.as_ptr().align_offset(std::mem::align_of::<u32>());
+    /// let buffer = &mut storage[offset..offset + bytes.len()];
+    /// buffer.copy_from_slice(&bytes);
+    ///
+    /// let view = RangeSetView::<u32>::from_bytes(buffer).unwrap();
+    /// assert!(view.contains(15));
+    /// assert_eq!(Some((1, 10..=20)), view.containing_range(15));
+    /// assert_eq!(Some(15u32), view.covered_len());
+    /// assert_eq!(RangeSet(vec![2, 5, 10, 20]), view.to_range_set());
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<RangeSetView<'a, T>, ViewError> {
+        if !bytes.len().is_multiple_of(2 * std::mem::size_of::<T>()) {
+            return Err(ViewError::InvalidLength);
+        }
+        // SAFETY: `T` is a primitive integer type, for which every bit pattern is valid.
+        let (prefix, bounds, suffix) = unsafe { bytes.align_to::<T>() };
+        if !prefix.is_empty() || !suffix.is_empty() {
+            return Err(ViewError::Misaligned);
+        }
+
+        RangeSetView::from_bounds(bounds)
+    }
+}
+
+impl<'a, T: NumericInteger> RangeSetView<'a, T> {
+    /// Create a view over the given bounds, checking that they are in canonical form.
+    pub fn from_bounds(bounds: &'a [T]) -> Result<RangeSetView<'a, T>, ViewError> {
+        if !bounds.len().is_multiple_of(2) {
+            Err(ViewError::InvalidLength)
+        } else if !is_canonical(bounds) {
+            Err(ViewError::NotCanonical)
+        } else {
+            Ok(RangeSetView { bounds })
+        }
+    }
+
+    /// Return the bounds.
+    #[inline]
+    pub fn bounds(&self) -> &'a [T] {
+        self.bounds
+    }
+
+    /// Return an iterator to iterate in order over all the values contained.
+    pub fn iter(&self) -> RangeSetIter<'a, T> {
+        RangeSetIter {
+            bounds: self.bounds,
+            current: None,
+        }
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// Return `true` if it contains all the possible values.
+    #[inline]
+    pub fn is_total(&self) -> bool {
+        self.bounds == [T::min_value(), T::max_value()]
+    }
+
+    /// Return `true` if it contains the given value.
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.containing_range(value).is_some()
+    }
+
+    /// Return the index and the range containing the given value.
+    pub fn containing_range(&self, value: T) -> Option<(usize, RangeInclusive<T>)> {
+        let position = self.bounds.partition_point(|&x| x < value);
+        if is_contained_at(self.bounds, position, value) {
+            let index = position / 2;
+            Some((index, self.bounds[index * 2]..=self.bounds[index * 2 + 1]))
+        } else {
+            None
+        }
+    }
+
+    /// Return an iterator over the ranges, in order.
+    pub fn ranges(&self) -> impl ExactSizeIterator<Item = RangeInclusive<T>> + 'a {
+        self.bounds.chunks_exact(2).map(|range| range[0]..=range[1])
+    }
+
+    /// Return an iterator over the ranges having a common value with the given window, in order.
+    pub fn ranges_overlapping<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = RangeInclusive<T>> + 'a {
+        let (min, max) = range_to_bounds(&range);
+        let start = if min > max {
+            self.bounds.len()
+        } else {
+            let position = self.bounds.partition_point(|&x| x < min);
+            position - position % 2
+        };
+
+        self.bounds[start..]
+            .chunks_exact(2)
+            .take_while(move |bounds| bounds[0] <= max)
+            .map(|bounds| bounds[0]..=bounds[1])
+    }
+
+    /// Return an iterator over the minimum of each range, in order.
+    pub fn starts(&self) -> impl ExactSizeIterator<Item = T> + 'a {
+        self.bounds.chunks_exact(2).map(|range| range[0])
+    }
+
+    /// Return an iterator over the maximum of each range, in order.
+    pub fn ends(&self) -> impl ExactSizeIterator<Item = T> + 'a {
+        self.bounds.chunks_exact(2).map(|range| range[1])
+    }
+
+    /// Return the distance between the given value and the closest value contained, `0` if it is contained and `None` if the view is empty.
+    pub fn distance_to(&self, value: T) -> Option<T::DistanceType> {
+        let position = self.bounds.partition_point(|&x| x < value);
+        if is_contained_at(self.bounds, position, value) {
+            return Some(T::distance_from_u128(0));
+        }
+
+        let below = (position > 0).then(|| T::distance(self.bounds[position - 1], value));
+        let above =
+            (position < self.bounds.len()).then(|| T::distance(value, self.bounds[position]));
+        match (below, above) {
+            (Some(below), Some(above)) => Some(std::cmp::min(below, above)),
+            (below, above) => below.or(above),
+        }
+    }
+
+    /// Return the number of values contained measured with the given type, `None` if it does not fit.
+    pub fn covered_len<M: Measure<T>>(&self) -> Option<M> {
+        self.bounds
+            .chunks_exact(2)
+            .try_fold(M::zero(), |len, range| {
+                len.checked_sum(M::measure(range[0], range[1])?)
+            })
+    }
+
+    /// Return the number of values contained within the given window, saturating at `u128::MAX`.
+    pub fn count_in<R: RangeBounds<T>>(&self, range: R) -> u128 {
+        let (min, max) = range_to_bounds(&range);
+        self.ranges_overlapping(min..=max)
+            .map(|range| {
+                let (range_min, range_max) = range.into_inner();
+                let extent =
+                    T::distance(std::cmp::max(range_min, min), std::cmp::min(range_max, max));
+                T::distance_to_u128(extent)
+            })
+            .fold(0u128, |count, extent| {
+                count.saturating_add(extent).saturating_add(1)
+            })
+    }
+
+    /// Return an iterator over the ranges of values not contained within the given window, in order.
+    pub fn gaps_within<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = RangeInclusive<T>> + 'a {
+        let (min, max) = range_to_bounds(&range);
+        stream::merge_difference(
+            (min <= max).then_some(min..=max),
+            self.ranges_overlapping(min..=max),
+        )
+    }
+
+    /// Return `true` if it contains all the values of the given `RangeSet`.
+    pub fn contains_all(&self, that: &RangeSet<T>) -> bool {
+        that.0.chunks_exact(2).all(|range| {
+            self.containing_range(range[0])
+                .is_some_and(|(_, containing)| *containing.end() >= range[1])
+        })
+    }
+
+    /// Return `true` if there is common value with the given `RangeSet`.
+    pub fn has_intersection(&self, that: &RangeSet<T>) -> bool {
+        let (mut i, mut j) = (0, 0);
+        while i < self.bounds.len() && j < that.0.len() {
+            if self.bounds[i + 1] < that.0[j] {
+                i += 2;
+            } else if that.0[j + 1] < self.bounds[i] {
+                j += 2;
+            } else {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Return a `RangeSet` holding a copy of the bounds.
+    pub fn to_range_set(&self) -> RangeSet<T> {
+        RangeSet(self.bounds.to_vec())
+    }
+}
+
+impl<'a, T: NumericInteger> From<&'a RangeSet<T>> for RangeSetView<'a, T> {
+    fn from(value: &'a RangeSet<T>) -> Self {
+        RangeSetView { bounds: &value.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes() -> Result<(), ViewError> {
+        let bounds: Vec<u64> = vec![2, 5, 10, 20, 22, 22];
+        let bytes: Vec<u8> = bounds.iter().flat_map(|b| b.to_ne_bytes()).collect();
+        let mut storage = vec![0u8; bytes.len() + 16];
+        let offset = storage.as_ptr().align_offset(std::mem::align_of::<u64>());
+        let buffer = &mut storage[offset..offset + bytes.len() + 8];
+        buffer[..bytes.len()].copy_from_slice(&bytes);
+
+        let view = RangeSetView::<u64>::from_bytes(&buffer[..bytes.len()])?;
+        assert_eq!(RangeSet(bounds.clone()), view.to_range_set());
+        assert_eq!(bounds.len(), view.bounds().len());
+        assert!(view.contains(2) && view.contains(20) && view.contains(22));
+        assert!(!view.contains(6) && !view.contains(21) && !view.contains(23));
+        assert!(view.has_intersection(&RangeSet(vec![21, 30])));
+        assert!(!view.has_intersection(&RangeSet(vec![6, 9])));
+        assert_eq!(16, view.iter().count());
+
+        let range_set = view.to_range_set();
+        for value in 0..30 {
+            assert_eq!(
+                range_set.containing_range(value),
+                view.containing_range(value)
+            );
+            assert_eq!(range_set.distance_to(value), view.distance_to(value));
+            assert_eq!(range_set.count_in(value..), view.count_in(value..));
+            assert!(range_set
+                .gaps_within(..=value)
+                .eq(view.gaps_within(..=value)));
+            assert!(range_set
+                .ranges_overlapping(value..=value + 3)
+                .eq(view.ranges_overlapping(value..=value + 3)));
+            let that = RangeSet::new_from_range(value..=value + 3);
+            assert_eq!(range_set.contains_all(&that), view.contains_all(&that));
+        }
+        assert_eq!(range_set.covered_len::<u64>(), view.covered_len());
+        assert!(range_set.starts().eq(view.starts()));
+        assert!(range_set.ends().eq(view.ends()));
+        assert_eq!(3, view.ranges().len());
+
+        assert_eq!(
+            Err(ViewError::Misaligned),
+            RangeSetView::<u64>::from_bytes(&buffer[1..bytes.len() + 1])
+        );
+        assert_eq!(
+            Err(ViewError::InvalidLength),
+            RangeSetView::<u64>::from_bytes(&buffer[..8])
+        );
+
+        assert_eq!(
+            Err(ViewError::NotCanonical),
+            RangeSetView::from_bounds(&[2u8, 5, 6, 9])
+        );
+        assert_eq!(
+            Err(ViewError::NotCanonical),
+            RangeSetView::from_bounds(&[5u8, 2])
+        );
+        assert!(RangeSetView::from_bounds(&[0u8, 255])?.is_total());
+
+        Ok(())
+    }
+}
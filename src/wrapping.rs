@@ -0,0 +1,264 @@
+use std::cmp::Ordering;
+
+use crate::{
+    integer::{Arithmetic, Bounded, NumericInteger},
+    RangeSet,
+};
+
+/// A structure holding a collection of values of a circular domain, where a range can wrap past `T::max_value()` back to `T::min_value()`.
+///
+/// Useful for sequence numbers, positions in a ring buffer or angles.
+/// The values are stored in a `RangeSet`, so a wrapping range such as `250..=5` is split in two at the end of the domain.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct WrappingRangeSet<T: NumericInteger>(RangeSet<T>);
+
+impl<T: NumericInteger> std::fmt::Display for WrappingRangeSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[ ")?;
+        for (min, max) in self.ranges() {
+            write!(f, "{}..={} ", min, max)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<T: NumericInteger> WrappingRangeSet<T> {
+    /// Create a new instance that does not contain any value.
+    #[inline]
+    pub fn empty() -> WrappingRangeSet<T> {
+        WrappingRangeSet(RangeSet::empty())
+    }
+
+    /// Create a new instance that contains all possible values.
+    #[inline]
+    pub fn total() -> WrappingRangeSet<T> {
+        WrappingRangeSet(RangeSet::total())
+    }
+
+    /// Create a new instance from the values going from `min` to `max` included, wrapping around the domain if `min > max`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::RangeSet;
+    /// use irange::wrapping::WrappingRangeSet;
+    ///
+    /// let range = WrappingRangeSet::<u8>::new_from_range(250, 5);
+    /// assert_eq!(&RangeSet(vec![0, 5, 250, 255]), range.as_range_set());
+    /// assert_eq!(vec![(250, 5)], range.ranges().collect::<Vec<_>>());
+    /// ```
+    pub fn new_from_range(min: T, max: T) -> WrappingRangeSet<T> {
+        if min <= max {
+            WrappingRangeSet(RangeSet(vec![min, max]))
+        } else if min == max + T::one() {
+            WrappingRangeSet::total()
+        } else {
+            WrappingRangeSet(RangeSet(vec![T::min_value(), max, min, T::max_value()]))
+        }
+    }
+
+    /// Return the values contained as a `RangeSet`.
+    #[inline]
+    pub fn as_range_set(&self) -> &RangeSet<T> {
+        &self.0
+    }
+
+    /// Return `true` if it does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Return `true` if it contains all the possible values.
+    #[inline]
+    pub fn is_total(&self) -> bool {
+        self.0.is_total()
+    }
+
+    /// Return `true` if it contains the given value.
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.0.contains(value)
+    }
+
+    /// Return an iterator over the maximal ranges as `(min, max)`, where `min > max` for the range wrapping around the domain.
+    ///
+    /// The wrapping range is returned first.
+    pub fn ranges(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        let bounds = &self.0 .0;
+        let wraps = bounds.len() >= 4
+            && bounds[0] == T::min_value()
+            && bounds[bounds.len() - 1] == T::max_value();
+        let (wrapping, bounds) = if wraps {
+            (
+                Some((bounds[bounds.len() - 2], bounds[1])),
+                &bounds[2..bounds.len() - 2],
+            )
+        } else {
+            (None, &bounds[..])
+        };
+
+        wrapping
+            .into_iter()
+            .chain(bounds.chunks_exact(2).map(|range| (range[0], range[1])))
+    }
+
+    /// Return the union with the given `WrappingRangeSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::wrapping::WrappingRangeSet;
+    ///
+    /// let range1 = WrappingRangeSet::<u8>::new_from_range(250, 5);
+    /// let range2 = WrappingRangeSet::<u8>::new_from_range(3, 10);
+    ///
+    /// assert_eq!(WrappingRangeSet::new_from_range(250, 10), range1.union(&range2));
+    /// ```
+    pub fn union(&self, that: &WrappingRangeSet<T>) -> WrappingRangeSet<T> {
+        WrappingRangeSet(self.0.union(&that.0))
+    }
+
+    /// Return the intersection with the given `WrappingRangeSet`.
+    pub fn intersection(&self, that: &WrappingRangeSet<T>) -> WrappingRangeSet<T> {
+        WrappingRangeSet(self.0.intersection(&that.0))
+    }
+
+    /// Return the difference with the given `WrappingRangeSet`.
+    pub fn difference(&self, that: &WrappingRangeSet<T>) -> WrappingRangeSet<T> {
+        WrappingRangeSet(self.0.difference(&that.0))
+    }
+
+    /// Return the complement.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use irange::wrapping::WrappingRangeSet;
+    ///
+    /// let range = WrappingRangeSet::<u8>::new_from_range(3, 10);
+    ///
+    /// assert_eq!(WrappingRangeSet::new_from_range(11, 2), range.complement());
+    /// ```
+    pub fn complement(&self) -> WrappingRangeSet<T> {
+        WrappingRangeSet(self.0.complement())
+    }
+}
+
+impl<T: NumericInteger> From<RangeSet<T>> for WrappingRangeSet<T> {
+    fn from(value: RangeSet<T>) -> Self {
+        WrappingRangeSet(value)
+    }
+}
+
+impl<T: NumericInteger> From<WrappingRangeSet<T>> for RangeSet<T> {
+    fn from(value: WrappingRangeSet<T>) -> Self {
+        value.0
+    }
+}
+
+/// Compare two serial numbers as defined by RFC 1982: `a` is lower than `b` if `b` follows `a` by less than half of the domain.
+///
+/// Return `None` if `b` is exactly half of the domain away from `a`, since the order is undefined.
+///
+/// # Example:
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use irange::wrapping::serial_cmp;
+///
+/// assert_eq!(Some(Ordering::Less), serial_cmp::<u8>(250, 5));
+/// assert_eq!(Some(Ordering::Greater), serial_cmp::<u8>(5, 250));
+/// assert_eq!(Some(Ordering::Less), serial_cmp::<u8>(5, 10));
+/// assert_eq!(None, serial_cmp::<u8>(0, 128));
+/// ```
+pub fn serial_cmp<T: NumericInteger>(a: T, b: T) -> Option<Ordering> {
+    let one = T::DistanceType::one();
+    let half = T::distance(T::min_value(), T::max_value()).div_euclid(one + one) + one;
+
+    match a.cmp(&b) {
+        Ordering::Equal => Some(Ordering::Equal),
+        Ordering::Less => match T::distance(a, b).cmp(&half) {
+            Ordering::Less => Some(Ordering::Less),
+            Ordering::Greater => Some(Ordering::Greater),
+            Ordering::Equal => None,
+        },
+        Ordering::Greater => match T::distance(b, a).cmp(&half) {
+            Ordering::Less => Some(Ordering::Greater),
+            Ordering::Greater => Some(Ordering::Less),
+            Ordering::Equal => None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges() -> Result<(), String> {
+        assert_eq!(0, WrappingRangeSet::<u8>::empty().ranges().count());
+        assert_eq!(
+            vec![(0, 255)],
+            WrappingRangeSet::<u8>::total().ranges().collect::<Vec<_>>()
+        );
+        assert!(WrappingRangeSet::<u8>::new_from_range(6, 5).is_total());
+        assert_eq!(
+            vec![(-128, 127)],
+            WrappingRangeSet::<i8>::new_from_range(-128, 127)
+                .ranges()
+                .collect::<Vec<_>>()
+        );
+
+        let range = WrappingRangeSet::<i8>::new_from_range(120, -120)
+            .union(&WrappingRangeSet::new_from_range(0, 10))
+            .union(&WrappingRangeSet::new_from_range(50, 50));
+        assert_eq!(
+            vec![(120, -120), (0, 10), (50, 50)],
+            range.ranges().collect::<Vec<_>>()
+        );
+        assert_eq!("[ 120..=-120 0..=10 50..=50 ]", range.to_string());
+        assert_eq!(
+            vec![(-119, -1), (11, 49), (51, 119)],
+            range.complement().ranges().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(127, -128), (5, 10)],
+            range
+                .intersection(
+                    &WrappingRangeSet::new_from_range(5, 10)
+                        .union(&WrappingRangeSet::new_from_range(127, -128))
+                )
+                .ranges()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(120, -120), (0, 4)],
+            range
+                .difference(&WrappingRangeSet::new_from_range(5, 100))
+                .ranges()
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_serial_cmp() -> Result<(), String> {
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                let expected = match b.wrapping_sub(a) {
+                    0 => Some(Ordering::Equal),
+                    1..=127 => Some(Ordering::Less),
+                    128 => None,
+                    _ => Some(Ordering::Greater),
+                };
+                assert_eq!(expected, serial_cmp(a, b));
+            }
+        }
+        assert_eq!(Some(Ordering::Less), serial_cmp(i128::MAX, i128::MIN));
+        assert_eq!(None, serial_cmp(u128::MAX, u128::MAX / 2));
+
+        Ok(())
+    }
+}